@@ -319,6 +319,22 @@ where
         })
     });
 
+    group.bench_function(format!("grow larger align pre-aligned x {SIZE}"), |b| {
+        b.iter(|| {
+            for _ in 0..SIZE {
+                unsafe {
+                    black_box((&alloc).allocate(Layout::new::<u64>()).unwrap());
+                    let ptr = (&alloc).allocate(Layout::new::<u32>()).unwrap();
+                    let ptr = (&alloc)
+                        .grow(ptr.cast(), Layout::new::<u32>(), Layout::new::<u64>())
+                        .unwrap();
+                    black_box(ptr);
+                }
+            }
+            alloc.reset();
+        })
+    });
+
     group.bench_function(format!("shrink same align x {SIZE}"), |b| {
         b.iter(|| {
             for _ in 0..SIZE {
@@ -516,6 +532,172 @@ where
     group.finish();
 }
 
+fn bench_from_exact_iter(c: &mut Criterion) {
+    let mut group = c.benchmark_group("from-exact-iter/blink_alloc::BlinkAlloc");
+
+    reset_mem_stat();
+    let mut blink = Blink::<BlinkAlloc>::default();
+
+    // Pre-warm the allocator
+    blink.emplace_no_drop().from_iter(0..65536usize);
+    blink.reset();
+
+    group.bench_function(format!("range generic x {SIZE}"), |b| {
+        b.iter(|| {
+            for _ in 0..SIZE {
+                black_box(blink.emplace_no_drop().from_iter(0..black_box(111usize)));
+            }
+            blink.reset();
+        })
+    });
+
+    print_mem_stat();
+    reset_mem_stat();
+
+    group.bench_function(format!("range exact x {SIZE}"), |b| {
+        b.iter(|| {
+            for _ in 0..SIZE {
+                black_box(
+                    blink
+                        .emplace_no_drop()
+                        .from_exact_iter(0..black_box(111usize)),
+                );
+            }
+            blink.reset();
+        })
+    });
+
+    print_mem_stat();
+
+    group.finish();
+}
+
+/// Collects many tiny slices.
+/// Values that need dropping are prefixed with drop list header
+/// which dominates memory usage for short slices.
+fn bench_tiny_slices(c: &mut Criterion) {
+    struct Tiny(u32);
+
+    impl Drop for Tiny {
+        #[inline(always)]
+        fn drop(&mut self) {
+            black_box(self.0);
+        }
+    }
+
+    const LEN: u32 = 2;
+
+    let mut group = c.benchmark_group("tiny-slices/blink_alloc::BlinkAlloc");
+    let mut blink = Blink::<BlinkAlloc>::default();
+
+    // Pre-warm the allocator.
+    for _ in 0..SIZE {
+        blink.emplace().from_iter((0..LEN).map(Tiny));
+    }
+    blink.reset();
+
+    group.bench_function(format!("drop x {SIZE}"), |b| {
+        b.iter(|| {
+            for _ in 0..SIZE {
+                black_box(blink.emplace().from_iter((0..black_box(LEN)).map(Tiny)));
+            }
+            blink.reset();
+        })
+    });
+
+    group.bench_function(format!("no-drop x {SIZE}"), |b| {
+        b.iter(|| {
+            for _ in 0..SIZE {
+                black_box(
+                    blink
+                        .emplace_no_drop()
+                        .from_iter((0..black_box(LEN)).map(Tiny)),
+                );
+            }
+            blink.reset();
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_single_thread(c: &mut Criterion) {
+    let mut group = c.benchmark_group("single-thread");
+
+    let mut blink = BlinkAlloc::new();
+    blink.allocate(Layout::new::<[u32; 65536]>()).unwrap();
+    blink.reset();
+
+    group.bench_function(format!("BlinkAlloc x {SIZE}"), |b| {
+        b.iter(|| {
+            for _ in 0..SIZE {
+                black_box(blink.allocate(Layout::new::<u32>()).unwrap());
+            }
+            blink.reset();
+        })
+    });
+
+    let mut sync = SyncBlinkAlloc::new();
+    sync.allocate(Layout::new::<[u32; 65536]>()).unwrap();
+    sync.reset();
+
+    group.bench_function(format!("SyncBlinkAlloc x {SIZE}"), |b| {
+        b.iter(|| {
+            for _ in 0..SIZE {
+                black_box(sync.allocate(Layout::new::<u32>()).unwrap());
+            }
+            sync.reset();
+        })
+    });
+
+    let mut local = sync.local();
+    local.allocate(Layout::new::<[u32; 65536]>()).unwrap();
+    local.reset();
+
+    group.bench_function(format!("LocalBlinkAlloc x {SIZE}"), |b| {
+        b.iter(|| {
+            for _ in 0..SIZE {
+                black_box(local.allocate(Layout::new::<u32>()).unwrap());
+            }
+            local.reset();
+        })
+    });
+
+    drop(local);
+    group.finish();
+}
+
+/// Allocates from many threads concurrently,
+/// comparing single arena with per-thread shards.
+/// Thread spawning cost is included in both cases.
+fn bench_sharded(c: &mut Criterion) {
+    let mut group = c.benchmark_group("multi-thread/blink_alloc::SyncBlinkAlloc");
+
+    for threads in [8, 16] {
+        for (name, mut sync) in [
+            ("single", SyncBlinkAlloc::new()),
+            ("sharded", SyncBlinkAlloc::new_sharded(threads)),
+        ] {
+            group.bench_function(format!("{name} {threads} threads x {SIZE}"), |b| {
+                b.iter(|| {
+                    std::thread::scope(|scope| {
+                        for _ in 0..threads {
+                            scope.spawn(|| {
+                                for _ in 0..SIZE {
+                                    black_box(sync.allocate(Layout::new::<u32>()).unwrap());
+                                }
+                            });
+                        }
+                    });
+                    sync.reset();
+                })
+            });
+        }
+    }
+
+    group.finish();
+}
+
 pub fn criterion_benchmark(c: &mut Criterion) {
     bench_alloc::<BlinkAlloc>("blink_alloc::BlinkAlloc", c);
     bench_alloc::<SyncBlinkAlloc>("blink_alloc::SyncBlinkAlloc", c);
@@ -532,6 +714,11 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     bench_from_iter::<Blink<BlinkAlloc>>("blink_alloc::BlinkAlloc", c);
     bench_from_iter::<Blink<SyncBlinkAlloc>>("blink_alloc::SyncBlinkAlloc", c);
     bench_from_iter::<bumpalo::Bump>("bumpalo::Bump", c);
+
+    bench_from_exact_iter(c);
+    bench_tiny_slices(c);
+    bench_single_thread(c);
+    bench_sharded(c);
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -0,0 +1,132 @@
+//! This module provides registry of allocation sites
+//! used by [`BlinkAlloc`](crate::BlinkAlloc) in debug builds
+//! with "debug-alloc-tracking" feature.
+
+use core::{
+    cell::{Cell, RefCell},
+    panic::Location,
+    ptr::NonNull,
+};
+
+use std::alloc::System;
+
+use allocator_api2::vec::Vec;
+
+/// Live allocation and location of the code that made it.
+struct AllocSite {
+    addr: usize,
+    seq: usize,
+    location: &'static Location<'static>,
+}
+
+/// Registry of live allocations.
+///
+/// Allocations are kept in order they were made.
+/// Most recent allocations are searched first,
+/// as they are the most likely to be deallocated or resized.
+///
+/// Registry allocates from system allocator,
+/// so it never allocates from the allocator being tracked,
+/// even if that one serves as global allocator.
+/// Allocations made while the registry is borrowed,
+/// e.g. from the callback of [`AllocSites::for_each`], are not recorded.
+pub(crate) struct AllocSites {
+    sites: RefCell<Vec<AllocSite, System>>,
+    next_seq: Cell<usize>,
+}
+
+impl AllocSites {
+    pub const fn new() -> Self {
+        AllocSites {
+            sites: RefCell::new(Vec::new_in(System)),
+            next_seq: Cell::new(0),
+        }
+    }
+
+    /// Returns position to roll back to with [`AllocSites::rollback`].
+    #[inline(always)]
+    pub fn checkpoint(&self) -> usize {
+        self.next_seq.get()
+    }
+
+    /// Records new allocation.
+    #[inline]
+    pub fn record(&self, ptr: NonNull<u8>, location: &'static Location<'static>) {
+        let Ok(mut sites) = self.sites.try_borrow_mut() else {
+            return;
+        };
+        let seq = self.next_seq.get();
+        self.next_seq.set(seq + 1);
+        sites.push(AllocSite {
+            addr: crate::addr(ptr.as_ptr()),
+            seq,
+            location,
+        });
+    }
+
+    /// Forgets deallocated allocation.
+    #[inline]
+    pub fn remove(&self, ptr: NonNull<u8>) {
+        let addr = crate::addr(ptr.as_ptr());
+        let Ok(mut sites) = self.sites.try_borrow_mut() else {
+            return;
+        };
+        if let Some(idx) = sites.iter().rposition(|site| site.addr == addr) {
+            sites.remove(idx);
+        }
+    }
+
+    /// Updates address of resized allocation.
+    /// Allocations that were not recorded,
+    /// e.g. zero-sized ones, are recorded with `location`.
+    #[inline]
+    pub fn resize(
+        &self,
+        old_ptr: NonNull<u8>,
+        new_ptr: NonNull<u8>,
+        location: &'static Location<'static>,
+    ) {
+        let old_addr = crate::addr(old_ptr.as_ptr());
+        let Ok(mut sites) = self.sites.try_borrow_mut() else {
+            return;
+        };
+        match sites.iter_mut().rev().find(|site| site.addr == old_addr) {
+            Some(site) => site.addr = crate::addr(new_ptr.as_ptr()),
+            None => {
+                drop(sites);
+                self.record(new_ptr, location);
+            }
+        }
+    }
+
+    /// Forgets allocations made after the checkpoint.
+    #[inline]
+    pub fn rollback(&self, checkpoint: usize) {
+        self.sites.borrow_mut().retain(|site| site.seq < checkpoint);
+    }
+
+    /// Calls `f` with address and location of each live allocation
+    /// in order they were made.
+    #[inline]
+    pub fn for_each(&self, mut f: impl FnMut(usize, &'static Location<'static>)) {
+        for site in self.sites.borrow().iter() {
+            f(site.addr, site.location);
+        }
+    }
+
+    /// Logs all live allocations to stderr and forgets them.
+    #[cold]
+    pub fn report(&self) {
+        let sites = match self.sites.try_borrow_mut() {
+            Ok(mut sites) => core::mem::replace(&mut *sites, Vec::new_in(System)),
+            Err(_) => return,
+        };
+        for site in sites {
+            std::eprintln!(
+                "blink-alloc: allocation {:#x} made at {} is live on reset",
+                site.addr,
+                site.location
+            );
+        }
+    }
+}
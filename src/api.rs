@@ -1,5 +1,7 @@
 use allocator_api2::alloc::Allocator;
 
+use crate::arena::BlinkStats;
+
 /// Extension trait for [`Allocator`] that defines blink allocator API.
 /// Blink-allocators are allocators with cheap allocation
 /// and potentially no-op deallocation.
@@ -12,7 +14,7 @@ use allocator_api2::alloc::Allocator;
 /// Additional guarantees are provided that
 ///
 /// * [`Allocator::shrink`] will always succeed and never move memory
-/// when `ptr` is already aligned to `new_layout.align()`.
+///   when `ptr` is already aligned to `new_layout.align()`.
 ///
 /// # Safety
 ///
@@ -41,6 +43,26 @@ pub unsafe trait BlinkAllocator: Allocator {
     ///
     /// [`Vec`]: alloc::vec::Vec
     fn reset(&mut self);
+
+    /// Returns memory usage statistics of this allocator
+    /// if it is able to report them.
+    ///
+    /// Default implementation returns `None`.
+    #[inline(always)]
+    fn blink_stats(&self) -> Option<BlinkStats> {
+        None
+    }
+
+    /// Returns `true` if nothing is allocated from this allocator
+    /// since creation or last reset.
+    ///
+    /// Default implementation checks used bytes in
+    /// [`blink_stats`](BlinkAllocator::blink_stats)
+    /// and returns `false` if allocator does not report them.
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        matches!(self.blink_stats(), Some(stats) if stats.used == 0)
+    }
 }
 
 unsafe impl<A> BlinkAllocator for &A
@@ -49,6 +71,16 @@ where
 {
     #[inline]
     fn reset(&mut self) {}
+
+    #[inline]
+    fn blink_stats(&self) -> Option<BlinkStats> {
+        A::blink_stats(self)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        A::is_empty(self)
+    }
 }
 
 unsafe impl<'a, A> BlinkAllocator for &'a mut A
@@ -60,4 +92,14 @@ where
     fn reset(&mut self) {
         A::reset(self);
     }
+
+    #[inline]
+    fn blink_stats(&self) -> Option<BlinkStats> {
+        A::blink_stats(self)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        A::is_empty(self)
+    }
 }
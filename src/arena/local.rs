@@ -1,11 +1,18 @@
 use super::*;
 
-with_cursor!(Cell<*mut u8>);
+with_cursor!(Cell<*mut u8>, ());
 
 /// Thread-local arena allocator.
 pub struct ArenaLocal {
     root: Cell<Option<NonNull<ChunkHeader>>>,
     min_chunk_size: Cell<usize>,
+
+    // Number of unused older chunks kept by `reset_partial`.
+    // Chain is searched for them only while this is non-zero.
+    unused_chunks: Cell<usize>,
+
+    // Chunk capacity ignores spare memory provided by the allocator.
+    exact_chunks: bool,
 }
 
 /// It is safe to send `ArenaLocal` between threads.
@@ -27,6 +34,8 @@ impl ArenaLocal {
         ArenaLocal {
             root: Cell::new(None),
             min_chunk_size: Cell::new(CHUNK_START_SIZE),
+            unused_chunks: Cell::new(0),
+            exact_chunks: false,
         }
     }
 
@@ -34,12 +43,25 @@ impl ArenaLocal {
     pub const fn with_chunk_size(min_chunk_size: usize) -> Self {
         ArenaLocal {
             root: Cell::new(None),
-            min_chunk_size: Cell::new(min_chunk_size),
+            min_chunk_size: Cell::new(checked_chunk_size(min_chunk_size)),
+            unused_chunks: Cell::new(0),
+            exact_chunks: false,
         }
     }
 
     #[inline(always)]
-    #[cfg(feature = "sync")]
+    pub fn set_exact_chunks(&mut self) {
+        self.exact_chunks = true;
+    }
+
+    /// Uses borrowed memory as the first chunk.
+    /// Arena must be empty.
+    #[inline(always)]
+    pub fn set_borrowed_chunk(&mut self, bytes: &'static mut [MaybeUninit<u8>]) {
+        set_borrowed_chunk(&self.root, bytes)
+    }
+
+    #[inline(always)]
     pub fn last_chunk_size(&self) -> usize {
         match self.root.get() {
             None => 0,
@@ -50,21 +72,103 @@ impl ArenaLocal {
         }
     }
 
+    #[inline(always)]
+    pub fn min_chunk_size(&self) -> usize {
+        self.min_chunk_size.get()
+    }
+
+    #[inline(always)]
+    pub fn last_chunk_free(&self) -> usize {
+        match self.root.get() {
+            None => 0,
+            Some(root) => {
+                // Safety: `root` is a valid pointer to chunk allocation.
+                let root = unsafe { root.as_ref() };
+                // Safety: `cursor` is within chunk memory.
+                unsafe { root.offset_from_end(root.cursor.get()) }
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub fn used_bytes(&self) -> usize {
+        used_bytes(self.root.get())
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.used_bytes() == 0
+    }
+
+    #[inline]
+    pub fn owns(&self, ptr: *const u8) -> bool {
+        owns(self.root.get(), ptr)
+    }
+
+    #[inline]
+    pub fn chunks(&self) -> ChunkIter<'_> {
+        chunks(self.root.get())
+    }
+
+    #[inline]
+    pub fn for_each_used_byte_range(&self, f: impl FnMut(*const u8, usize)) {
+        for_each_used_byte_range(self.root.get(), f)
+    }
+
+    #[inline]
+    pub fn stats(&self) -> BlinkStats {
+        BlinkStats::collect(self.chunks())
+    }
+
+    #[inline]
+    pub fn minimum_viable_chunk_size(&self) -> usize {
+        minimum_viable_chunk_size(self.chunks())
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn collect_chunk_sizes(&self, sizes: &mut alloc::vec::Vec<usize>) {
+        collect_chunk_sizes(self.root.get(), sizes)
+    }
+
     #[inline(always)]
     pub unsafe fn alloc_fast(&self, layout: Layout) -> Option<NonNull<[u8]>> {
+        if layout.size() == 0 {
+            return Some(dangling_slice(&layout));
+        }
         if let Some(root) = self.root.get() {
             return unsafe { ChunkHeader::alloc(root, layout) };
         }
         None
     }
 
+    /// Allocates from unused chunk kept by `reset_partial`.
+    /// Chain is searched only while such chunks remain.
+    #[inline(always)]
+    pub fn alloc_unused(&self, layout: Layout) -> Option<NonNull<[u8]>> {
+        if self.unused_chunks.get() == 0 {
+            return None;
+        }
+        // Safety: `ArenaLocal` is not shared between threads.
+        let ptr = unsafe { alloc_unused(&self.root, layout)? };
+        self.unused_chunks.set(self.unused_chunks.get() - 1);
+        Some(ptr)
+    }
+
+    /// Always allocates new chunk.
     #[inline(always)]
     pub unsafe fn alloc_slow(
         &self,
         layout: Layout,
         allocator: impl Allocator,
     ) -> Result<NonNull<[u8]>, AllocError> {
-        alloc_slow(&self.root, self.min_chunk_size.get(), layout, allocator)
+        alloc_slow(
+            &self.root,
+            self.min_chunk_size.get(),
+            layout,
+            allocator,
+            self.exact_chunks,
+        )
     }
 
     #[inline(always)]
@@ -95,6 +199,7 @@ impl ArenaLocal {
             old_layout,
             new_layout,
             allocator,
+            self.exact_chunks,
         )
     }
 
@@ -105,17 +210,67 @@ impl ArenaLocal {
 
     #[inline(always)]
     pub unsafe fn reset(&mut self, keep_last: bool, allocator: impl Allocator) {
+        self.unused_chunks.set(0);
         unsafe { reset(&self.root, keep_last, allocator) }
     }
 
+    /// # Safety
+    ///
+    /// See [`reset_partial`].
+    #[inline(always)]
+    pub unsafe fn reset_partial(&mut self, keep_n: usize, allocator: impl Allocator) {
+        let kept_n = unsafe { reset_partial(&self.root, keep_n, allocator) };
+        // Newest kept chunk is the current one.
+        self.unused_chunks.set(kept_n.saturating_sub(1));
+    }
+
+    #[inline(always)]
+    pub unsafe fn reset_shrink(&mut self, allocator: impl Allocator) {
+        self.unused_chunks.set(0);
+        unsafe {
+            reset_shrink(
+                &self.root,
+                self.min_chunk_size.get(),
+                allocator,
+                self.exact_chunks,
+            )
+        }
+    }
+
     #[inline(always)]
     pub unsafe fn reset_unchecked(&self, keep_last: bool, allocator: impl Allocator) {
+        self.unused_chunks.set(0);
         unsafe { reset(&self.root, keep_last, allocator) }
     }
 
-    #[cfg(feature = "sync")]
+    #[inline(always)]
+    pub fn checkpoint(&self) -> Checkpoint {
+        checkpoint(self.root.get())
+    }
+
+    /// # Safety
+    ///
+    /// See [`rollback`].
+    #[inline(always)]
+    pub unsafe fn rollback(&mut self, checkpoint: Checkpoint, allocator: impl Allocator) {
+        unsafe { rollback(&self.root, checkpoint, allocator) }
+    }
+
     #[inline(always)]
     pub fn reset_leak(&mut self, keep_last: bool) {
+        self.unused_chunks.set(0);
         reset_leak(&self.root, keep_last)
     }
+
+    /// Takes the last chunk of `other` arena and makes it
+    /// current chunk of this arena.
+    ///
+    /// # Safety
+    ///
+    /// See [`steal_last_chunk`].
+    #[cfg(feature = "sync")]
+    #[inline(always)]
+    pub unsafe fn steal_last_chunk(&mut self, other: &mut ArenaLocal) {
+        unsafe { steal_last_chunk(&self.root, &other.root) }
+    }
 }
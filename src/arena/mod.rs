@@ -6,17 +6,18 @@
 use core::{
     alloc::Layout,
     cell::Cell,
-    mem::{align_of, size_of},
+    marker::PhantomData,
+    mem::{align_of, size_of, MaybeUninit},
     ptr::{self, NonNull},
     sync::atomic::{AtomicPtr, Ordering},
 };
 
 use allocator_api2::alloc::{AllocError, Allocator};
 
-use crate::cold;
+use crate::{cold, without_provenance_mut};
 
 #[cfg(feature = "sync")]
-use parking_lot::RwLock;
+use parking_lot::{RwLock, RwLockReadGuard};
 
 #[inline(always)]
 fn is_aligned_to(value: usize, align: usize) -> bool {
@@ -46,10 +47,22 @@ fn layout_sum(layout: &Layout) -> usize {
     layout.size() + (layout.align() - 1)
 }
 
+/// Returns dangling pointer aligned to the layout alignment.
+/// Zero-sized allocations are served with it
+/// without touching chunks.
+#[inline(always)]
+fn dangling_slice(layout: &Layout) -> NonNull<[u8]> {
+    debug_assert_eq!(layout.size(), 0);
+    let ptr = without_provenance_mut::<u8>(layout.align());
+    // Safety: alignment is never zero.
+    unsafe { NonNull::new_unchecked(core::ptr::slice_from_raw_parts_mut(ptr, 0)) }
+}
+
 pub(crate) trait CasPtr {
     #[allow(dead_code)]
     fn new(value: *mut u8) -> Self;
     fn load(&self, order: Ordering) -> *mut u8;
+    #[allow(dead_code)]
     fn set(&mut self, value: *mut u8);
     fn compare_exchange(
         &self,
@@ -159,6 +172,129 @@ impl CasPtr for AtomicPtr<u8> {
     }
 }
 
+/// Information about a memory chunk of blink allocator.
+#[derive(Clone, Copy, Debug)]
+pub struct ChunkInfo {
+    base: NonNull<u8>,
+    cap: usize,
+    used: usize,
+}
+
+impl ChunkInfo {
+    /// Returns pointer to the start of usable memory of the chunk.
+    #[inline(always)]
+    pub fn base(&self) -> NonNull<u8> {
+        self.base
+    }
+
+    /// Returns capacity of the chunk in bytes.
+    #[inline(always)]
+    pub fn cap(&self) -> usize {
+        self.cap
+    }
+
+    /// Returns number of bytes used by allocations from the chunk
+    /// at the moment of inspection.
+    #[inline(always)]
+    pub fn used(&self) -> usize {
+        self.used
+    }
+}
+
+/// Snapshot of blink allocator memory statistics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BlinkStats {
+    /// Number of chunks owned by the allocator.
+    pub chunks: usize,
+
+    /// Total capacity of all chunks in bytes.
+    pub capacity: usize,
+
+    /// Number of bytes used by allocations in all chunks.
+    pub used: usize,
+}
+
+impl BlinkStats {
+    fn collect(chunks: ChunkIter<'_>) -> Self {
+        chunks.fold(BlinkStats::default(), |stats, chunk| BlinkStats {
+            chunks: stats.chunks + 1,
+            capacity: stats.capacity + chunk.cap(),
+            used: stats.used + chunk.used(),
+        })
+    }
+}
+
+/// Reads chunk info and pointer to the previous chunk.
+type ReadChunk = unsafe fn(NonNull<u8>) -> (ChunkInfo, Option<NonNull<u8>>);
+
+/// Iterator over memory chunks of blink allocator.
+/// Yields chunks from the newest to the oldest.
+pub struct ChunkIter<'a> {
+    next: Option<NonNull<u8>>,
+    read: ReadChunk,
+    marker: PhantomData<&'a ()>,
+
+    // Shards to walk after current chunk list ends.
+    #[cfg(feature = "sync")]
+    shards: &'a [ArenaShard],
+
+    // Read lock of the sync arena being walked.
+    // Keeps epoch reclamation from freeing chunks under the iterator.
+    #[cfg(feature = "sync")]
+    lock: Option<RwLockReadGuard<'a, self::sync::Inner>>,
+}
+
+impl Iterator for ChunkIter<'_> {
+    type Item = ChunkInfo;
+
+    #[inline]
+    fn next(&mut self) -> Option<ChunkInfo> {
+        #[cfg(feature = "sync")]
+        while self.next.is_none() {
+            let (shard, rest) = self.shards.split_first()?;
+            self.shards = rest;
+            let (lock, root) = shard.lock_chunks();
+            self.lock = Some(lock);
+            self.next = root.map(NonNull::cast);
+        }
+
+        let chunk = self.next?;
+
+        // Safety: `chunk` is a valid pointer to chunk allocation
+        // that lives as long as borrow of the allocator.
+        // Chunks of sync arena are reclaimed only under write lock,
+        // and iterator holds read lock.
+        let (info, next) = unsafe { (self.read)(chunk) };
+        self.next = next;
+        Some(info)
+    }
+}
+
+/// Returns size of a single chunk that would fit
+/// everything currently allocated in the chunks.
+///
+/// Used bytes of each chunk already include alignment padding
+/// relative to chunk base.
+/// When contents of several chunks are placed one after another,
+/// each but the first one may require padding up to alignment of
+/// its original base.
+/// Chunk header is not accounted for, as it is added on chunk allocation.
+fn minimum_viable_chunk_size(chunks: ChunkIter<'_>) -> usize {
+    let mut size = 0usize;
+    let mut last_padding = 0;
+
+    for chunk in chunks {
+        let base_align = 1usize << crate::addr(chunk.base().as_ptr()).trailing_zeros();
+        last_padding = base_align - 1;
+        size = size
+            .saturating_add(chunk.used())
+            .saturating_add(last_padding);
+    }
+
+    // Oldest chunk is placed first and needs no padding.
+    size - last_padding
+}
+
 /// 0.25 KB. Initial chunk size.
 const CHUNK_START_SIZE: usize = 256;
 
@@ -168,14 +304,76 @@ const CHUNK_POWER_OF_TWO_THRESHOLD: usize = 1 << 14;
 /// 1/16 KB. Minimum chunk size growth step.
 const CHUNK_MIN_GROW_STEP: usize = 64;
 
+/// 1/16 KB. Minimum usable size of a chunk.
+/// Smaller requested chunk sizes are bumped to this value.
+const CHUNK_MIN_SIZE: usize = 64;
+
+/// 2 MB. Size and alignment of transparent huge page.
+/// Chunks large enough to contain at least one aligned huge page
+/// are advised to be backed by huge pages.
+#[cfg(all(feature = "hugepage", target_os = "linux"))]
+const HUGE_PAGE_SIZE: usize = 1 << 21;
+
+/// Advises kernel to back huge-page-aligned part of the memory region
+/// with transparent huge pages.
+///
+/// Chunks are not page-aligned, so only whole huge pages
+/// inside the region are advised.
+/// Advice is a hint, errors are ignored.
+/// Returns `true` if advice was given and accepted.
+#[cfg(all(feature = "hugepage", target_os = "linux"))]
+#[inline]
+pub(crate) fn advise_huge_pages(ptr: *mut u8, len: usize) -> bool {
+    let addr = crate::addr(ptr);
+    let Some(start) = align_up(addr, HUGE_PAGE_SIZE) else {
+        return false;
+    };
+    let end = align_down(addr.saturating_add(len), HUGE_PAGE_SIZE);
+    if start >= end {
+        return false;
+    }
+
+    // Safety: `start` is within the region, as `start < end <= addr + len`.
+    let start_ptr = unsafe { ptr.add(start - addr) };
+
+    // Safety: The range is within memory region owned by the caller.
+    // `MADV_HUGEPAGE` does not change content of the memory.
+    let ret = unsafe {
+        libc::madvise(
+            start_ptr.cast::<libc::c_void>(),
+            end - start,
+            libc::MADV_HUGEPAGE,
+        )
+    };
+    ret == 0
+}
+
+/// Bumps requested chunk size to [`CHUNK_MIN_SIZE`].
+#[inline(always)]
+const fn checked_chunk_size(chunk_size: usize) -> usize {
+    if chunk_size < CHUNK_MIN_SIZE {
+        CHUNK_MIN_SIZE
+    } else {
+        chunk_size
+    }
+}
+
 macro_rules! with_cursor {
-    ($cursor:ty) => {
+    ($cursor:ty, $epoch:ty) => {
         #[repr(C)]
         pub struct ChunkHeader {
             cursor: $cursor,
             end: *mut u8,
             prev: Option<NonNull<Self>>,
             cumulative_size: usize,
+
+            // Chunk memory is borrowed and must not be deallocated.
+            borrowed: bool,
+
+            // Epoch in which chunk was retired.
+            // Only used by `ArenaSync`.
+            #[allow(dead_code)]
+            epoch: $epoch,
         }
 
         impl ChunkHeader {
@@ -184,6 +382,7 @@ macro_rules! with_cursor {
                 size: usize,
                 allocator: impl Allocator,
                 prev: Option<NonNull<Self>>,
+                exact: bool,
             ) -> Result<NonNull<Self>, AllocError> {
                 let Some(size) = align_up(size, align_of::<Self>()) else {
                     return Err(AllocError);
@@ -193,7 +392,22 @@ macro_rules! with_cursor {
                 // size + (align - 1) hasn't overflow above.
                 // `align_of` returns valid align value.
                 let layout = unsafe { Layout::from_size_align_unchecked(size, align_of::<Self>()) };
-                let slice = allocator.allocate(layout)?;
+                let mut slice = allocator.allocate(layout)?;
+                if exact {
+                    // Ignore spare memory provided by the allocator,
+                    // so that chunk capacity depends only on requested size.
+                    // Deallocating with requested size is allowed.
+                    slice = NonNull::new_unchecked(ptr::slice_from_raw_parts_mut(
+                        slice.as_ptr().cast::<u8>(),
+                        size,
+                    ));
+                }
+
+                #[cfg(all(feature = "hugepage", target_os = "linux"))]
+                if slice.len() >= HUGE_PAGE_SIZE {
+                    advise_huge_pages(slice.as_ptr().cast::<u8>(), slice.len());
+                }
+
                 Ok(Self::init_chunk(slice, prev))
             }
 
@@ -205,6 +419,10 @@ macro_rules! with_cursor {
                 let me = unsafe { chunk.as_ref() };
                 let prev = me.prev;
 
+                if me.borrowed {
+                    return prev;
+                }
+
                 let size = unsafe { me.end.offset_from(chunk.as_ptr().cast()) } as usize;
 
                 // Safety:
@@ -228,7 +446,7 @@ macro_rules! with_cursor {
             ) -> NonNull<Self> {
                 let len = slice.len();
                 let ptr = slice.as_ptr().cast::<u8>();
-                debug_assert!(is_aligned_to(ptr as usize, align_of::<Self>()));
+                debug_assert!(is_aligned_to(crate::addr(ptr), align_of::<Self>()));
                 debug_assert!(len > size_of::<Self>());
 
                 let end = ptr.add(len);
@@ -251,20 +469,51 @@ macro_rules! with_cursor {
                         end,
                         prev,
                         cumulative_size,
+                        borrowed: false,
+                        epoch: Default::default(),
                     },
                 );
                 NonNull::new_unchecked(header_ptr)
             }
 
+            /// Makes chunk from borrowed memory.
+            /// Such chunk is never deallocated.
+            ///
+            /// Returns `None` if memory is too small to fit chunk header.
+            #[allow(dead_code)]
+            #[inline]
+            fn borrowed_chunk(bytes: &'static mut [MaybeUninit<u8>]) -> Option<NonNull<Self>> {
+                let ptr = bytes.as_mut_ptr().cast::<u8>();
+                let addr = crate::addr(ptr);
+                let offset = align_up(addr, align_of::<Self>())? - addr;
+                let len = align_down(bytes.len().checked_sub(offset)?, align_of::<Self>());
+                if len <= size_of::<Self>() {
+                    return None;
+                }
+
+                // Safety: `offset + len` is within `bytes`.
+                // Memory is exclusively borrowed forever.
+                unsafe {
+                    let slice = ptr::slice_from_raw_parts_mut(ptr.add(offset), len);
+                    let mut chunk = Self::init_chunk(NonNull::new_unchecked(slice), None);
+                    chunk.as_mut().borrowed = true;
+                    Some(chunk)
+                }
+            }
+
             #[inline(always)]
             fn base(&self) -> *const u8 {
-                unsafe { <*const Self>::add(self, 1).cast() }
+                // Derived from `end` and not from `self` reference
+                // to keep provenance of the whole chunk memory.
+                // Safety: `base` is `end - cap` by construction.
+                unsafe { self.end.sub(self.cap()) }
             }
 
             /// # Safety
             ///
             /// `ptr` must be a pointer withing the usable memory of the chunk.
             /// e.g. it must be between `base` and `self`.
+            #[allow(dead_code)]
             #[inline(always)]
             unsafe fn offset_from_end(&self, ptr: *const u8) -> usize {
                 // Safety: end and base belong to the same memory chunk.
@@ -274,8 +523,9 @@ macro_rules! with_cursor {
 
             #[inline(always)]
             fn cap(&self) -> usize {
-                // Safety: `base` fits `base..=self` range.
-                unsafe { self.offset_from_end(self.base()) }
+                // Usable memory starts right after the header.
+                let header_end = <*const Self>::wrapping_add(self, 1);
+                crate::addr(self.end) - crate::addr(header_end)
             }
 
             // Safety: `chunk` must be a pointer to the valid chunk allocation.
@@ -286,7 +536,7 @@ macro_rules! with_cursor {
                 let mut cursor = me.cursor.load(Ordering::Relaxed);
 
                 loop {
-                    let cursor_addr = cursor as usize;
+                    let cursor_addr = crate::addr(cursor);
 
                     let layout_sum = layout_sum(&layout);
 
@@ -305,7 +555,7 @@ macro_rules! with_cursor {
 
                     let next_addr = aligned_addr + layout.size();
 
-                    let end_addr = me.end as usize;
+                    let end_addr = crate::addr(me.end);
                     if next_addr > end_addr {
                         return None;
                     }
@@ -362,11 +612,40 @@ macro_rules! with_cursor {
                 // Safety: `chunk` is a valid pointer to chunk allocation.
                 let me = unsafe { chunk.as_ref() };
 
-                let addr = ptr.as_ptr() as usize;
-                if old_layout.align() >= new_layout.align() {
+                let addr = crate::addr(ptr.as_ptr());
+
+                // Pointer may happen to satisfy larger alignment already.
+                if old_layout.align() >= new_layout.align() || addr & (new_layout.align() - 1) == 0
+                {
                     if new_layout.size() <= old_layout.size() {
-                        let slice =
-                            core::ptr::slice_from_raw_parts_mut(ptr.as_ptr(), old_layout.size());
+                        // Safety:
+                        // `ptr + old_layout.size()` is within allocation or one by past end.
+                        let old_end = unsafe { ptr.as_ptr().add(old_layout.size()) };
+
+                        // Give memory back if this is the last allocation.
+                        // This keeps following in-place grows possible.
+                        let cursor = me.cursor.load(Ordering::Relaxed);
+                        let len = if cursor == old_end {
+                            // New cursor is derived from the old one
+                            // to keep provenance of the whole chunk.
+                            // Safety: `cursor - shrink` is not less than `ptr`.
+                            let new_end =
+                                unsafe { cursor.sub(old_layout.size() - new_layout.size()) };
+                            match CasPtr::compare_exchange(
+                                &me.cursor,
+                                cursor,
+                                new_end,
+                                Ordering::Release, // Released some memory.
+                                Ordering::Relaxed,
+                            ) {
+                                Ok(()) => new_layout.size(),
+                                Err(_) => old_layout.size(),
+                            }
+                        } else {
+                            old_layout.size()
+                        };
+
+                        let slice = core::ptr::slice_from_raw_parts_mut(ptr.as_ptr(), len);
                         return Some(NonNull::new_unchecked(slice));
                     } else {
                         // Safety:
@@ -377,13 +656,14 @@ macro_rules! with_cursor {
                         if cursor == old_end {
                             let next_addr = addr.checked_add(new_layout.size())?;
 
-                            let end_addr = me.end as usize;
+                            let end_addr = crate::addr(me.end);
                             if next_addr > end_addr {
                                 // Not enough space.
                                 return None;
                             }
 
-                            let next = unsafe { ptr.as_ptr().add(new_layout.size()) };
+                            // Safety: `next_addr` is within chunk memory.
+                            let next = unsafe { cursor.add(new_layout.size() - old_layout.size()) };
 
                             let result = CasPtr::compare_exchange(
                                 &me.cursor,
@@ -432,6 +712,22 @@ macro_rules! with_cursor {
                 me.prev.take()
             }
 
+            // Safety: `chunk` must be a pointer to the valid chunk allocation.
+            // No memory allocated from the chunk may be used after this call.
+            #[allow(dead_code)]
+            #[inline(always)]
+            unsafe fn reset_cursor(mut chunk: NonNull<Self>) {
+                let me = chunk.as_mut();
+                let base = me.end.sub(me.cap());
+                me.cursor.set(base);
+            }
+
+            #[allow(dead_code)]
+            #[inline(always)]
+            fn is_unused(&self) -> bool {
+                ptr::eq(self.cursor.load(Ordering::Relaxed), self.base())
+            }
+
             // Safety: `chunk` must be a pointer to the valid chunk allocation.
             // `ptr` must be a pointer to the allocated memory of at least `size` bytes.
             // `ptr` may be allocated from different chunk.
@@ -441,14 +737,25 @@ macro_rules! with_cursor {
                 let me = unsafe { chunk.as_ref() };
 
                 // Safety: `ptr` is a valid pointer to the allocated memory of at least `size` bytes.
-                let new = unsafe { ptr.as_ptr().add(size) };
+                let end = unsafe { ptr.as_ptr().add(size) };
+
+                let cursor = me.cursor.load(Ordering::Relaxed);
+                if cursor != end {
+                    // `ptr` is not the last memory allocated from this chunk.
+                    return;
+                }
+
+                // Rolled back cursor is derived from the current one
+                // to keep provenance of the whole chunk.
+                // Safety: `cursor - size` is `ptr` address within chunk memory.
+                let new = unsafe { cursor.sub(size) };
 
                 // Single attempt to update cursor.
-                // Fails if `ptr` is not the last memory allocated from this chunk.
                 // Spurious failures in multithreaded environment are possible
                 // but do not affect correctness.
-                let _ = me.cursor.compare_exchange(
-                    ptr.as_ptr(),
+                let _ = CasPtr::compare_exchange(
+                    &me.cursor,
+                    cursor,
                     new,
                     Ordering::Release, // Released some memory.
                     Ordering::Relaxed,
@@ -456,12 +763,26 @@ macro_rules! with_cursor {
             }
         }
 
+        /// Returns size of chunk allocation with `size` bytes of usable memory.
+        #[inline(always)]
+        fn chunk_alloc_size(size: usize) -> Option<usize> {
+            let chunk_size = size.checked_add(size_of::<ChunkHeader>())?;
+
+            // Grow size exponentially until a threshold.
+            if chunk_size < CHUNK_POWER_OF_TWO_THRESHOLD {
+                Some(chunk_size.next_power_of_two())
+            } else {
+                Some(align_up(chunk_size, CHUNK_POWER_OF_TWO_THRESHOLD).unwrap_or(chunk_size))
+            }
+        }
+
         #[cold]
         pub unsafe fn alloc_slow(
             root: &Cell<Option<NonNull<ChunkHeader>>>,
             mut chunk_size: usize,
             layout: Layout,
             allocator: impl Allocator,
+            exact: bool,
         ) -> Result<NonNull<[u8]>, AllocError> {
             if let Some(root) = root.get() {
                 chunk_size = chunk_size.max(root.as_ref().cumulative_size);
@@ -476,20 +797,12 @@ macro_rules! with_cursor {
                 chunk_size = chunk_size.checked_add(layout.align()).ok_or(AllocError)?;
             }
 
-            let Some(mut chunk_size) = chunk_size.checked_add(size_of::<ChunkHeader>()) else {
+            let Some(chunk_size) = chunk_alloc_size(chunk_size) else {
                 return Err(AllocError);
             };
 
-            // Grow size exponentially until a threshold.
-            if chunk_size < CHUNK_POWER_OF_TWO_THRESHOLD {
-                chunk_size = chunk_size.next_power_of_two();
-            } else {
-                chunk_size =
-                    align_up(chunk_size, CHUNK_POWER_OF_TWO_THRESHOLD).unwrap_or(chunk_size);
-            }
-
             debug_assert_eq!(chunk_size % align_of::<ChunkHeader>(), 0);
-            let new_chunk = ChunkHeader::alloc_chunk(chunk_size, allocator, root.get())?;
+            let new_chunk = ChunkHeader::alloc_chunk(chunk_size, allocator, root.get(), exact)?;
 
             // Safety: `chunk` is a valid pointer to chunk allocation.
             let ptr = unsafe { ChunkHeader::alloc(new_chunk, layout).unwrap_unchecked() };
@@ -506,8 +819,9 @@ macro_rules! with_cursor {
             old_layout: Layout,
             new_layout: Layout,
             allocator: impl Allocator,
+            exact: bool,
         ) -> Result<NonNull<[u8]>, AllocError> {
-            let new_ptr = alloc_slow(root, chunk_size, new_layout, allocator)?;
+            let new_ptr = alloc_slow(root, chunk_size, new_layout, allocator, exact)?;
             core::ptr::copy_nonoverlapping(
                 ptr.as_ptr(),
                 new_ptr.as_ptr().cast(),
@@ -558,6 +872,385 @@ macro_rules! with_cursor {
             }
         }
 
+        /// Resets arena keeping `keep_n` newest chunks
+        /// and deallocating older ones.
+        /// Cursors of kept chunks are reset to their bases.
+        /// Returns number of kept chunks.
+        ///
+        /// # Safety
+        ///
+        /// `allocator` must be the same allocator that was used in `alloc`.
+        #[allow(dead_code)]
+        #[inline]
+        pub unsafe fn reset_partial<A>(
+            root: &Cell<Option<NonNull<ChunkHeader>>>,
+            keep_n: usize,
+            allocator: A,
+        ) -> usize
+        where
+            A: Allocator,
+        {
+            if keep_n == 0 {
+                unsafe { reset(root, false, allocator) };
+                return 0;
+            }
+
+            let mut kept_n = 0;
+            let mut last_kept = None;
+            let mut chunk = root.get();
+
+            for _ in 0..keep_n {
+                let Some(mut kept) = chunk else {
+                    break;
+                };
+                // Safety: `kept` is a valid pointer to chunk allocation.
+                // This function owns mutable reference to the arena.
+                // Kept chunks are unused, so none of them
+                // contributes to cumulative size.
+                unsafe {
+                    ChunkHeader::reset_cursor(kept);
+                    kept.as_mut().cumulative_size = 0;
+                    chunk = kept.as_ref().prev;
+                }
+                last_kept = Some(kept);
+                kept_n += 1;
+            }
+
+            if let Some(mut last_kept) = last_kept {
+                // Safety: `last_kept` is a valid pointer to chunk allocation.
+                unsafe { last_kept.as_mut().prev = None };
+            }
+
+            while let Some(old) = chunk {
+                // Safety: `old` is a valid pointer to chunk allocation.
+                // Allocated from this allocator with this layout.
+                chunk = unsafe { ChunkHeader::dealloc_chunk(old, &allocator) };
+            }
+
+            kept_n
+        }
+
+        /// Allocates from unused older chunk, making it the current one.
+        /// Such chunks are left by `reset_partial`.
+        ///
+        /// Moving unused chunk to the front keeps checkpoints valid,
+        /// as rollback deallocates it as any chunk allocated after checkpoint.
+        ///
+        /// # Safety
+        ///
+        /// Arena must not be accessed concurrently.
+        #[allow(dead_code)]
+        #[inline]
+        pub unsafe fn alloc_unused(
+            root: &Cell<Option<NonNull<ChunkHeader>>>,
+            layout: Layout,
+        ) -> Option<NonNull<[u8]>> {
+            let current = root.get()?;
+            let mut newer = current;
+
+            // Safety: All chunks in the list are valid.
+            unsafe {
+                let mut chunk = current.as_ref().prev;
+                while let Some(mut unused) = chunk {
+                    if unused.as_ref().is_unused() {
+                        if let Some(ptr) = ChunkHeader::alloc(unused, layout) {
+                            newer.as_mut().prev = unused.as_ref().prev;
+                            unused.as_mut().prev = Some(current);
+                            unused.as_mut().cumulative_size =
+                                current.as_ref().cap() + current.as_ref().cumulative_size;
+                            root.set(Some(unused));
+                            return Some(ptr);
+                        }
+                    }
+                    newer = unused;
+                    chunk = unused.as_ref().prev;
+                }
+            }
+            None
+        }
+
+        /// Position in the arena that can be restored with `rollback`.
+        #[derive(Clone, Copy)]
+        #[allow(dead_code)]
+        pub struct Checkpoint {
+            chunk: Option<NonNull<ChunkHeader>>,
+            cursor: *mut u8,
+        }
+
+        #[allow(dead_code)]
+        #[inline(always)]
+        pub fn checkpoint(root: Option<NonNull<ChunkHeader>>) -> Checkpoint {
+            match root {
+                None => Checkpoint {
+                    chunk: None,
+                    cursor: ptr::null_mut(),
+                },
+                Some(chunk) => Checkpoint {
+                    chunk: Some(chunk),
+                    // Safety: `chunk` is a valid pointer to chunk allocation.
+                    cursor: unsafe { chunk.as_ref().cursor.load(Ordering::Relaxed) },
+                },
+            }
+        }
+
+        /// Deallocates all chunks allocated after the checkpoint
+        /// and restores cursor of the checkpoint chunk.
+        ///
+        /// # Safety
+        ///
+        /// `checkpoint` must be created from this arena
+        /// and no reset could happen since then.
+        /// Memory allocated after the checkpoint must not be used.
+        /// `allocator` must be the same allocator that was used in `alloc`.
+        #[allow(dead_code)]
+        #[inline]
+        pub unsafe fn rollback<A>(
+            root: &Cell<Option<NonNull<ChunkHeader>>>,
+            checkpoint: Checkpoint,
+            allocator: A,
+        ) where
+            A: Allocator,
+        {
+            let mut chunk = root.get();
+
+            while chunk != checkpoint.chunk {
+                let Some(newer) = chunk else {
+                    debug_assert!(false, "Checkpoint chunk is not found in the arena");
+                    return;
+                };
+                // Safety: `newer` is a valid pointer to chunk allocation.
+                // Allocated from this allocator with this layout.
+                chunk = unsafe { ChunkHeader::dealloc_chunk(newer, &allocator) };
+            }
+
+            root.set(chunk);
+
+            if let Some(mut chunk) = chunk {
+                // Safety: `chunk` is a valid pointer to chunk allocation.
+                // Nothing references memory allocated after the checkpoint.
+                unsafe { chunk.as_mut().cursor.set(checkpoint.cursor) };
+            }
+        }
+
+        /// Resets arena keeping single chunk large enough
+        /// to fit memory used in all chunks before reset.
+        /// Retained chunk is replaced with smaller one if it is oversized.
+        ///
+        /// # Safety
+        ///
+        /// `allocator` must be the same allocator that was used in `alloc`.
+        #[allow(dead_code)]
+        #[inline]
+        pub unsafe fn reset_shrink<A>(
+            root: &Cell<Option<NonNull<ChunkHeader>>>,
+            min_chunk_size: usize,
+            allocator: A,
+            exact: bool,
+        ) where
+            A: Allocator,
+        {
+            let used = minimum_viable_chunk_size(chunks(root.get()));
+
+            unsafe { reset(root, true, &allocator) };
+
+            let Some(chunk) = root.get() else {
+                return;
+            };
+
+            // Borrowed chunk costs nothing to keep.
+            if unsafe { chunk.as_ref().borrowed } {
+                return;
+            }
+
+            let Some(chunk_size) = chunk_alloc_size(used.max(min_chunk_size)) else {
+                return;
+            };
+
+            // Safety: `chunk` is a valid pointer to chunk allocation.
+            let current_size = unsafe { chunk.as_ref().end.offset_from(chunk.as_ptr().cast()) };
+
+            if chunk_size >= current_size as usize {
+                return;
+            }
+
+            unsafe { reset(root, false, &allocator) };
+
+            // Failure to allocate smaller chunk is not an error.
+            // Next allocation will try again.
+            if let Ok(chunk) =
+                unsafe { ChunkHeader::alloc_chunk(chunk_size, &allocator, None, exact) }
+            {
+                root.set(Some(chunk));
+            }
+        }
+
+        /// Makes borrowed memory the only chunk of the arena.
+        /// Does nothing if memory is too small to fit chunk header.
+        #[allow(dead_code)]
+        #[inline]
+        pub fn set_borrowed_chunk(
+            root: &Cell<Option<NonNull<ChunkHeader>>>,
+            bytes: &'static mut [MaybeUninit<u8>],
+        ) {
+            debug_assert!(root.get().is_none(), "Arena must be empty");
+            if let Some(chunk) = ChunkHeader::borrowed_chunk(bytes) {
+                root.set(Some(chunk));
+            }
+        }
+
+        /// Safety: `chunk` must be a pointer to the valid chunk allocation.
+        unsafe fn read_chunk(chunk: NonNull<u8>) -> (ChunkInfo, Option<NonNull<u8>>) {
+            let chunk = unsafe { chunk.cast::<ChunkHeader>().as_ref() };
+            let base = chunk.base();
+            let cursor = chunk.cursor.load(Ordering::Relaxed);
+
+            let info = ChunkInfo {
+                // Safety: `base` is derived from non-null chunk pointer.
+                base: unsafe { NonNull::new_unchecked(base.cast_mut()) },
+                cap: chunk.cap(),
+                // Safety: `cursor` is within chunk memory and not less than `base`.
+                used: unsafe { cursor.cast_const().offset_from(base) } as usize,
+            };
+            (info, chunk.prev.map(NonNull::cast))
+        }
+
+        /// Returns number of bytes consumed from chunks starting from `root`.
+        /// Previous chunks are counted in full as they are not used anymore.
+        #[allow(dead_code)]
+        #[inline(always)]
+        pub fn used_bytes(root: Option<NonNull<ChunkHeader>>) -> usize {
+            match root {
+                None => 0,
+                Some(root) => {
+                    // Safety: `root` is a valid pointer to chunk allocation.
+                    let root = unsafe { root.as_ref() };
+                    let cursor = root.cursor.load(Ordering::Relaxed);
+                    // Safety: `cursor` is within chunk memory and not less than `base`.
+                    let used = unsafe { cursor.cast_const().offset_from(root.base()) } as usize;
+                    root.cumulative_size + used
+                }
+            }
+        }
+
+        /// Returns iterator over chunks starting from `root`.
+        ///
+        /// Chunks are deallocated only on reset which requires
+        /// mutable borrow or is unsafe,
+        /// or on epoch reclamation which is excluded
+        /// by read lock stored in the iterator.
+        #[inline(always)]
+        pub fn chunks<'a>(root: Option<NonNull<ChunkHeader>>) -> ChunkIter<'a> {
+            ChunkIter {
+                next: root.map(NonNull::cast),
+                read: read_chunk,
+                marker: PhantomData,
+                #[cfg(feature = "sync")]
+                shards: &[],
+                #[cfg(feature = "sync")]
+                lock: None,
+            }
+        }
+
+        /// Calls `f` with base pointer and number of used bytes
+        /// of each chunk starting from `root` that has used bytes.
+        #[inline]
+        pub fn for_each_used_byte_range(
+            root: Option<NonNull<ChunkHeader>>,
+            mut f: impl FnMut(*const u8, usize),
+        ) {
+            for chunk in chunks(root) {
+                if chunk.used() != 0 {
+                    f(chunk.base().as_ptr(), chunk.used());
+                }
+            }
+        }
+
+        /// Checks if `ptr` points into usable memory of one of the chunks.
+        #[allow(dead_code)]
+        pub fn owns(root: Option<NonNull<ChunkHeader>>, ptr: *const u8) -> bool {
+            let mut next = root;
+            while let Some(chunk) = next {
+                // Safety: `chunk` is a valid pointer to chunk allocation.
+                let chunk = unsafe { chunk.as_ref() };
+                if chunk.base() <= ptr && ptr < chunk.end.cast_const() {
+                    return true;
+                }
+                next = chunk.prev;
+            }
+            false
+        }
+
+        /// Checks if `ptr` points into usable memory of the last chunk.
+        /// Only memory of the last chunk can be reclaimed or grown in place.
+        #[allow(dead_code)]
+        pub fn last_chunk_owns(root: Option<NonNull<ChunkHeader>>, ptr: *const u8) -> bool {
+            match root {
+                None => false,
+                Some(chunk) => {
+                    // Safety: `chunk` is a valid pointer to chunk allocation.
+                    let chunk = unsafe { chunk.as_ref() };
+                    chunk.base() <= ptr && ptr < chunk.end.cast_const()
+                }
+            }
+        }
+
+        /// Pushes capacity of each chunk into `sizes`,
+        /// from the newest chunk to the oldest.
+        #[cfg(feature = "alloc")]
+        #[allow(dead_code)]
+        pub fn collect_chunk_sizes(
+            root: Option<NonNull<ChunkHeader>>,
+            sizes: &mut alloc::vec::Vec<usize>,
+        ) {
+            let mut next = root;
+            while let Some(chunk) = next {
+                // Safety: `chunk` is a valid pointer to chunk allocation.
+                let chunk = unsafe { chunk.as_ref() };
+                sizes.push(chunk.cap());
+                next = chunk.prev;
+            }
+        }
+
+        /// Moves the last chunk of `other` arena to the `root`
+        /// making it current chunk.
+        /// Remaining chunks stay in `other` arena.
+        ///
+        /// # Safety
+        ///
+        /// Memory allocated from the last chunk of `other` must not be used anymore.
+        /// Both arenas must use the same underlying allocator.
+        #[allow(dead_code)]
+        #[inline]
+        pub unsafe fn steal_last_chunk(
+            root: &Cell<Option<NonNull<ChunkHeader>>>,
+            other: &Cell<Option<NonNull<ChunkHeader>>>,
+        ) {
+            let Some(mut chunk) = other.get() else {
+                return;
+            };
+
+            // Safety: `chunk` is a valid pointer to chunk allocation.
+            // Memory allocated from it is not used anymore.
+            let rest = unsafe { ChunkHeader::reset(chunk) };
+            other.set(rest);
+
+            let cumulative_size = match root.get() {
+                None => 0,
+                Some(prev) => {
+                    // Safety: `prev` is a valid pointer to chunk allocation.
+                    let prev = unsafe { prev.as_ref() };
+                    prev.cumulative_size + prev.cap()
+                }
+            };
+
+            // Safety: `chunk` is a valid pointer to chunk allocation.
+            // It is not shared with `other` anymore.
+            let me = unsafe { chunk.as_mut() };
+            me.prev = root.get();
+            me.cumulative_size = cumulative_size;
+            root.set(Some(chunk));
+        }
+
         #[allow(dead_code)]
         #[inline(always)]
         pub fn reset_leak(root: &Cell<Option<NonNull<ChunkHeader>>>, keep_last: bool) {
@@ -579,10 +1272,10 @@ macro_rules! with_cursor {
 }
 
 mod local;
-pub use self::local::ArenaLocal;
+pub use self::local::{ArenaLocal, Checkpoint};
 
 #[cfg(feature = "sync")]
 mod sync;
 
 #[cfg(feature = "sync")]
-pub use self::sync::ArenaSync;
+pub use self::sync::{ArenaShard, ArenaSync};
@@ -1,18 +1,66 @@
 use super::*;
 
-with_cursor!(AtomicPtr<u8>);
+use core::sync::atomic::AtomicUsize;
 
-struct Inner {
+with_cursor!(AtomicPtr<u8>, u64);
+
+pub(super) struct Inner {
     root: Option<NonNull<ChunkHeader>>,
     min_chunk_size: usize,
+
+    // Epoch of chunks in `root` list.
+    epoch: u64,
+
+    // Chunks detached by `retire`, tagged with epoch of retirement.
+    // Most recently retired chunks come first.
+    retired: Option<NonNull<ChunkHeader>>,
 }
 
 unsafe impl Send for Inner {}
 unsafe impl Sync for Inner {}
 
+/// Arena of sharded allocator.
+///
+/// Aligned to cache line, so that locks of neighbouring shards
+/// do not share cache line.
+#[cfg_attr(
+    any(target_arch = "aarch64", target_arch = "powerpc64"),
+    repr(align(128))
+)]
+#[cfg_attr(target_arch = "s390x", repr(align(256)))]
+#[cfg_attr(
+    not(any(
+        target_arch = "aarch64",
+        target_arch = "powerpc64",
+        target_arch = "s390x"
+    )),
+    repr(align(64))
+)]
+pub struct ArenaShard(pub ArenaSync);
+
+impl core::ops::Deref for ArenaShard {
+    type Target = ArenaSync;
+
+    #[inline(always)]
+    fn deref(&self) -> &ArenaSync {
+        &self.0
+    }
+}
+
+impl core::ops::DerefMut for ArenaShard {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut ArenaSync {
+        &mut self.0
+    }
+}
+
 /// Multi-threaded arena allocator.
 pub struct ArenaSync {
     inner: RwLock<Inner>,
+
+    // Bytes requested from this arena since last reset.
+    // Kept per arena, so shards do not contend on a single counter.
+    allocated: AtomicUsize,
 }
 
 impl Drop for ArenaSync {
@@ -22,6 +70,10 @@ impl Drop for ArenaSync {
             self.inner.get_mut().root.is_none(),
             "Owner must reset `ArenaSync` with `keep_last` set to `false` before drop"
         );
+        debug_assert!(
+            self.inner.get_mut().retired.is_none(),
+            "Owner must reset `ArenaSync` before drop"
+        );
     }
 }
 
@@ -32,7 +84,10 @@ impl ArenaSync {
             inner: RwLock::new(Inner {
                 root: None,
                 min_chunk_size: CHUNK_START_SIZE,
+                epoch: 0,
+                retired: None,
             }),
+            allocated: AtomicUsize::new(0),
         }
     }
 
@@ -41,13 +96,91 @@ impl ArenaSync {
         ArenaSync {
             inner: RwLock::new(Inner {
                 root: None,
-                min_chunk_size,
+                min_chunk_size: checked_chunk_size(min_chunk_size),
+                epoch: 0,
+                retired: None,
             }),
+            allocated: AtomicUsize::new(0),
         }
     }
 
+    #[inline]
+    pub fn owns(&self, ptr: *const u8) -> bool {
+        owns(self.inner.read().root, ptr)
+    }
+
+    #[inline(always)]
+    pub fn last_chunk_owns(&self, ptr: *const u8) -> bool {
+        last_chunk_owns(self.inner.read().root, ptr)
+    }
+
+    #[inline(always)]
+    pub fn last_chunk_size(&self) -> usize {
+        last_chunk_cap(&self.inner.read())
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        used_bytes(self.inner.read().root) == 0
+    }
+
+    /// Returned iterator holds read lock of this arena,
+    /// so chunks can't be reclaimed while it walks them.
+    #[inline]
+    pub fn chunks(&self) -> ChunkIter<'_> {
+        let (lock, root) = self.lock_chunks();
+        let mut iter = chunks(root);
+        iter.lock = Some(lock);
+        iter
+    }
+
+    /// Returns iterator over chunks of this arena
+    /// followed by chunks of `shards`.
+    #[inline]
+    pub fn chunks_with<'a>(&'a self, shards: &'a [ArenaShard]) -> ChunkIter<'a> {
+        let mut iter = self.chunks();
+        iter.shards = shards;
+        iter
+    }
+
+    /// Locks arena for reading and returns the newest chunk.
+    /// Chunks are not reclaimed while the lock is held.
+    #[inline(always)]
+    pub(super) fn lock_chunks(&self) -> (RwLockReadGuard<'_, Inner>, Option<NonNull<ChunkHeader>>) {
+        let inner = self.inner.read();
+        let root = inner.root;
+        (inner, root)
+    }
+
+    /// Walks chunks under read lock.
+    /// New chunks are allocated under write lock,
+    /// so snapshot is coherent.
+    #[inline]
+    pub fn stats(&self) -> BlinkStats {
+        let inner = self.inner.read();
+        BlinkStats::collect(chunks(inner.root))
+    }
+
+    /// Walks chunks under read lock.
+    #[inline]
+    pub fn for_each_used_byte_range(&self, f: impl FnMut(*const u8, usize)) {
+        let inner = self.inner.read();
+        for_each_used_byte_range(inner.root, f)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn collect_chunk_sizes(&self, sizes: &mut alloc::vec::Vec<usize>) {
+        let inner = self.inner.read();
+        collect_chunk_sizes(inner.root, sizes)
+    }
+
     #[inline(always)]
     pub unsafe fn alloc_fast(&self, layout: Layout) -> Option<NonNull<[u8]>> {
+        if layout.size() == 0 {
+            return Some(dangling_slice(&layout));
+        }
+
         let inner = self.inner.read();
 
         if let Some(root) = inner.root {
@@ -57,21 +190,25 @@ impl ArenaSync {
         None
     }
 
+    /// Allocates new chunk.
+    /// Returns allocated memory and capacity of the new chunk.
     #[inline(always)]
     pub unsafe fn alloc_slow(
         &self,
         layout: Layout,
         allocator: impl Allocator,
-    ) -> Result<NonNull<[u8]>, AllocError> {
+    ) -> Result<(NonNull<[u8]>, usize), AllocError> {
         let mut guard = self.inner.write();
         let inner = &mut *guard;
 
-        alloc_slow(
+        let ptr = alloc_slow(
             Cell::from_mut(&mut inner.root),
             inner.min_chunk_size,
             layout,
             &allocator,
-        )
+            false,
+        )?;
+        Ok((ptr, last_chunk_cap(inner)))
     }
 
     #[inline(always)]
@@ -89,6 +226,8 @@ impl ArenaSync {
         None
     }
 
+    /// Allocates new chunk.
+    /// Returns allocated memory and capacity of the new chunk.
     #[inline(always)]
     pub unsafe fn resize_slow(
         &self,
@@ -96,18 +235,20 @@ impl ArenaSync {
         old_layout: Layout,
         new_layout: Layout,
         allocator: impl Allocator,
-    ) -> Result<NonNull<[u8]>, AllocError> {
+    ) -> Result<(NonNull<[u8]>, usize), AllocError> {
         let mut guard = self.inner.write();
         let inner = &mut *guard;
 
-        resize_slow(
+        let ptr = resize_slow(
             Cell::from_mut(&mut inner.root),
             inner.min_chunk_size,
             ptr,
             old_layout,
             new_layout,
             &allocator,
-        )
+            false,
+        )?;
+        Ok((ptr, last_chunk_cap(inner)))
     }
 
     #[inline(always)]
@@ -115,21 +256,91 @@ impl ArenaSync {
         dealloc(self.inner.read().root, ptr, size)
     }
 
+    #[inline(always)]
+    pub fn count_allocated(&self, size: usize) {
+        self.allocated.fetch_add(size, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub fn allocated(&self) -> usize {
+        self.allocated.load(Ordering::Relaxed)
+    }
+
     #[inline(always)]
     pub unsafe fn reset(&mut self, keep_last: bool, allocator: impl Allocator) {
+        *self.allocated.get_mut() = 0;
+        let inner = self.inner.get_mut();
         unsafe {
-            reset(
-                Cell::from_mut(&mut self.inner.get_mut().root),
-                keep_last,
-                allocator,
-            )
+            reclaim(&mut inner.retired, None, &allocator);
+            reset(Cell::from_mut(&mut inner.root), keep_last, allocator)
         }
     }
 
     #[inline(always)]
     pub unsafe fn reset_unchecked(&self, keep_last: bool, allocator: impl Allocator) {
+        self.allocated.store(0, Ordering::Relaxed);
         let mut guard = self.inner.write();
-        unsafe { reset(Cell::from_mut(&mut guard.root), keep_last, allocator) }
+        let inner = &mut *guard;
+        unsafe {
+            reclaim(&mut inner.retired, None, &allocator);
+            reset(Cell::from_mut(&mut inner.root), keep_last, allocator)
+        }
+    }
+
+    /// Retires all chunks if `epoch` is newer than the arena epoch
+    /// and advances the arena epoch to `epoch`.
+    /// Retired chunks are no longer used for allocations
+    /// and are kept until reclaimed.
+    ///
+    /// Returns number and capacity of retired chunks.
+    #[inline]
+    pub fn retire(&self, epoch: u64) -> BlinkStats {
+        let mut guard = self.inner.write();
+        let inner = &mut *guard;
+        let mut retired = BlinkStats::default();
+
+        if epoch <= inner.epoch {
+            return retired;
+        }
+        inner.epoch = epoch;
+
+        let Some(root) = inner.root.take() else {
+            return retired;
+        };
+
+        let mut chunk = root;
+        loop {
+            // Safety: `chunk` is a valid pointer to chunk allocation.
+            // Chunks are detached from the arena under write lock.
+            let me = unsafe { &mut *chunk.as_ptr() };
+            me.epoch = epoch;
+            retired.chunks += 1;
+            retired.capacity += me.cap();
+
+            match me.prev {
+                Some(prev) => chunk = prev,
+                None => {
+                    me.prev = inner.retired;
+                    break;
+                }
+            }
+        }
+
+        inner.retired = Some(root);
+        retired
+    }
+
+    /// Deallocates retired chunks retired not later than `epoch`.
+    /// Deallocates all retired chunks if `epoch` is `None`.
+    ///
+    /// # Safety
+    ///
+    /// Memory allocated from reclaimed chunks must not be used anymore.
+    /// `allocator` must be the same allocator that was used in `alloc`.
+    #[inline]
+    pub unsafe fn reclaim(&self, epoch: Option<u64>, allocator: impl Allocator) {
+        let mut guard = self.inner.write();
+        unsafe { reclaim(&mut guard.retired, epoch, allocator) }
     }
 
     // #[inline(always)]
@@ -137,3 +348,48 @@ impl ArenaSync {
     //     reset_leak(Cell::from_mut(&mut self.inner.get_mut().root), keep_last)
     // }
 }
+
+/// Returns capacity of the newest chunk or 0 if there are no chunks.
+#[inline(always)]
+fn last_chunk_cap(inner: &Inner) -> usize {
+    match inner.root {
+        None => 0,
+        Some(root) => {
+            // Safety: `root` is a valid pointer to chunk allocation.
+            unsafe { root.as_ref().cap() }
+        }
+    }
+}
+
+/// Deallocates chunks from `retired` list retired not later than `epoch`.
+/// Deallocates all chunks if `epoch` is `None`.
+///
+/// # Safety
+///
+/// Memory allocated from reclaimed chunks must not be used anymore.
+/// `allocator` must be the same allocator that was used in `alloc`.
+unsafe fn reclaim(
+    retired: &mut Option<NonNull<ChunkHeader>>,
+    epoch: Option<u64>,
+    allocator: impl Allocator,
+) {
+    // Retirement epochs are non-increasing along the list.
+    // Find first chunk to reclaim, all chunks after it are reclaimed too.
+    let mut link = retired;
+    while let Some(chunk) = *link {
+        // Safety: `chunk` is a valid pointer to chunk allocation.
+        let me = unsafe { &mut *chunk.as_ptr() };
+        match epoch {
+            Some(epoch) if me.epoch > epoch => {}
+            _ => break,
+        }
+        link = &mut me.prev;
+    }
+
+    let mut next = link.take();
+    while let Some(chunk) = next {
+        // Safety: `chunk` is a valid pointer to chunk allocation.
+        // Allocated from this allocator with this layout.
+        next = unsafe { ChunkHeader::dealloc_chunk(chunk, &allocator) };
+    }
+}
@@ -1,28 +1,45 @@
 //! Provides `Blink` allocator adaptor.
 
+#[cfg(feature = "async")]
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
 use core::{
     alloc::Layout,
-    convert::{identity, Infallible},
+    cell::Cell,
+    convert::Infallible,
+    iter::Chain,
     marker::PhantomData,
     mem::{needs_drop, size_of, ManuallyDrop, MaybeUninit},
+    ops::Deref,
+    option,
     ptr::{self, NonNull},
 };
 
+use allocator_api2::alloc::{AllocError, Allocator};
+
+#[cfg(not(no_global_oom_handling))]
+use core::convert::identity;
+
 #[cfg(feature = "alloc")]
 use allocator_api2::alloc::Global;
 
 use crate::{
     api::BlinkAllocator,
     cold,
-    drop_list::{DropItem, DropList},
+    drop_list::{DropCheckpoint, DropItem, DropList},
     in_place,
+    local::{BlinkAlloc, Checkpoint},
 };
 
 #[cfg(not(no_global_oom_handling))]
 use crate::ResultExt;
 
-#[cfg(feature = "alloc")]
-use crate::local::BlinkAlloc;
+#[cfg(feature = "tracing")]
+use crate::arena::BlinkStats;
 
 #[cfg(not(no_global_oom_handling))]
 use crate::oom::{handle_alloc_error, size_overflow};
@@ -48,6 +65,20 @@ impl<'a, T: ?Sized> CoerceFromMut<'a, T> for &'a T {
     }
 }
 
+impl<'a, T> CoerceFromMut<'a, T> for &'a Cell<T> {
+    #[inline(always)]
+    fn coerce(t: &'a mut T) -> Self {
+        Cell::from_mut(t)
+    }
+}
+
+impl<'a, T> CoerceFromMut<'a, [T]> for &'a [Cell<T>] {
+    #[inline(always)]
+    fn coerce(t: &'a mut [T]) -> Self {
+        Cell::from_mut(t).as_slice_of_cells()
+    }
+}
+
 /// Iterator extension trait for collecting iterators into blink allocator.
 ///
 /// # Examples
@@ -160,10 +191,96 @@ pub trait IteratorExt: Iterator {
     {
         blink.emplace_no_drop().try_from_iter(self)
     }
+
+    /// Fills provided uninitialized slice with items from this iterator
+    /// until either is exhausted.
+    /// Returns initialized prefix of the slice and the rest of the iterator.
+    ///
+    /// No items are taken from the iterator once the slice is full.
+    /// Items in the returned slice are not dropped automatically.
+    /// If the iterator panics, already written items are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::{Blink, IteratorExt};
+    /// let blink = Blink::new();
+    /// let slice = blink.uninit_slice::<u32>(4);
+    ///
+    /// let (filled, mut rest) = (0..10).fill_blink_slice(slice);
+    /// assert_eq!(filled, [0, 1, 2, 3]);
+    /// assert_eq!(rest.next(), Some(4));
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    fn fill_blink_slice(self, slice: &mut [MaybeUninit<Self::Item>]) -> (&mut [Self::Item], Self)
+    where
+        Self: Sized,
+    {
+        struct Guard<'a, T> {
+            slice: &'a mut [MaybeUninit<T>],
+            len: usize,
+        }
+
+        impl<'a, T> Drop for Guard<'a, T> {
+            #[inline(always)]
+            fn drop(&mut self) {
+                // Safety: First `len` elements are initialized.
+                unsafe {
+                    let init = self.slice.as_mut_ptr().cast::<T>();
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(init, self.len));
+                }
+            }
+        }
+
+        let mut iter = self;
+        let mut guard = Guard { slice, len: 0 };
+
+        while guard.len < guard.slice.len() {
+            match iter.next() {
+                None => break,
+                Some(item) => {
+                    guard.slice[guard.len].write(item);
+                    guard.len += 1;
+                }
+            }
+        }
+
+        let guard = ManuallyDrop::new(guard);
+        let len = guard.len;
+
+        // Safety: Guard is never dropped, so the slice reference is moved out of it.
+        let slice = unsafe { ptr::read(&guard.slice) };
+
+        // Safety: First `len` elements are initialized.
+        let filled = unsafe { core::slice::from_raw_parts_mut(slice.as_mut_ptr().cast(), len) };
+        (filled, iter)
+    }
 }
 
 impl<I> IteratorExt for I where I: Iterator {}
 
+/// Iterators that report exact number of remaining elements
+/// as lower bound of [`Iterator::size_hint`].
+///
+/// [`Emplace::from_exact_iter`] and [`Emplace::try_from_exact_iter`]
+/// use this guarantee to allocate memory for all elements at once
+/// and skip checks for extra elements.
+///
+/// This is a stable substitute for unstable `TrustedLen` trait.
+///
+/// # Safety
+///
+/// Lower bound returned by [`Iterator::size_hint`] must be equal to
+/// the number of elements iterator yields.
+pub unsafe trait ExactFill: Iterator {}
+
+unsafe impl ExactFill for core::ops::Range<usize> {}
+unsafe impl<T> ExactFill for core::slice::Iter<'_, T> {}
+unsafe impl<T, const N: usize> ExactFill for core::array::IntoIter<T, N> {}
+
 switch_alloc_default! {
     /// An allocator adaptor for designed for blink allocator.
     /// Provides user-friendly methods to emplace values into allocated memory.
@@ -191,6 +308,45 @@ switch_alloc_default! {
     /// It drops all emplaced values and resets associated allocator instance.
     /// If allocator instance is shared, resetting it will have no effect.
     ///
+    /// # Reference counting
+    ///
+    /// Values emplaced into [`Blink`] are shared by plain references.
+    /// References are `Copy` and live until reset,
+    /// then values are dropped exactly once.
+    /// So reference counting is rarely needed for values in [`Blink`].
+    ///
+    /// On nightly `Rc::new_in` and `Arc::new_in` accept `&BlinkAlloc`
+    /// or `&Blink::allocator()` as allocator.
+    /// That is sound without any extra bookkeeping.
+    /// Each `Rc` borrows the allocator, so the allocator can't be reset
+    /// while any `Rc` is alive.
+    /// Value is dropped when the last `Rc` is dropped
+    /// and deallocation of the reference-counted block is a no-op,
+    /// its memory is reclaimed on reset.
+    /// `Rc` that is leaked, e.g. with [`core::mem::forget`] or a cycle,
+    /// keeps its value alive forever and reset does not run its destructor,
+    /// same as with any other allocator.
+    /// Registering the drop in [`Blink`] would instead drop the value
+    /// twice if the last `Rc` is dropped before reset.
+    ///
+    /// `Rc` and `Arc` emplaced into [`Blink`] are ordinary values,
+    /// emplaced handle is dropped on reset, releasing its reference.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// # use std::rc::Rc;
+    /// let mut blink = Blink::new();
+    /// let shared = Rc::new(String::from("shared"));
+    /// let handle = blink.put(shared.clone());
+    /// assert_eq!(Rc::strong_count(handle), 2);
+    ///
+    /// blink.reset();
+    /// assert_eq!(Rc::strong_count(&shared), 1);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    ///
     /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
     /// [`allocation_api`]: https://doc.rust-lang.org/beta/unstable-book/library-features/allocator-api.html
     pub struct Blink<A = +BlinkAlloc<Global>> {
@@ -287,10 +443,112 @@ where
 {
     /// Drops all allocated values.
     /// And resets associated allocator instance.
+    ///
+    /// Values may allocate from this [`Blink`] when dropped,
+    /// values emplaced that way are dropped by the same reset.
+    /// Resetting this [`Blink`] from a dropped value is not allowed
+    /// and panics in debug builds.
+    ///
+    /// With `"tracing"` feature enabled emits debug event
+    /// with allocator statistics and number of dropped values.
     #[inline(always)]
     pub fn reset(&mut self) {
-        self.drop_list.reset();
+        #[cfg(feature = "tracing")]
+        let stats = self.reset_stats();
+        let _drop_count = self.drop_list.reset();
+        self.alloc.reset();
+        #[cfg(feature = "tracing")]
+        trace_reset(stats, _drop_count);
+    }
+
+    /// Drops all allocated values.
+    /// And resets associated allocator instance.
+    ///
+    /// Same as [`Blink::reset`], named after collections' `clear`.
+    /// Allocator keeps its last chunk, so following allocations
+    /// reuse memory without calling underlying allocator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// blink.put(42);
+    /// assert!(!blink.is_empty());
+    ///
+    /// blink.clear();
+    /// assert!(blink.is_empty());
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.reset();
+    }
+
+    /// Returns `true` if no values are emplaced into this [`Blink`]
+    /// and nothing is allocated from associated allocator
+    /// since creation or last reset.
+    ///
+    /// Values emplaced without drop are accounted
+    /// through the allocator, see [`BlinkAllocator::is_empty`].
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.drop_list.is_empty() && self.alloc.is_empty()
+    }
+
+    /// Drops all allocated values in order they were emplaced.
+    /// And resets associated allocator instance.
+    ///
+    /// Unlike [`Blink::reset`] which drops most recently emplaced values first,
+    /// this method is suitable for values that must be released
+    /// in acquisition order, like lock guards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// # use std::{cell::RefCell, rc::Rc};
+    /// struct Log(Rc<RefCell<Vec<u32>>>, u32);
+    ///
+    /// impl Drop for Log {
+    ///     fn drop(&mut self) {
+    ///         self.0.borrow_mut().push(self.1);
+    ///     }
+    /// }
+    ///
+    /// let log = Rc::new(RefCell::new(Vec::new()));
+    /// let mut blink = Blink::new();
+    /// blink.put(Log(log.clone(), 1));
+    /// blink.put(Log(log.clone(), 2));
+    /// blink.ordered_reset();
+    ///
+    /// assert_eq!(*log.borrow(), [1, 2]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn ordered_reset(&mut self) {
+        #[cfg(feature = "tracing")]
+        let stats = self.reset_stats();
+        let _drop_count = self.drop_list.ordered_reset();
         self.alloc.reset();
+        #[cfg(feature = "tracing")]
+        trace_reset(stats, _drop_count);
+    }
+
+    /// Collects allocator statistics for reset event.
+    /// Skips collection if the event would be discarded anyway.
+    #[cfg(feature = "tracing")]
+    #[inline(always)]
+    fn reset_stats(&self) -> Option<BlinkStats> {
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            self.alloc.blink_stats()
+        } else {
+            None
+        }
     }
 
     /// Allocates memory for a copy of the slice.
@@ -298,6 +556,7 @@ where
     /// Otherwise copies the slice into the allocated memory and returns
     /// mutable reference to the copy.
     #[inline]
+    #[allow(clippy::mut_from_ref)]
     unsafe fn _try_copy_slice<'a, T, E>(
         &'a self,
         slice: &[T],
@@ -316,6 +575,69 @@ where
         Ok(core::slice::from_raw_parts_mut(ptr, slice.len()))
     }
 
+    /// Allocates memory for concatenation of the slices
+    /// and copies them into it one after another.
+    /// If allocation fails, returns `Err`.
+    ///
+    /// Allocation is grown geometrically as slices are copied.
+    /// Growth happens in place unless other allocation is made in between.
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn _try_copy_chunks<'a, 'b, T, E>(
+        &'a self,
+        chunks: impl Iterator<Item = &'b [T]>,
+        alloc_err: impl FnOnce(Option<Layout>) -> E,
+    ) -> Result<&'a mut [T], E>
+    where
+        T: Copy + 'b,
+    {
+        let mut ptr = NonNull::<T>::dangling();
+        let mut len = 0usize;
+
+        // Zero-sized values need no memory.
+        let mut cap = if size_of::<T>() == 0 { usize::MAX } else { 0 };
+
+        for chunk in chunks {
+            let Some(new_len) = len.checked_add(chunk.len()) else {
+                return Err(alloc_err(None));
+            };
+
+            if new_len > cap {
+                let (new_cap, new_layout) = match Layout::array::<T>(new_len.max(cap * 2)) {
+                    Ok(layout) => (new_len.max(cap * 2), layout),
+                    Err(_) => match Layout::array::<T>(new_len) {
+                        Ok(layout) => (new_len, layout),
+                        Err(_) => return Err(alloc_err(None)),
+                    },
+                };
+
+                let result = if cap == 0 {
+                    self.alloc.allocate(new_layout)
+                } else {
+                    // Safety: `ptr` is allocated with array layout of `cap` elements.
+                    let old_layout = Layout::from_size_align_unchecked(
+                        cap * size_of::<T>(),
+                        core::mem::align_of::<T>(),
+                    );
+                    self.alloc.grow(ptr.cast(), old_layout, new_layout)
+                };
+
+                let Ok(new_ptr) = result else {
+                    return Err(alloc_err(Some(new_layout)));
+                };
+                ptr = new_ptr.cast();
+                cap = new_cap;
+            }
+
+            // Safety: `ptr` is allocated for at least `new_len` elements.
+            core::ptr::copy_nonoverlapping(chunk.as_ptr(), ptr.as_ptr().add(len), chunk.len());
+            len = new_len;
+        }
+
+        Ok(core::slice::from_raw_parts_mut(ptr.as_ptr(), len))
+    }
+
+    #[allow(clippy::mut_from_ref)]
     unsafe fn _try_emplace_drop<'a, T, I, G: 'a, E>(
         &'a self,
         init: I,
@@ -386,6 +708,7 @@ where
     /// Otherwise calls closure consuming `init`
     /// and initializes memory with closure result.
     #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
     unsafe fn _try_emplace<'a, T, I, G: 'a, E>(
         &'a self,
         init: I,
@@ -401,25 +724,124 @@ where
         }
     }
 
+    /// Allocates memory for a value and initializes it in place
+    /// with provided closure.
+    /// If allocation fails, returns `Err` with the closure.
+    /// If closure fails, deallocates memory and returns the error.
+    ///
+    /// Unlike [`Blink::_try_emplace`] this writes `T` directly
+    /// without `Result` wrapper around it.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn _try_emplace_in_place<T, F, G, E>(
+        &self,
+        f: F,
+        no_drop: bool,
+        err: impl FnOnce(G) -> E,
+        alloc_err: impl FnOnce(F, Layout) -> E,
+    ) -> Result<&mut T, E>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> Result<(), G>,
+    {
+        if !needs_drop::<T>() || no_drop {
+            let layout = Layout::new::<T>();
+            let Ok(ptr) = self.alloc.allocate(layout) else {
+                return Err(alloc_err(f, layout));
+            };
+
+            // Safety: `ptr` is a valid pointer to allocated memory.
+            // Allocated with this `T`'s layout.
+            let uninit = &mut *ptr.as_ptr().cast::<MaybeUninit<T>>();
+
+            match f(uninit) {
+                Ok(()) => Ok(uninit.assume_init_mut()),
+                Err(g) => {
+                    // Give memory back.
+                    self.alloc.deallocate(ptr.cast(), layout);
+                    Err(err(g))
+                }
+            }
+        } else {
+            let layout = Layout::new::<DropItem<T>>();
+            let Ok(ptr) = self.alloc.allocate(layout) else {
+                return Err(alloc_err(f, layout));
+            };
+
+            let mut result = Ok(());
+
+            // Safety: `ptr` is a valid pointer to allocated memory for type `DropItem<T>`.
+            let item = DropItem::init_value(ptr.cast(), f, |slot, f| result = f(slot));
+
+            match result {
+                Ok(()) => Ok(self.drop_list.add(item)),
+                Err(g) => {
+                    // Give memory back.
+                    self.alloc.deallocate(ptr.cast(), layout);
+                    Err(err(g))
+                }
+            }
+        }
+    }
+
+    /// Allocates memory for a value and moves `value` into the memory.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// Returns reference to the value and number of bytes
+    /// available at its address, which may exceed `size_of::<T>()`.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn _try_put_with_len<T>(&self, value: T, no_drop: bool) -> Result<(&mut T, usize), T> {
+        if !needs_drop::<T>() || no_drop {
+            let Ok(ptr) = self.alloc.allocate(Layout::new::<T>()) else {
+                return Err(value);
+            };
+
+            // Safety: `ptr` is a valid pointer to allocated memory.
+            // Allocated with this `T`'s layout.
+            let slot = ptr.as_ptr().cast::<T>();
+            ptr::write(slot, value);
+            Ok((&mut *slot, ptr.len()))
+        } else {
+            let Ok(ptr) = self.alloc.allocate(Layout::new::<DropItem<T>>()) else {
+                return Err(value);
+            };
+            let end = crate::addr(ptr.as_ptr().cast::<u8>()) + ptr.len();
+
+            // Safety: `ptr` is a valid pointer to allocated memory for type `DropItem<T>`.
+            let item = DropItem::init_value(ptr.cast(), value, |slot, value| {
+                slot.write(value);
+            });
+            let value = self.drop_list.add(item);
+            let len = end - crate::addr::<T>(value);
+            Ok((value, len))
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
     unsafe fn _try_emplace_drop_from_iter<'a, T: 'a, I, E>(
         &'a self,
         mut iter: I,
+        estimate: usize,
         err: impl FnOnce(&'a mut [T], Option<T>, Option<Layout>) -> E,
     ) -> Result<&'a mut [T], E>
     where
         I: Iterator<Item = T>,
     {
         if size_of::<T>() == 0 {
+            // Take elements from iterator without dropping them.
+            // Stop at `usize::MAX`.
+            // Drop exactly this number of elements on reset.
+            let count = saturating_forget_iter(iter);
+            if count == 0 {
+                return Ok(&mut []);
+            }
+
             let item_layout = Layout::new::<DropItem<[T; 0]>>();
             let Ok(ptr) = self.alloc.allocate(item_layout) else {
                 return Err(err(&mut [], None, Some(item_layout)));
             };
-            // Drain elements from iterator.
-            // Stop at `usize::MAX`.
-            // Drop exactly this number of elements on reset.
-            let count = saturating_drain_iter(iter);
             let (item, slice) = DropItem::init_slice(ptr.cast(), count);
-            self.drop_list.add(item);
+            self.drop_list.add_slice(item);
             return Ok(slice);
         }
 
@@ -473,7 +895,7 @@ where
                         // Safety: `item` was properly initialized.
                         let (item, slice) = unsafe { DropItem::init_slice(ptr, self.count) };
                         unsafe {
-                            self.drop_list.add(item);
+                            self.drop_list.add_slice(item);
                         }
                         slice
                     }
@@ -570,7 +992,8 @@ where
             drop_list: &self.drop_list,
         };
 
-        let (lower, _) = iter.size_hint();
+        // Estimate may exceed actual number of elements.
+        let lower = iter.size_hint().0.max(estimate);
 
         if lower != 0 {
             if let Err(layout) = guard.fill(lower.max(FASTER_START), &mut None, &mut iter) {
@@ -602,9 +1025,11 @@ where
         }
     }
 
+    #[allow(clippy::mut_from_ref)]
     unsafe fn _try_emplace_no_drop_from_iter<'a, T: 'a, I, E>(
         &'a self,
         mut iter: I,
+        estimate: usize,
         err: impl FnOnce(&'a mut [T], Option<T>, Option<Layout>) -> E,
     ) -> Result<&'a mut [T], E>
     where
@@ -738,7 +1163,8 @@ where
             alloc: &self.alloc,
         };
 
-        let (lower, _) = iter.size_hint();
+        // Estimate may exceed actual number of elements.
+        let lower = iter.size_hint().0.max(estimate);
 
         if lower != 0 {
             if let Err(layout) = guard.fill(lower.max(FASTER_START), &mut None, &mut iter) {
@@ -770,35 +1196,228 @@ where
         }
     }
 
+    /// Makes sure that `additional` values of type `T`
+    /// can be emplaced as the next allocation without new chunk.
+    #[inline]
+    fn _try_reserve<T>(&self, additional: usize, no_drop: bool) -> Result<(), Option<Layout>> {
+        if size_of::<T>() == 0 || additional == 0 {
+            return Ok(());
+        }
+
+        let Ok(array_layout) = Layout::array::<T>(additional) else {
+            return Err(None);
+        };
+
+        let layout = if needs_drop::<T>() && !no_drop {
+            match Layout::new::<DropItem<[T; 0]>>().extend(array_layout) {
+                Ok((layout, _)) => layout,
+                Err(_) => return Err(None),
+            }
+        } else {
+            array_layout
+        };
+
+        let Ok(ptr) = self.alloc.allocate(layout) else {
+            return Err(Some(layout));
+        };
+
+        // Safety: `ptr` is the last allocation made with `layout`.
+        // Deallocating it leaves the space for the next allocation.
+        unsafe { self.alloc.deallocate(ptr.cast(), layout) };
+        Ok(())
+    }
+
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn _try_emplace_from_exact_iter<'a, T: 'a, I, E>(
+        &'a self,
+        mut iter: I,
+        no_drop: bool,
+        err: impl FnOnce(&'a mut [T], Option<T>, Option<Layout>) -> E,
+    ) -> Result<&'a mut [T], E>
+    where
+        I: ExactFill<Item = T>,
+    {
+        if size_of::<T>() == 0 {
+            return self._try_emplace_from_iter(iter, no_drop, err);
+        }
+
+        let (len, _) = iter.size_hint();
+        if len == 0 {
+            return Ok(&mut []);
+        }
+
+        let Ok(array_layout) = Layout::array::<T>(len) else {
+            return Err(err(&mut [], None, None));
+        };
+
+        let drop = needs_drop::<T>() && !no_drop;
+
+        let (full_layout, array_offset) = if drop {
+            let item_layout = Layout::new::<DropItem<[T; 0]>>();
+            let Ok(layouts) = item_layout.extend(array_layout) else {
+                return Err(err(&mut [], None, None));
+            };
+            layouts
+        } else {
+            (array_layout, 0)
+        };
+
+        let Ok(ptr) = self.alloc.allocate(full_layout) else {
+            return Err(err(&mut [], None, Some(full_layout)));
+        };
+
+        // Safety: `array_offset` is within allocated memory.
+        let array_ptr = unsafe { ptr.cast::<u8>().as_ptr().add(array_offset).cast::<T>() };
+
+        let mut count = 0;
+        while count < len {
+            let Some(elem) = iter.next() else {
+                debug_assert!(
+                    false,
+                    "`ExactFill` iterator yielded less elements than promised"
+                );
+                break;
+            };
+            // Safety: `array_ptr` is a valid pointer to allocated memory for type `[T; len]`.
+            unsafe { ptr::write(array_ptr.add(count), elem) };
+            count += 1;
+        }
+
+        if count == 0 {
+            // Safety: Memory was allocated above with this layout.
+            unsafe { self.alloc.deallocate(ptr.cast(), full_layout) };
+            return Ok(&mut []);
+        }
+
+        if drop {
+            // Safety: `count` elements were initialized.
+            let (item, slice) = unsafe { DropItem::init_slice(ptr.cast(), count) };
+            unsafe { self.drop_list.add_slice(item) };
+            Ok(slice)
+        } else {
+            // Safety: `count` elements were initialized.
+            Ok(unsafe { core::slice::from_raw_parts_mut(array_ptr, count) })
+        }
+    }
+
     /// Allocates memory for a value and emplaces value into the memory
     /// using init value and provided closure.
     /// If allocation fails, returns `Err(init)`.
     /// Otherwise calls closure consuming `init`
     /// and initializes memory with closure result.
     #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
     unsafe fn _try_emplace_from_iter<'a, T: 'a, I, E>(
         &'a self,
         iter: I,
         no_drop: bool,
         err: impl FnOnce(&'a mut [T], Option<T>, Option<Layout>) -> E,
-    ) -> Result<&mut [T], E>
+    ) -> Result<&'a mut [T], E>
     where
         I: IntoIterator<Item = T>,
     {
-        if !needs_drop::<T>() || no_drop {
-            self._try_emplace_no_drop_from_iter(iter.into_iter(), err)
-        } else {
-            self._try_emplace_drop_from_iter(iter.into_iter(), err)
-        }
+        self._try_emplace_from_iter_estimated(iter, 0, no_drop, err)
     }
-}
 
-/// Provides interface for emplacing values.
-/// Created by [`Blink::emplace`], [`Blink::emplace_no_drop`]
-/// and [`Blink::emplace_unchecked`].
-pub struct Emplace<'a, A, T, R = &'a mut T, S = &'a mut [T]> {
-    blink: &'a Blink<A>,
-    no_drop: bool,
+    /// Same as [`Blink::_try_emplace_from_iter`] but the first allocation
+    /// has space for at least `estimate` elements.
+    /// `estimate` is not required to be a lower bound.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn _try_emplace_from_iter_estimated<'a, T: 'a, I, E>(
+        &'a self,
+        iter: I,
+        estimate: usize,
+        no_drop: bool,
+        err: impl FnOnce(&'a mut [T], Option<T>, Option<Layout>) -> E,
+    ) -> Result<&'a mut [T], E>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        if !needs_drop::<T>() || no_drop {
+            self._try_emplace_no_drop_from_iter(iter.into_iter(), estimate, err)
+        } else {
+            self._try_emplace_drop_from_iter(iter.into_iter(), estimate, err)
+        }
+    }
+}
+
+/// Future returned by [`Blink::emplace_from_async`].
+///
+/// Resolves to reference to the output of the wrapped future
+/// placed into the `Blink` instance.
+#[cfg(all(feature = "async", not(no_global_oom_handling)))]
+#[must_use = "futures do nothing unless polled"]
+pub struct EmplaceAsyncFuture<'a, A, T, F> {
+    blink: &'a Blink<A>,
+    future: F,
+    marker: PhantomData<fn() -> T>,
+}
+
+#[cfg(all(feature = "async", not(no_global_oom_handling)))]
+impl<'a, A, T, F> Future for EmplaceAsyncFuture<'a, A, T, F>
+where
+    A: BlinkAllocator,
+    T: 'static,
+    F: Future<Output = T>,
+{
+    type Output = &'a mut T;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<&'a mut T> {
+        let blink = self.blink;
+
+        // Safety: `future` is never moved out of pinned `self`.
+        let future = unsafe { self.map_unchecked_mut(|me| &mut me.future) };
+        match future.poll(cx) {
+            Poll::Ready(value) => Poll::Ready(blink.put(value)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Future returned by [`Blink::try_emplace_from_async`].
+///
+/// Resolves to reference to the output of the wrapped future
+/// placed into the `Blink` instance.
+/// If allocation fails, resolves to `Err(output)`.
+#[cfg(feature = "async")]
+#[must_use = "futures do nothing unless polled"]
+pub struct TryEmplaceAsyncFuture<'a, A, T, F> {
+    blink: &'a Blink<A>,
+    future: F,
+    marker: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "async")]
+impl<'a, A, T, F> Future for TryEmplaceAsyncFuture<'a, A, T, F>
+where
+    A: BlinkAllocator,
+    T: 'static,
+    F: Future<Output = T>,
+{
+    type Output = Result<&'a mut T, T>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<&'a mut T, T>> {
+        let blink = self.blink;
+
+        // Safety: `future` is never moved out of pinned `self`.
+        let future = unsafe { self.map_unchecked_mut(|me| &mut me.future) };
+        match future.poll(cx) {
+            Poll::Ready(value) => Poll::Ready(blink.try_put(value)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Provides interface for emplacing values.
+/// Created by [`Blink::emplace`], [`Blink::emplace_no_drop`]
+/// and [`Blink::emplace_unchecked`].
+pub struct Emplace<'a, A, T, R = &'a mut T, S = &'a mut [T]> {
+    blink: &'a Blink<A>,
+    no_drop: bool,
     marker: PhantomData<fn(T) -> (R, S)>,
 }
 
@@ -850,6 +1469,116 @@ where
         )
     }
 
+    /// Allocates memory for a value and moves `value` into the memory.
+    /// If allocation fails, returns `Err(value)`.
+    /// On success returns reference to the emplaced value
+    /// and number of bytes allocated for it.
+    ///
+    /// Allocated length is at least `size_of::<T>()` and may exceed it.
+    #[inline(always)]
+    pub fn try_value_with_len(&self, value: T) -> Result<(R, usize), T> {
+        unsafe { self.blink._try_put_with_len(value, self.no_drop) }
+            .map(|(value, len)| (R::coerce(value), len))
+    }
+
+    /// Allocates memory for a value and moves `value` into the memory.
+    /// Returns reference to the emplaced value
+    /// and number of bytes allocated for it.
+    /// If allocation fails, diverges.
+    ///
+    /// Allocated length is at least `size_of::<T>()` and may exceed it.
+    /// Useful to report buffer size across FFI boundary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let (value, len) = blink.emplace().value_with_len([1u8; 3]);
+    /// assert_eq!(*value, [1, 1, 1]);
+    /// assert!(len >= 3);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn value_with_len(&self, value: T) -> (R, usize) {
+        match self.try_value_with_len(value) {
+            Ok(result) => result,
+            Err(_) => handle_alloc_error(Layout::new::<T>()),
+        }
+    }
+
+    /// Allocates memory for a value and moves `value` into the memory.
+    /// Returns raw pointer to the emplaced value.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// See [`Emplace::value_raw`].
+    #[inline(always)]
+    pub fn try_value_raw(&self, value: T) -> Result<*mut T, T> {
+        unsafe {
+            self.blink._try_emplace(
+                value,
+                |slot, value| {
+                    slot.write(Ok::<_, ManuallyDrop<Infallible>>(value));
+                },
+                self.no_drop,
+                |never| match never {},
+                |init, _| init,
+            )
+        }
+        .map(|value| value as *mut T)
+    }
+
+    /// Allocates memory for a value and moves `value` into the memory.
+    /// Returns raw pointer to the emplaced value.
+    /// If allocation fails, diverges.
+    ///
+    /// Value is registered for drop same as with [`Emplace::value`].
+    /// Pointer is valid until [`Blink`] is reset or dropped
+    /// and must not be used after that.
+    /// Unlike returned reference, pointer does not borrow [`Blink`],
+    /// so it can be stored in FFI structures.
+    /// Ensuring it does not outlive the arena is up to the caller.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// #[repr(C)]
+    /// struct Handle {
+    ///     value: *mut u32,
+    /// }
+    ///
+    /// let mut blink = Blink::new();
+    /// let handle = Handle {
+    ///     value: blink.emplace().value_raw(42),
+    /// };
+    /// assert_eq!(unsafe { *handle.value }, 42);
+    /// blink.reset();
+    /// // `handle.value` is dangling now.
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn value_raw(&self, value: T) -> *mut T {
+        unsafe {
+            self.blink._try_emplace(
+                value,
+                |slot, value| {
+                    slot.write(Ok::<_, ManuallyDrop<Infallible>>(value));
+                },
+                self.no_drop,
+                identity,
+                |_, layout| handle_alloc_error(layout),
+            )
+        }
+        .safe_ok()
+    }
+
     /// Allocates memory for a value.
     /// On success invokes closure and initialize the value.
     /// Returns reference to the value.
@@ -949,6 +1678,76 @@ where
         .map(R::coerce)
     }
 
+    /// Allocates memory for a value.
+    /// If allocation fails, returns error with closure.
+    /// On success invokes closure to initialize the value in place.
+    /// If closure fails, returns the error and gives memory back.
+    /// Returns reference to the value.
+    ///
+    /// Unlike [`Emplace::try_with_fallible`] the value is written directly
+    /// into allocated memory, avoiding space overhead
+    /// of `Result<T, E>` layout.
+    ///
+    /// # Safety
+    ///
+    /// If closure returns `Ok(())` it must have initialized the value.
+    #[inline(always)]
+    pub unsafe fn try_with_uninit<F, E>(&self, f: F) -> Result<R, Result<E, F>>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> Result<(), E>,
+    {
+        unsafe {
+            self.blink
+                ._try_emplace_in_place(f, self.no_drop, Ok, |f, _| Err(f))
+        }
+        .map(R::coerce)
+    }
+
+    /// Allocates memory for a value.
+    /// On success invokes closure to initialize the value in place.
+    /// If closure fails, returns the error and gives memory back.
+    /// Returns reference to the value.
+    /// If allocation fails, diverges.
+    ///
+    /// Unlike [`Emplace::with_fallible`] the value is written directly
+    /// into allocated memory, avoiding space overhead
+    /// of `Result<T, E>` layout.
+    ///
+    /// # Safety
+    ///
+    /// If closure returns `Ok(())` it must have initialized the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let value = unsafe {
+    ///     blink.emplace().with_uninit(|slot| {
+    ///         slot.write([42u64; 16]);
+    ///         Ok::<(), ()>(())
+    ///     })
+    /// };
+    /// assert_eq!(value.unwrap()[15], 42);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub unsafe fn with_uninit<F, E>(&self, f: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut MaybeUninit<T>) -> Result<(), E>,
+    {
+        unsafe {
+            self.blink
+                ._try_emplace_in_place(f, self.no_drop, identity, |_, layout| {
+                    handle_alloc_error(layout)
+                })
+        }
+        .map(R::coerce)
+    }
+
     /// Allocates memory for an array and initializes it with
     /// values from iterator.
     /// Uses iterator hints to allocate memory.
@@ -974,6 +1773,52 @@ where
         .map(S::coerce)
     }
 
+    /// Allocates memory for an array and initializes it with
+    /// values from iterator.
+    /// Works like [`Emplace::try_from_iter`],
+    /// but if allocation fails, returns slice of values emplaced so far
+    /// and the rest of the iterator.
+    /// The element that was taken from iterator and not emplaced
+    /// is yielded first.
+    ///
+    /// This allows caller to allocate more space elsewhere
+    /// and continue with the remaining values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let slice = blink.emplace().try_from_iter_recover(0..5).unwrap();
+    /// assert_eq!(slice, [0, 1, 2, 3, 4]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    #[allow(clippy::type_complexity)]
+    pub fn try_from_iter_recover<I>(
+        &self,
+        iter: I,
+    ) -> Result<S, (S, Chain<option::IntoIter<T>, I::IntoIter>)>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        let result = unsafe {
+            // Iterator is borrowed, so the rest of it is kept on failure.
+            self.blink._try_emplace_from_iter(
+                &mut iter,
+                self.no_drop,
+                |slice: &'a mut [T], value, _| (slice, value),
+            )
+        };
+        match result {
+            Ok(slice) => Ok(S::coerce(slice)),
+            Err((slice, value)) => Err((S::coerce(slice), value.into_iter().chain(iter))),
+        }
+    }
+
     /// Allocates memory for an array and initializes it with
     /// values from iterator.
     /// Uses iterator hints to allocate memory.
@@ -1004,65 +1849,35 @@ where
             .safe_ok(),
         )
     }
-}
 
-impl<A> Blink<A>
-where
-    A: BlinkAllocator,
-{
-    /// Puts value into this `Blink` instance.
-    /// Returns reference to the value.
-    ///
-    /// Effectively extends lifetime of the value
-    /// from local scope to the reset scope.
-    ///
-    /// For more flexible value placement see
-    /// [`Blink::emplace`], [`Blink::emplace_no_drop`] and
-    /// [`Blink::emplace_unchecked`].
-    ///
-    /// # Example
+    /// Allocates memory for an array and initializes it with
+    /// values from iterator that reports exact length.
+    /// Allocates memory for all values at once.
+    /// If allocation fails, returns empty slice.
     ///
-    /// ```
-    /// # #[cfg(feature = "alloc")] fn main() {
-    /// # use blink_alloc::Blink;
-    /// let mut blink = Blink::new();
-    /// let foo = blink.put(42);
-    /// assert_eq!(*foo, 42);
-    /// *foo = 24;
-    /// blink.reset();
-    /// // assert_eq!(*foo, 24); // Cannot compile. `foo` does not outlive reset.
-    /// # }
-    /// # #[cfg(not(feature = "alloc"))] fn main() {}
-    /// ```
-    #[cfg(not(no_global_oom_handling))]
+    /// See [`ExactFill`].
     #[inline(always)]
-    #[allow(clippy::mut_from_ref)]
-    pub fn put<T: 'static>(&self, value: T) -> &mut T {
+    pub fn try_from_exact_iter<I>(&self, iter: I) -> Result<S, (S, Option<T>)>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactFill,
+    {
         unsafe {
-            self._try_emplace(
-                value,
-                |slot, value| {
-                    slot.write(Ok::<_, ManuallyDrop<Infallible>>(value));
-                },
-                false,
-                identity,
-                |_, layout| handle_alloc_error(layout),
+            self.blink._try_emplace_from_exact_iter(
+                iter.into_iter(),
+                self.no_drop,
+                |slice: &'a mut [T], value, _| (S::coerce(slice), value),
             )
         }
-        .safe_ok()
+        .map(S::coerce)
     }
 
-    /// Puts value into this `Blink` instance.
-    /// Returns reference to the value.
-    ///
-    /// The value will not be dropped when `Blink` is reset.
-    ///
-    /// Effectively extends lifetime of the value
-    /// from local scope to the reset scope.
+    /// Allocates memory for an array and initializes it with
+    /// values from iterator that reports exact length.
+    /// Allocates memory for all values at once.
+    /// If allocation fails, diverges.
     ///
-    /// For more flexible value placement see
-    /// [`Blink::emplace`], [`Blink::emplace_no_drop`] and
-    /// [`Blink::emplace_unchecked`].
+    /// See [`ExactFill`].
     ///
     /// # Example
     ///
@@ -1070,36 +1885,1105 @@ where
     /// # #[cfg(feature = "alloc")] fn main() {
     /// # use blink_alloc::Blink;
     /// let mut blink = Blink::new();
-    /// let foo = blink.put(42);
-    /// assert_eq!(*foo, 42);
-    /// *foo = 24;
-    /// blink.reset();
-    /// // assert_eq!(*foo, 24); // Cannot compile. `foo` does not outlive reset.
+    /// let slice = blink.emplace().from_exact_iter(0..5);
+    /// assert_eq!(slice, [0, 1, 2, 3, 4]);
+    ///
+    /// let slice = blink.emplace().from_exact_iter([1u8, 2, 3]);
+    /// assert_eq!(slice, [1, 2, 3]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn from_exact_iter<I>(&self, iter: I) -> S
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactFill,
+    {
+        S::coerce(
+            unsafe {
+                self.blink._try_emplace_from_exact_iter(
+                    iter.into_iter(),
+                    self.no_drop,
+                    |_, _, layout| match layout {
+                        Some(layout) => handle_alloc_error(layout),
+                        None => size_overflow(),
+                    },
+                )
+            }
+            .safe_ok(),
+        )
+    }
+
+    /// Allocates memory for an array and initializes it with
+    /// values from iterator in reversed order.
+    /// Works with any iterator, not only [`DoubleEndedIterator`].
+    /// Values are collected in iteration order and then reversed in place.
+    /// If allocation fails, returns slice of values emplaced so far
+    /// in reversed order.
+    /// And one element that was taken from iterator and not emplaced.
+    #[inline(always)]
+    pub fn try_from_iter_rev<I>(&self, iter: I) -> Result<S, (S, Option<T>)>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        unsafe {
+            self.blink
+                ._try_emplace_from_iter(iter, self.no_drop, |slice: &'a mut [T], value, _| {
+                    slice.reverse();
+                    (S::coerce(slice), value)
+                })
+        }
+        .map(|slice| {
+            slice.reverse();
+            S::coerce(slice)
+        })
+    }
+
+    /// Allocates memory for an array and initializes it with
+    /// values from iterator in reversed order.
+    /// Works with any iterator, not only [`DoubleEndedIterator`].
+    /// Values are collected in iteration order and then reversed in place.
+    /// If allocation fails, diverges.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let slice = blink.emplace().from_iter_rev((0..10).filter(|x| x % 3 != 0));
+    /// assert_eq!(slice, [8, 7, 5, 4, 2, 1]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn from_iter_rev<I>(&self, iter: I) -> S
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let slice = unsafe {
+            self.blink
+                ._try_emplace_from_iter(iter, self.no_drop, |_, _, layout| match layout {
+                    Some(layout) => handle_alloc_error(layout),
+                    None => size_overflow(),
+                })
+        }
+        .safe_ok();
+        slice.reverse();
+        S::coerce(slice)
+    }
+
+    /// Reserves contiguous space for at least `additional` elements.
+    /// If allocation fails, returns `Err(AllocError)`.
+    ///
+    /// Following [`from_iter`](Emplace::from_iter) grows its array
+    /// in place until reserved space is exhausted,
+    /// avoiding new chunks when iterator's size hint is not accurate.
+    /// Any other allocation made in between may use reserved space.
+    #[inline(always)]
+    pub fn try_reserve(&self, additional: usize) -> Result<(), AllocError> {
+        self.blink
+            ._try_reserve::<T>(additional, self.no_drop)
+            .map_err(|_| AllocError)
+    }
+
+    /// Reserves contiguous space for at least `additional` elements.
+    /// If allocation fails, diverges.
+    ///
+    /// Following [`from_iter`](Emplace::from_iter) grows its array
+    /// in place until reserved space is exhausted,
+    /// avoiding new chunks when iterator's size hint is not accurate.
+    /// Any other allocation made in between may use reserved space.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let emplace = blink.emplace::<u32>();
+    /// emplace.reserve(100);
+    /// let slice = emplace.from_iter((0..100).filter(|x| x % 2 == 0));
+    /// assert_eq!(slice.len(), 50);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn reserve(&self, additional: usize) {
+        self.blink
+            ._try_reserve::<T>(additional, self.no_drop)
+            .map_err(|layout| match layout {
+                Some(layout) => handle_alloc_error(layout),
+                None => size_overflow(),
+            })
+            .safe_ok()
+    }
+
+    /// Allocates memory for an array of `len` elements
+    /// and initializes each with [`T::default()`](Default::default).
+    /// If allocation fails, returns `None`.
+    ///
+    /// If `T::default()` panics, already created values
+    /// are dropped on reset as usual.
+    #[inline(always)]
+    pub fn try_slice_default(&self, len: usize) -> Option<S>
+    where
+        T: Default,
+    {
+        unsafe {
+            self.blink._try_emplace_from_iter(
+                core::iter::repeat_with(T::default).take(len),
+                self.no_drop,
+                |_, _, _| (),
+            )
+        }
+        .ok()
+        .map(S::coerce)
+    }
+
+    /// Allocates memory for an array of `len` elements
+    /// and initializes each with [`T::default()`](Default::default).
+    /// If allocation fails, diverges.
+    ///
+    /// If `T::default()` panics, already created values
+    /// are dropped on reset as usual.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let slice = blink.emplace::<String>().slice_default(3);
+    /// slice[1].push_str("foo");
+    /// assert_eq!(slice, ["", "foo", ""]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn slice_default(&self, len: usize) -> S
+    where
+        T: Default,
+    {
+        S::coerce(
+            unsafe {
+                self.blink._try_emplace_from_iter(
+                    core::iter::repeat_with(T::default).take(len),
+                    self.no_drop,
+                    |_, _, layout| match layout {
+                        Some(layout) => handle_alloc_error(layout),
+                        None => size_overflow(),
+                    },
+                )
+            }
+            .safe_ok(),
+        )
+    }
+}
+
+impl<A> Blink<A>
+where
+    A: BlinkAllocator,
+{
+    /// Puts value into this `Blink` instance.
+    /// Returns reference to the value.
+    ///
+    /// Effectively extends lifetime of the value
+    /// from local scope to the reset scope.
+    ///
+    /// For more flexible value placement see
+    /// [`Blink::emplace`], [`Blink::emplace_no_drop`] and
+    /// [`Blink::emplace_unchecked`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let foo = blink.put(42);
+    /// assert_eq!(*foo, 42);
+    /// *foo = 24;
+    /// blink.reset();
+    /// // assert_eq!(*foo, 24); // Cannot compile. `foo` does not outlive reset.
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn put<T: 'static>(&self, value: T) -> &mut T {
+        unsafe {
+            self._try_emplace(
+                value,
+                |slot, value| {
+                    slot.write(Ok::<_, ManuallyDrop<Infallible>>(value));
+                },
+                false,
+                identity,
+                |_, layout| handle_alloc_error(layout),
+            )
+        }
+        .safe_ok()
+    }
+
+    /// Puts value into this `Blink` instance.
+    /// Returns reference to the value.
+    /// If allocation fails, returns `Err(value)`.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_put<T: 'static>(&self, value: T) -> Result<&mut T, T> {
+        self.emplace().try_value(value)
+    }
+
+    /// Moves boxed value into this `Blink` instance
+    /// and frees the box.
+    /// Returns reference to the value.
+    ///
+    /// The value will be dropped when this `Blink` is reset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let foo = blink.put_boxed(Box::new(String::from("foo")));
+    /// foo.push_str("bar");
+    /// assert_eq!(foo, "foobar");
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(all(feature = "alloc", not(no_global_oom_handling)))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn put_boxed<T: 'static>(&self, boxed: alloc::boxed::Box<T>) -> &mut T {
+        unsafe {
+            self._try_emplace(
+                boxed,
+                |slot, boxed| {
+                    slot.write(Ok::<_, ManuallyDrop<Infallible>>(*boxed));
+                },
+                false,
+                identity,
+                |_, layout| handle_alloc_error(layout),
+            )
+        }
+        .safe_ok()
+    }
+
+    /// Moves boxed value into this `Blink` instance
+    /// and frees the box.
+    /// Returns reference to the value.
+    /// If allocation fails, returns `Err(boxed)`.
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_put_boxed<T: 'static>(
+        &self,
+        boxed: alloc::boxed::Box<T>,
+    ) -> Result<&mut T, alloc::boxed::Box<T>> {
+        unsafe {
+            self._try_emplace(
+                boxed,
+                |slot, boxed| {
+                    slot.write(Ok::<_, ManuallyDrop<Infallible>>(*boxed));
+                },
+                false,
+                |never| match never {},
+                |boxed, _| boxed,
+            )
+        }
+    }
+
+    /// Puts value into this `Blink` instance.
+    /// Returns reference to the value.
+    ///
+    /// The value will not be dropped when `Blink` is reset.
+    ///
+    /// Effectively extends lifetime of the value
+    /// from local scope to the reset scope.
+    ///
+    /// For more flexible value placement see
+    /// [`Blink::emplace`], [`Blink::emplace_no_drop`] and
+    /// [`Blink::emplace_unchecked`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let foo = blink.put(42);
+    /// assert_eq!(*foo, 42);
+    /// *foo = 24;
+    /// blink.reset();
+    /// // assert_eq!(*foo, 24); // Cannot compile. `foo` does not outlive reset.
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn put_no_drop<T>(&self, value: T) -> &mut T {
+        unsafe {
+            self._try_emplace(
+                value,
+                |slot, value| {
+                    slot.write(Ok::<_, ManuallyDrop<Infallible>>(value));
+                },
+                true,
+                identity,
+                |_, layout| handle_alloc_error(layout),
+            )
+        }
+        .safe_ok()
+    }
+
+    /// Puts value into this `Blink` instance.
+    /// Returns reference to the value.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// The value will not be dropped when `Blink` is reset.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_put_no_drop<T>(&self, value: T) -> Result<&mut T, T> {
+        self.emplace_no_drop().try_value(value)
+    }
+
+    /// Puts values from iterator into this `Blink` instance
+    /// as a single slice.
+    /// Returns reference to the slice.
+    /// If allocation fails, diverges.
+    ///
+    /// Values will be dropped when `Blink` is reset.
+    /// Faster than putting values one by one.
+    /// Shorthand for `blink.emplace().from_iter(values)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let names = blink.batch_put(["a", "b", "c"].map(String::from));
+    /// names[1].push('!');
+    /// assert_eq!(names, ["a", "b!", "c"]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn batch_put<T: 'static>(&self, values: impl IntoIterator<Item = T>) -> &mut [T] {
+        self.emplace().from_iter(values.into_iter())
+    }
+
+    /// Puts values from iterator into this `Blink` instance
+    /// as a single slice.
+    /// Returns reference to the slice.
+    /// If allocation fails, returns `Err(AllocError)`.
+    ///
+    /// Values will be dropped when `Blink` is reset.
+    /// Values emplaced before allocation failure are dropped on reset as well,
+    /// the rest of the values are dropped before this method returns.
+    /// Use [`Emplace::try_from_iter`] to get partially emplaced values.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_batch_put<T: 'static>(
+        &self,
+        values: impl IntoIterator<Item = T>,
+    ) -> Result<&mut [T], AllocError> {
+        self.emplace().try_from_iter(values).map_err(|_| AllocError)
+    }
+
+    /// Puts key-value pairs from iterator into this `Blink` instance
+    /// as a single slice.
+    /// Returns reference to the slice.
+    /// If allocation fails, diverges.
+    ///
+    /// Pairs will be dropped when `Blink` is reset.
+    /// Useful for building lookup tables without intermediate
+    /// heap allocations.
+    ///
+    /// See [`Blink::emplace_sorted_map_iter`] for slice sorted by key.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn emplace_map_iter<K: 'static, V: 'static>(
+        &self,
+        pairs: impl IntoIterator<Item = (K, V)>,
+    ) -> &mut [(K, V)] {
+        self.batch_put(pairs)
+    }
+
+    /// Puts key-value pairs from iterator into this `Blink` instance
+    /// as a single slice.
+    /// Returns reference to the slice.
+    /// If allocation fails, returns `Err(AllocError)`.
+    ///
+    /// Pairs will be dropped when `Blink` is reset.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_emplace_map_iter<K: 'static, V: 'static>(
+        &self,
+        pairs: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<&mut [(K, V)], AllocError> {
+        self.try_batch_put(pairs)
+    }
+
+    /// Puts key-value pairs from iterator into this `Blink` instance
+    /// as a single slice sorted by key.
+    /// Returns reference to the slice.
+    /// If allocation fails, diverges.
+    ///
+    /// Pairs are sorted in place with unstable sort,
+    /// so order of pairs with equal keys is unspecified.
+    /// Sorted slice allows lookups with [`binary_search_by`](slice::binary_search_by).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let table = blink.emplace_sorted_map_iter([("c", 3), ("a", 1), ("b", 2)]);
+    /// assert_eq!(table, [("a", 1), ("b", 2), ("c", 3)]);
+    ///
+    /// let idx = table.binary_search_by(|(key, _)| key.cmp(&"b")).unwrap();
+    /// assert_eq!(table[idx].1, 2);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn emplace_sorted_map_iter<K: Ord + 'static, V: 'static>(
+        &self,
+        pairs: impl IntoIterator<Item = (K, V)>,
+    ) -> &mut [(K, V)] {
+        let slice = self.batch_put(pairs);
+        slice.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        slice
+    }
+
+    /// Puts key-value pairs from iterator into this `Blink` instance
+    /// as a single slice sorted by key.
+    /// Returns reference to the slice.
+    /// If allocation fails, returns `Err(AllocError)`.
+    ///
+    /// Pairs are sorted in place with unstable sort,
+    /// so order of pairs with equal keys is unspecified.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_emplace_sorted_map_iter<K: Ord + 'static, V: 'static>(
+        &self,
+        pairs: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<&mut [(K, V)], AllocError> {
+        let slice = self.try_batch_put(pairs)?;
+        slice.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(slice)
+    }
+
+    /// Puts value contained in `Some` into this `Blink` instance.
+    /// Returns reference to the value or `None` if `value` is `None`.
+    ///
+    /// Returns `Err(inner)` if allocation fails.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_emplace_option<T: 'static>(&self, value: Option<T>) -> Result<Option<&mut T>, T> {
+        match value {
+            None => Ok(None),
+            Some(inner) => self.emplace().try_value(inner).map(Some),
+        }
+    }
+
+    /// Puts value contained in `Ok` into this `Blink` instance.
+    /// Returns reference to the value.
+    ///
+    /// If `value` is `Err(err)`, returns `Err((err, context))`.
+    /// If allocation fails, value is dropped and
+    /// `Err((E::from(AllocError), context))` is returned.
+    /// On success `context` is dropped.
+    ///
+    /// Allows forwarding errors with context attached using `?` operator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// # use allocator_api2::alloc::AllocError;
+    /// # use core::ops::Range;
+    /// #[derive(Debug, PartialEq)]
+    /// enum Error {
+    ///     Parse,
+    ///     OutOfMemory,
+    /// }
+    ///
+    /// impl From<AllocError> for Error {
+    ///     fn from(_: AllocError) -> Self {
+    ///         Error::OutOfMemory
+    ///     }
+    /// }
+    ///
+    /// fn parse<'a>(
+    ///     blink: &'a Blink,
+    ///     s: &str,
+    ///     span: Range<usize>,
+    /// ) -> Result<&'a mut u32, (Error, Range<usize>)> {
+    ///     let value = s[span.clone()].parse().map_err(|_| Error::Parse);
+    ///     blink.emplace_with_context(value, span)
+    /// }
+    ///
+    /// let blink = Blink::new();
+    /// assert_eq!(parse(&blink, "x = 42", 4..6).copied(), Ok(42));
+    /// assert_eq!(parse(&blink, "x = 42", 0..1), Err((Error::Parse, 0..1)));
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn emplace_with_context<T: 'static, E, C>(
+        &self,
+        value: Result<T, E>,
+        context: C,
+    ) -> Result<&mut T, (E, C)>
+    where
+        E: From<AllocError>,
+    {
+        match value {
+            Err(err) => Err((err, context)),
+            Ok(value) => match self.emplace().try_value(value) {
+                Ok(value) => Ok(value),
+                Err(_) => Err((E::from(AllocError), context)),
+            },
+        }
+    }
+
+    /// Puts value contained in `Some` into this `Blink` instance.
+    /// Returns reference to the value or `None` if `value` is `None`.
+    ///
+    /// Allows forwarding `None` with `?` operator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// fn parse<'a>(blink: &'a Blink, s: &str) -> Option<&'a mut u32> {
+    ///     let value = blink.emplace_option(s.parse().ok())?;
+    ///     *value += 1;
+    ///     Some(value)
+    /// }
+    ///
+    /// let blink = Blink::new();
+    /// assert_eq!(parse(&blink, "41").copied(), Some(42));
+    /// assert_eq!(parse(&blink, "foo"), None);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn emplace_option<T: 'static>(&self, value: Option<T>) -> Option<&mut T> {
+        value.map(|inner| self.emplace().value(inner))
+    }
+
+    /// Allocates memory for a value and initializes it in place with `init`.
+    /// Returns reference to the value or `None` if allocation fails.
+    ///
+    /// Useful for values that must know their address
+    /// before initialization begins.
+    ///
+    /// # Safety
+    ///
+    /// `init` must fully initialize the value.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn try_emplace_then_init<T: 'static>(
+        &self,
+        init: impl FnOnce(&mut MaybeUninit<T>),
+    ) -> Option<&mut T> {
+        unsafe {
+            self.emplace().try_with_uninit(|slot| {
+                init(slot);
+                Ok::<(), Infallible>(())
+            })
+        }
+        .ok()
+    }
+
+    /// Allocates memory for a value and initializes it in place with `init`.
+    /// Returns reference to the value.
+    ///
+    /// Useful for values that must know their address
+    /// before initialization begins.
+    ///
+    /// # Safety
+    ///
+    /// `init` must fully initialize the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// struct Node {
+    ///     this: *const Node,
+    /// }
+    ///
+    /// let blink = Blink::new();
+    /// let node = unsafe {
+    ///     blink.emplace_then_init(|slot| {
+    ///         let this = slot.as_ptr();
+    ///         slot.write(Node { this });
+    ///     })
+    /// };
+    /// assert_eq!(node.this, &*node as *const Node);
     /// # }
     /// # #[cfg(not(feature = "alloc"))] fn main() {}
     /// ```
     #[cfg(not(no_global_oom_handling))]
     #[inline(always)]
     #[allow(clippy::mut_from_ref)]
-    pub fn put_no_drop<T>(&self, value: T) -> &mut T {
+    pub unsafe fn emplace_then_init<T: 'static>(
+        &self,
+        init: impl FnOnce(&mut MaybeUninit<T>),
+    ) -> &mut T {
         unsafe {
-            self._try_emplace(
-                value,
-                |slot, value| {
-                    slot.write(Ok::<_, ManuallyDrop<Infallible>>(value));
+            self.emplace().with_uninit(|slot| {
+                init(slot);
+                Ok::<(), Infallible>(())
+            })
+        }
+        .safe_ok()
+    }
+
+    /// Returns future that drives `f` to completion
+    /// and puts its output into this `Blink` instance.
+    /// Resolves to reference to the value.
+    ///
+    /// Future `f` is stored inline in the returned future,
+    /// no heap allocation is made until output is ready.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "alloc", feature = "async"))]
+    /// # async fn example() {
+    /// # use blink_alloc::Blink;
+    /// let blink = Blink::new();
+    /// let foo = blink.emplace_from_async(async { 42 }).await;
+    /// assert_eq!(*foo, 42);
+    /// # }
+    /// # fn main() {}
+    /// ```
+    #[cfg(all(feature = "async", not(no_global_oom_handling)))]
+    #[inline(always)]
+    pub fn emplace_from_async<T: 'static, F>(&self, f: F) -> EmplaceAsyncFuture<'_, A, T, F>
+    where
+        F: Future<Output = T>,
+    {
+        EmplaceAsyncFuture {
+            blink: self,
+            future: f,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns future that drives `f` to completion
+    /// and puts its output into this `Blink` instance.
+    /// Resolves to reference to the value
+    /// or `Err(output)` if allocation fails.
+    ///
+    /// Future `f` is stored inline in the returned future,
+    /// no heap allocation is made until output is ready.
+    #[cfg(feature = "async")]
+    #[inline(always)]
+    pub fn try_emplace_from_async<T: 'static, F>(&self, f: F) -> TryEmplaceAsyncFuture<'_, A, T, F>
+    where
+        F: Future<Output = T>,
+    {
+        TryEmplaceAsyncFuture {
+            blink: self,
+            future: f,
+            marker: PhantomData,
+        }
+    }
+
+    /// Retains only elements of the slice for which `f` returns `true`.
+    /// Returns prefix of the slice containing retained elements
+    /// in their original order.
+    ///
+    /// This works like `Vec::retain`.
+    /// Removed elements are dropped immediately if the slice was emplaced
+    /// with drop into this `Blink` and is not a sub-slice of emplaced one,
+    /// or if elements don't need drop.
+    /// Only retained elements are dropped on reset then.
+    /// Finding the slice takes time linear in number of values
+    /// emplaced with drop since last reset.
+    ///
+    /// Otherwise removed elements are moved past the end of the returned slice
+    /// and left to whoever owns the slice.
+    /// Underlying allocation is unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let slice = blink.emplace().from_iter(0..10);
+    /// let even = blink.retain_slice(slice, |x| *x % 2 == 0);
+    /// assert_eq!(even, [0, 2, 4, 6, 8]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn retain_slice<'a, T, F>(&self, slice: &'a mut [T], mut f: F) -> &'a mut [T]
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let count = match needs_drop::<T>() {
+            false => None,
+            true => match self.drop_list.find_slice(NonNull::from(&mut *slice)) {
+                Some(count) => Some(count),
+                None => {
+                    // Values are owned by someone else, keep them alive.
+                    let mut retained = 0;
+                    for idx in 0..slice.len() {
+                        if f(&mut slice[idx]) {
+                            slice.swap(retained, idx);
+                            retained += 1;
+                        }
+                    }
+                    return &mut slice[..retained];
+                }
+            },
+        };
+
+        struct Guard<T> {
+            ptr: *mut T,
+            len: usize,
+            processed: usize,
+            deleted: usize,
+            count: Option<NonNull<usize>>,
+        }
+
+        impl<T> Drop for Guard<T> {
+            #[inline(always)]
+            fn drop(&mut self) {
+                // Runs on panic too, closing the gap of dropped elements.
+                if self.deleted > 0 {
+                    // Safety: Unprocessed elements are initialized
+                    // and the gap is as large as number of dropped elements.
+                    unsafe {
+                        ptr::copy(
+                            self.ptr.add(self.processed),
+                            self.ptr.add(self.processed - self.deleted),
+                            self.len - self.processed,
+                        );
+                    }
+                }
+                if let Some(count) = self.count {
+                    // Safety: Drop item is valid until reset.
+                    unsafe { *count.as_ptr() = self.len - self.deleted };
+                }
+            }
+        }
+
+        let mut guard = Guard {
+            ptr: slice.as_mut_ptr(),
+            len: slice.len(),
+            processed: 0,
+            deleted: 0,
+            count,
+        };
+
+        while guard.processed < guard.len {
+            // Safety: `processed` is in bounds and element at it is initialized.
+            let cur = unsafe { &mut *guard.ptr.add(guard.processed) };
+            let keep = f(cur);
+
+            // Mark element as processed before dropping it,
+            // so it is not moved by the guard if drop panics.
+            guard.processed += 1;
+            if keep {
+                if guard.deleted > 0 {
+                    // Safety: Element is moved into the gap of dropped elements.
+                    unsafe {
+                        ptr::copy_nonoverlapping(
+                            cur,
+                            guard.ptr.add(guard.processed - 1 - guard.deleted),
+                            1,
+                        );
+                    }
+                }
+            } else {
+                guard.deleted += 1;
+                // Safety: Element is initialized and won't be used anymore.
+                // Drop item drops only retained elements after the guard is dropped.
+                unsafe { ptr::drop_in_place(cur) };
+            }
+        }
+
+        let ptr = guard.ptr;
+        let retained = guard.len - guard.deleted;
+        drop(guard);
+
+        // Safety: First `retained` elements are initialized.
+        unsafe { core::slice::from_raw_parts_mut(ptr, retained) }
+    }
+
+    /// Emplaces items from nested iterators into contiguous memory.
+    /// Returns reference to the slice of all items in order.
+    ///
+    /// Unlike collecting `iter.flatten()` this uses outer iterator
+    /// size hint to estimate total size of the first allocation.
+    /// The allocation is grown geometrically only when estimate is exceeded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let slice = blink.emplace_from_flatten([0..3, 3..5, 5..5, 5..8]);
+    /// assert_eq!(slice, [0, 1, 2, 3, 4, 5, 6, 7]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn emplace_from_flatten<T: 'static, II>(&self, iter_of_iters: II) -> &mut [T]
+    where
+        II: IntoIterator,
+        II::Item: IntoIterator<Item = T>,
+    {
+        let mut iter = FlattenHint {
+            outer: iter_of_iters.into_iter(),
+            front: None,
+        };
+        let estimate = iter.estimate();
+        unsafe {
+            self._try_emplace_from_iter_estimated(
+                iter,
+                estimate,
+                false,
+                |_, _, layout| match layout {
+                    Some(layout) => handle_alloc_error(layout),
+                    None => size_overflow(),
                 },
-                true,
-                identity,
-                |_, layout| handle_alloc_error(layout),
             )
         }
         .safe_ok()
     }
 
+    /// Emplaces items from nested iterators into contiguous memory.
+    /// Returns reference to the slice of all items in order.
+    ///
+    /// If allocation fails, returns slice of items emplaced so far
+    /// and one item that was taken from iterators and not emplaced.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref, clippy::type_complexity)]
+    pub fn try_emplace_from_flatten<T: 'static, II>(
+        &self,
+        iter_of_iters: II,
+    ) -> Result<&mut [T], (&mut [T], Option<T>)>
+    where
+        II: IntoIterator,
+        II::Item: IntoIterator<Item = T>,
+    {
+        let mut iter = FlattenHint {
+            outer: iter_of_iters.into_iter(),
+            front: None,
+        };
+        let estimate = iter.estimate();
+        unsafe {
+            self._try_emplace_from_iter_estimated(iter, estimate, false, |slice, value, _| {
+                (slice, value)
+            })
+        }
+    }
+
+    /// Emplaces items of each row into its own contiguous slice
+    /// and references to those slices into another slice.
+    /// Returns reference to the slice of rows.
+    ///
+    /// Useful to build compact nested structures like adjacency lists.
+    /// Each row is registered for drop separately.
+    /// Slice of rows holds only references and needs no drop.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let edges = [(0, 1), (0, 2), (1, 2), (2, 0)];
+    /// let adjacency = blink.jagged((0..3).map(|v| {
+    ///     edges.iter().filter(move |e| e.0 == v).map(|e| e.1)
+    /// }));
+    /// assert_eq!(adjacency.len(), 3);
+    /// assert_eq!(adjacency[0], [1, 2]);
+    /// assert_eq!(adjacency[1], [2]);
+    /// assert_eq!(adjacency[2], [0]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn jagged<T: 'static, R>(&self, rows: R) -> &mut [&mut [T]]
+    where
+        R: IntoIterator,
+        R::Item: IntoIterator<Item = T>,
+    {
+        self.emplace_no_drop().from_iter(
+            rows.into_iter()
+                .map(|row| self.emplace().from_iter(row.into_iter())),
+        )
+    }
+
+    /// Emplaces items of each row into its own contiguous slice
+    /// and references to those slices into another slice.
+    /// Returns reference to the slice of rows.
+    /// If allocation fails, returns `None`.
+    ///
+    /// Rows emplaced before failure are dropped on reset.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_jagged<T: 'static, R>(&self, rows: R) -> Option<&mut [&mut [T]]>
+    where
+        R: IntoIterator,
+        R::Item: IntoIterator<Item = T>,
+    {
+        let mut failed = false;
+        let rows = rows
+            .into_iter()
+            .map_while(|row| match self.emplace().try_from_iter(row) {
+                Ok(row) => Some(row),
+                Err(_) => {
+                    failed = true;
+                    None
+                }
+            });
+
+        match self.emplace_no_drop().try_from_iter(rows) {
+            Ok(rows) if !failed => Some(rows),
+            _ => None,
+        }
+    }
+
+    /// Copies slices into contiguous memory one after another.
+    /// Returns reference to the concatenated slice.
+    ///
+    /// Unlike collecting flattened iterator this copies whole slices at once.
+    /// Iterator is traversed once, allocation is grown as needed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let header = [1u8, 2];
+    /// let body = vec![3u8, 4, 5];
+    /// let slice = blink.emplace_from_chunks([&header[..], &[], &body]);
+    /// assert_eq!(slice, [1, 2, 3, 4, 5]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn emplace_from_chunks<'b, T, II>(&self, chunks: II) -> &mut [T]
+    where
+        T: Copy + 'b,
+        II: IntoIterator<Item = &'b [T]>,
+    {
+        unsafe {
+            self._try_copy_chunks(chunks.into_iter(), |layout| match layout {
+                Some(layout) => handle_alloc_error(layout),
+                None => size_overflow(),
+            })
+        }
+        .safe_ok()
+    }
+
+    /// Copies slices into contiguous memory one after another.
+    /// Returns reference to the concatenated slice.
+    /// If allocation fails, returns `None`.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_emplace_from_chunks<'b, T, II>(&self, chunks: II) -> Option<&mut [T]>
+    where
+        T: Copy + 'b,
+        II: IntoIterator<Item = &'b [T]>,
+    {
+        unsafe { self._try_copy_chunks(chunks.into_iter(), |_| ()) }.ok()
+    }
+
+    /// Calls `f` with writer that appends bytes
+    /// to contiguous memory allocated from this `Blink`.
+    /// Returns reference to all written bytes.
+    ///
+    /// Writer starts with capacity of `size_hint` bytes and grows as needed.
+    /// Growth happens in place unless `f` allocates from this `Blink`.
+    ///
+    /// [`BlinkWriter`] implements [`core::fmt::Write`],
+    /// and [`std::io::Write`] with "std" feature.
+    /// Write fails only if allocation fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "alloc", feature = "std"))] fn main() {
+    /// # use blink_alloc::Blink;
+    /// use std::io::Write;
+    ///
+    /// let mut blink = Blink::new();
+    /// let bytes = blink.emplace_write(16, |writer| {
+    ///     writer.write_all(b"Hello, ").unwrap();
+    ///     write!(writer, "{}!", "World").unwrap();
+    /// });
+    /// assert_eq!(bytes, b"Hello, World!");
+    /// # }
+    /// # #[cfg(not(all(feature = "alloc", feature = "std")))] fn main() {}
+    /// ```
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub fn emplace_write(
+        &self,
+        size_hint: usize,
+        f: impl FnOnce(&mut BlinkWriter<'_, A>),
+    ) -> &mut [u8] {
+        let mut writer = BlinkWriter {
+            alloc: &self.alloc,
+            ptr: NonNull::dangling(),
+            len: 0,
+            cap: 0,
+        };
+
+        // Failure to reserve is reported by writes.
+        let _ = writer.reserve(size_hint);
+        f(&mut writer);
+        writer.finish()
+    }
+
     /// Allocates memory for a value.
     /// Returns some reference to the uninitialized value.
     /// If allocation fails, returns none.
     #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
     pub fn try_uninit<T>(&self) -> Option<&mut MaybeUninit<T>> {
         let layout = Layout::new::<T>();
         let ptr = self.alloc.allocate(layout).ok()?;
@@ -1128,6 +3012,41 @@ where
         unsafe { &mut *ptr.as_ptr().cast() }
     }
 
+    /// Allocates memory for `len` values.
+    /// Returns some reference to the uninitialized slice.
+    /// If allocation fails, returns none.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_uninit_slice<T>(&self, len: usize) -> Option<&mut [MaybeUninit<T>]> {
+        let layout = Layout::array::<T>(len).ok()?;
+        let ptr = self.alloc.allocate(layout).ok()?;
+
+        // Safety:
+        // - `ptr` is valid for `layout`.
+        // - `MaybeUninit` is always initialized.
+        Some(unsafe { core::slice::from_raw_parts_mut(ptr.as_ptr().cast(), len) })
+    }
+
+    /// Allocates memory for `len` values.
+    /// Returns reference to the uninitialized slice.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn uninit_slice<T>(&self, len: usize) -> &mut [MaybeUninit<T>] {
+        let layout = Layout::array::<T>(len)
+            .map_err(|_| size_overflow())
+            .safe_ok();
+        let ptr = self
+            .alloc
+            .allocate(layout)
+            .unwrap_or_else(|_| handle_alloc_error(layout));
+
+        // Safety:
+        // - `ptr` is valid for `layout`.
+        // - `MaybeUninit` is always initialized.
+        unsafe { core::slice::from_raw_parts_mut(ptr.as_ptr().cast(), len) }
+    }
+
     /// Copies the slice to the allocated memory
     /// and returns reference to the new slice.
     #[cfg(not(no_global_oom_handling))]
@@ -1149,6 +3068,7 @@ where
     /// and returns reference to the new slice.
     /// If allocation fails, returns `None`.
     #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
     pub fn try_copy_slice<T>(&self, slice: &[T]) -> Option<&mut [T]>
     where
         T: Copy,
@@ -1161,23 +3081,79 @@ where
     #[cfg(not(no_global_oom_handling))]
     #[inline(always)]
     #[allow(clippy::mut_from_ref)]
-    pub fn copy_str(&self, string: &str) -> &mut str {
-        let result = unsafe { self._try_copy_slice(string.as_bytes(), handle_alloc_error) };
-        match result {
-            Ok(slice) => unsafe { core::str::from_utf8_unchecked_mut(slice) },
-            Err(never) => never,
+    pub fn copy_str(&self, string: &str) -> &mut str {
+        let result = unsafe { self._try_copy_slice(string.as_bytes(), handle_alloc_error) };
+        match result {
+            Ok(slice) => unsafe { core::str::from_utf8_unchecked_mut(slice) },
+            Err(never) => never,
+        }
+    }
+
+    /// Allocates memory for a copy of the slice.
+    /// Copies the slice to the allocated memory
+    /// and returns reference to the new slice.
+    /// If allocation fails, returns `None`.
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_copy_str(&self, string: &str) -> Option<&mut str> {
+        unsafe { self._try_copy_slice(string.as_bytes(), |_| ()) }
+            .ok()
+            .map(|bytes| unsafe { core::str::from_utf8_unchecked_mut(bytes) })
+    }
+
+    /// Encodes characters from iterator as UTF-8
+    /// into the allocated memory
+    /// and returns reference to the new string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let string = blink.emplace_string_from_chars("Hello, мир!".chars().rev());
+    /// assert_eq!(string, "!рим ,olleH");
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn emplace_string_from_chars<I>(&self, chars: I) -> &mut str
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let bytes = unsafe {
+            self._try_emplace_from_iter(Utf8Bytes::new(chars.into_iter()), true, |_, _, layout| {
+                match layout {
+                    Some(layout) => handle_alloc_error(layout),
+                    None => size_overflow(),
+                }
+            })
         }
+        .safe_ok();
+
+        // Safety: bytes are UTF-8 encoded characters.
+        unsafe { core::str::from_utf8_unchecked_mut(bytes) }
     }
 
-    /// Allocates memory for a copy of the slice.
-    /// Copies the slice to the allocated memory
-    /// and returns reference to the new slice.
+    /// Encodes characters from iterator as UTF-8
+    /// into the allocated memory
+    /// and returns reference to the new string.
     /// If allocation fails, returns `None`.
     #[inline(always)]
-    pub fn try_copy_str(&self, string: &str) -> Option<&mut str> {
-        unsafe { self._try_copy_slice(string.as_bytes(), |_| ()) }
-            .ok()
-            .map(|bytes| unsafe { core::str::from_utf8_unchecked_mut(bytes) })
+    #[allow(clippy::mut_from_ref)]
+    pub fn try_emplace_string_from_chars<I>(&self, chars: I) -> Option<&mut str>
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let bytes = unsafe {
+            self._try_emplace_from_iter(Utf8Bytes::new(chars.into_iter()), true, |_, _, _| ())
+        }
+        .ok()?;
+
+        // Safety: bytes are UTF-8 encoded characters.
+        Some(unsafe { core::str::from_utf8_unchecked_mut(bytes) })
     }
 
     /// Returns an `Emplace` adaptor that can emplace values into
@@ -1211,7 +3187,7 @@ where
     /// # #[cfg(not(feature = "alloc"))] fn main() {}
     /// ```
     #[inline(always)]
-    pub fn emplace<T: 'static>(&self) -> Emplace<A, T> {
+    pub fn emplace<T: 'static>(&self) -> Emplace<'_, A, T> {
         Emplace {
             blink: self,
             no_drop: false,
@@ -1260,7 +3236,7 @@ where
     /// # #[cfg(not(feature = "alloc"))] fn main() {}
     /// ```
     #[inline(always)]
-    pub fn emplace_no_drop<T>(&self) -> Emplace<A, T> {
+    pub fn emplace_no_drop<T>(&self) -> Emplace<'_, A, T> {
         Emplace {
             blink: self,
             no_drop: true,
@@ -1312,7 +3288,37 @@ where
     /// # #[cfg(not(feature = "alloc"))] fn main() {}
     /// ```
     #[inline(always)]
-    pub fn emplace_shared<T>(&self) -> Emplace<A, T, &T, &[T]> {
+    pub fn emplace_shared<T>(&self) -> Emplace<'_, A, T, &T, &[T]> {
+        Emplace {
+            blink: self,
+            no_drop: true,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an `Emplace` adaptor that can emplace values into
+    /// the blink allocator.
+    ///
+    /// This version returns shared references to [`Cell`]s
+    /// wrapping emplaced values, allowing mutation through shared references.
+    /// Lifts the `'static` requirement.
+    /// Emplaced values are not dropped on reset.
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// let counter = blink.emplace_cell().value(0u32);
+    /// let shared = (counter, counter);
+    /// shared.0.set(shared.0.get() + 1);
+    /// shared.1.set(shared.1.get() + 1);
+    /// assert_eq!(counter.get(), 2);
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn emplace_cell<T>(&self) -> Emplace<'_, A, T, &Cell<T>, &[Cell<T>]> {
         Emplace {
             blink: self,
             no_drop: true,
@@ -1370,7 +3376,7 @@ where
     /// # #[cfg(not(feature = "alloc"))] fn main() {}
     /// ```
     #[inline(always)]
-    pub unsafe fn emplace_unchecked<T>(&self) -> Emplace<A, T> {
+    pub unsafe fn emplace_unchecked<T>(&self) -> Emplace<'_, A, T> {
         Emplace {
             blink: self,
             no_drop: false,
@@ -1427,11 +3433,444 @@ where
     }
 }
 
+/// Writer that appends bytes to contiguous memory allocated from [`Blink`].
+///
+/// Created by [`Blink::emplace_write`].
+pub struct BlinkWriter<'a, A> {
+    alloc: &'a A,
+    ptr: NonNull<u8>,
+    len: usize,
+    cap: usize,
+}
+
+impl<'a, A> BlinkWriter<'a, A>
+where
+    A: BlinkAllocator,
+{
+    /// Returns bytes written so far.
+    #[inline(always)]
+    pub fn written(&self) -> &[u8] {
+        // Safety: `len` bytes are initialized.
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Makes sure that `additional` bytes can be written
+    /// without growing the allocation.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+        let required = self.len.checked_add(additional).ok_or(AllocError)?;
+        if required <= self.cap {
+            return Ok(());
+        }
+
+        let new_cap = required.max(self.cap * 2);
+        let new_layout = Layout::array::<u8>(new_cap).map_err(|_| AllocError)?;
+
+        let ptr = if self.cap == 0 {
+            self.alloc.allocate(new_layout)?
+        } else {
+            // Safety: `ptr` is allocated with at least `cap` bytes.
+            unsafe {
+                let old_layout = Layout::from_size_align_unchecked(self.cap, 1);
+                self.alloc.grow(self.ptr, old_layout, new_layout)?
+            }
+        };
+
+        self.ptr = ptr.cast();
+        self.cap = ptr.len();
+        Ok(())
+    }
+
+    /// Appends bytes to the written data.
+    #[inline]
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), AllocError> {
+        self.reserve(bytes.len())?;
+
+        // Safety: `ptr` is allocated with at least `len + bytes.len()` bytes.
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                self.ptr.as_ptr().add(self.len),
+                bytes.len(),
+            );
+        }
+        self.len += bytes.len();
+        Ok(())
+    }
+
+    /// Gives unused capacity back and returns written bytes.
+    #[inline]
+    fn finish(self) -> &'a mut [u8] {
+        let mut ptr = self.ptr;
+        if self.len == 0 && self.cap != 0 {
+            // Safety: `ptr` is allocated with at least `cap` bytes.
+            unsafe {
+                let old_layout = Layout::from_size_align_unchecked(self.cap, 1);
+                self.alloc.deallocate(ptr, old_layout);
+            }
+            return &mut [];
+        }
+
+        if self.cap > self.len {
+            // Safety: `ptr` is allocated with at least `cap` bytes.
+            unsafe {
+                let old_layout = Layout::from_size_align_unchecked(self.cap, 1);
+                let new_layout = Layout::from_size_align_unchecked(self.len, 1);
+                if let Ok(new_ptr) = self.alloc.shrink(ptr, old_layout, new_layout) {
+                    ptr = new_ptr.cast();
+                }
+            }
+        }
+
+        // Safety: `len` bytes are initialized
+        // and stay valid until allocator is reset.
+        unsafe { core::slice::from_raw_parts_mut(ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<A> core::fmt::Write for BlinkWriter<'_, A>
+where
+    A: BlinkAllocator,
+{
+    #[inline]
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.write_bytes(s.as_bytes()).map_err(|_| core::fmt::Error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A> std::io::Write for BlinkWriter<'_, A>
+where
+    A: BlinkAllocator,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.write_bytes(buf)
+            .map_err(|_| std::io::ErrorKind::OutOfMemory.into())
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Saved state of [`Blink`] to roll back to.
+#[derive(Clone, Copy)]
+struct BlinkCheckpoint {
+    drops: DropCheckpoint,
+    arena: Checkpoint,
+}
+
+impl<A> Blink<BlinkAlloc<A>>
+where
+    A: Allocator,
+{
+    /// Opens allocation scope.
+    ///
+    /// Returned [`BlinkScope`] dereferences to this [`Blink`] and allows
+    /// to allocate values as usual.
+    /// When scope is dropped, all values emplaced within the scope
+    /// are dropped and memory allocated within the scope is reclaimed.
+    /// Values emplaced before the scope is opened are kept intact.
+    ///
+    /// Nested scopes can be opened with [`BlinkScope::open_scope`].
+    ///
+    /// Unlike closure-based scoping this allows scope boundary to be
+    /// determined by control flow.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    /// blink.put(1u32);
+    ///
+    /// let scope = blink.open_scope();
+    /// let value = scope.put(2u32);
+    /// assert_eq!(*value, 2);
+    /// drop(scope);
+    ///
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn open_scope(&mut self) -> BlinkScope<'_, A> {
+        let checkpoint = BlinkCheckpoint {
+            drops: self.drop_list.checkpoint(),
+            arena: self.alloc.checkpoint(),
+        };
+
+        BlinkScope {
+            blink: self,
+            checkpoint,
+            marker: PhantomData,
+        }
+    }
+
+    /// Calls `f` within allocation scope,
+    /// rolling it back if `f` returns an error.
+    ///
+    /// If `f` returns `Ok`, values emplaced within the scope are kept
+    /// as if they were emplaced directly into this [`Blink`].
+    /// If `f` returns `Err`, values emplaced within the scope are dropped
+    /// and memory allocated within the scope is reclaimed,
+    /// same as when [`BlinkScope`] is dropped.
+    ///
+    /// `f` receives the scope instead of this [`Blink`]
+    /// so it can't reset the allocator in the middle of the scope.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::Blink;
+    /// let mut blink = Blink::new();
+    ///
+    /// let result = blink.try_scope_with_result(|scope| {
+    ///     let value = scope.put(1u32);
+    ///     if *value > 0 {
+    ///         return Err("rejected");
+    ///     }
+    ///     Ok(*value)
+    /// });
+    /// assert_eq!(result, Err("rejected"));
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn try_scope_with_result<T, E, F>(&mut self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut BlinkScope<'_, A>) -> Result<T, E>,
+    {
+        let mut scope = self.open_scope();
+        let result = f(&mut scope);
+        if result.is_ok() {
+            // Keep values emplaced within the scope.
+            core::mem::forget(scope);
+        }
+        result
+    }
+
+    /// Drops all allocated values.
+    /// And resets associated allocator,
+    /// shrinking retained chunk to fit memory used before this call.
+    ///
+    /// See [`BlinkAlloc::shrink_to_fit`].
+    #[inline(always)]
+    pub fn shrink_to_fit(&mut self) {
+        self.drop_list.reset();
+        self.alloc.shrink_to_fit();
+    }
+
+    /// Drops values emplaced after the checkpoint
+    /// and reclaims memory allocated after the checkpoint.
+    ///
+    /// # Safety
+    ///
+    /// `checkpoint` must be created from this instance
+    /// and no reset could happen since then.
+    /// Values emplaced after the checkpoint must not be used.
+    #[inline(always)]
+    unsafe fn rollback_to(&mut self, checkpoint: BlinkCheckpoint) {
+        unsafe {
+            self.drop_list.rollback(checkpoint.drops);
+            self.alloc.rollback(checkpoint.arena);
+        }
+    }
+}
+
+/// Allocation scope of [`Blink`].
+///
+/// Created with [`Blink::open_scope`].
+/// Values emplaced through the scope are dropped and their memory is reclaimed
+/// when scope is dropped.
+///
+/// Scopes may be nested, borrow checker ensures they are dropped in LIFO order.
+pub struct BlinkScope<'a, A: Allocator> {
+    blink: &'a mut Blink<BlinkAlloc<A>>,
+    checkpoint: BlinkCheckpoint,
+    marker: PhantomData<*mut u8>,
+}
+
+impl<A> Drop for BlinkScope<'_, A>
+where
+    A: Allocator,
+{
+    #[inline(always)]
+    fn drop(&mut self) {
+        // Safety: Checkpoint was created from this `Blink`.
+        // `Blink` is borrowed mutably since then, so no reset could happen.
+        // Values emplaced within the scope borrow the scope and are unreachable now.
+        unsafe {
+            self.blink.rollback_to(self.checkpoint);
+        }
+    }
+}
+
+impl<A> Deref for BlinkScope<'_, A>
+where
+    A: Allocator,
+{
+    type Target = Blink<BlinkAlloc<A>>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Blink<BlinkAlloc<A>> {
+        self.blink
+    }
+}
+
+impl<A> BlinkScope<'_, A>
+where
+    A: Allocator,
+{
+    /// Opens nested allocation scope.
+    ///
+    /// See [`Blink::open_scope`].
+    #[inline(always)]
+    pub fn open_scope(&mut self) -> BlinkScope<'_, A> {
+        self.blink.open_scope()
+    }
+}
+
 #[inline(always)]
 fn never<T>(never: Infallible) -> T {
     match never {}
 }
 
+/// Iterator adaptor that yields UTF-8 encoded bytes of characters.
+struct Utf8Bytes<I> {
+    chars: I,
+    buf: [u8; 4],
+    pos: u8,
+    len: u8,
+}
+
+impl<I> Utf8Bytes<I> {
+    #[inline(always)]
+    fn new(chars: I) -> Self {
+        Utf8Bytes {
+            chars,
+            buf: [0; 4],
+            pos: 0,
+            len: 0,
+        }
+    }
+}
+
+impl<I> Iterator for Utf8Bytes<I>
+where
+    I: Iterator<Item = char>,
+{
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        if self.pos == self.len {
+            let c = self.chars.next()?;
+            self.len = c.encode_utf8(&mut self.buf).len() as u8;
+            self.pos = 0;
+        }
+        let byte = self.buf[usize::from(self.pos)];
+        self.pos += 1;
+        Some(byte)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = usize::from(self.len - self.pos);
+        let (lower, upper) = self.chars.size_hint();
+        let lower = lower.saturating_add(buffered);
+        let upper = upper
+            .and_then(|upper| upper.checked_mul(4))
+            .and_then(|upper| upper.checked_add(buffered));
+        (lower, upper)
+    }
+}
+
+/// Flattening iterator adaptor.
+/// Provides size estimate from the outer iterator,
+/// assuming that each remaining inner iterator is as long as the current one.
+struct FlattenHint<O, I> {
+    outer: O,
+    front: Option<I>,
+}
+
+impl<O, T> FlattenHint<O, <O::Item as IntoIterator>::IntoIter>
+where
+    O: Iterator,
+    O::Item: IntoIterator<Item = T>,
+{
+    /// Takes the first inner iterator if not taken yet
+    /// and estimates total number of items.
+    /// Estimate is not a lower bound.
+    #[inline]
+    fn estimate(&mut self) -> usize {
+        if self.front.is_none() {
+            self.front = self.outer.next().map(IntoIterator::into_iter);
+        }
+        if size_of::<T>() == 0 {
+            // Zero-sized items are counted by draining.
+            return 0;
+        }
+
+        let (front_lower, _) = match &self.front {
+            None => return 0,
+            Some(front) => front.size_hint(),
+        };
+        let (outer_lower, _) = self.outer.size_hint();
+        let estimate = front_lower.saturating_mul(outer_lower.saturating_add(1));
+        match self.size_hint() {
+            (_, Some(upper)) => estimate.min(upper),
+            (_, None) => estimate,
+        }
+    }
+}
+
+impl<O, T> Iterator for FlattenHint<O, <O::Item as IntoIterator>::IntoIter>
+where
+    O: Iterator,
+    O::Item: IntoIterator<Item = T>,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some(front) = &mut self.front {
+                if let Some(item) = front.next() {
+                    return Some(item);
+                }
+            }
+            self.front = Some(self.outer.next()?.into_iter());
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (front_lower, front_upper) = match &self.front {
+            None => (0, Some(0)),
+            Some(front) => front.size_hint(),
+        };
+        let (_, outer_upper) = self.outer.size_hint();
+
+        // Remaining inner iterators may be empty.
+        let upper = match outer_upper {
+            Some(0) => front_upper,
+            _ => None,
+        };
+        (front_lower, upper)
+    }
+}
+
 const FASTER_START: usize = 8;
 
 #[inline]
@@ -1479,9 +3918,67 @@ fn saturating_drain_iter<T>(mut iter: impl Iterator<Item = T>) -> usize {
     }
 }
 
+/// Like [`saturating_drain_iter`] but forgets elements instead of dropping them.
+/// Used for zero-sized elements that are dropped later.
+#[inline]
+fn saturating_forget_iter<T>(iter: impl Iterator<Item = T>) -> usize {
+    let mut forgotten = 0;
+    for elem in iter {
+        core::mem::forget(elem);
+        forgotten += 1;
+        if forgotten == usize::MAX {
+            break;
+        }
+    }
+    forgotten
+}
+
+#[test]
+fn test_uninit_slot_layout() {
+    // `with_uninit` writes value directly, without `Result` wrapper.
+    assert!(
+        Layout::new::<DropItem<[u64; 4]>>().size()
+            < Layout::new::<DropItem<EmplaceType<[u64; 4], u64>>>().size()
+    );
+    assert!(Layout::new::<[u64; 4]>().size() < Layout::new::<EmplaceType<[u64; 4], u64>>().size());
+}
+
 #[test]
 fn test_iter_drain() {
     assert_eq!(5, saturating_drain_iter(0..5));
     assert_eq!(usize::MAX, saturating_drain_iter(0..usize::MAX));
     assert_eq!(usize::MAX, saturating_drain_iter(core::iter::repeat(1)));
 }
+
+#[test]
+fn test_flatten_hint() {
+    let mut iter = FlattenHint {
+        outer: IntoIterator::into_iter([0..3, 3..3, 3..5]),
+        front: None::<core::ops::Range<u32>>,
+    };
+    assert_eq!(iter.size_hint(), (0, None));
+
+    // Estimate assumes all inner iterators are as long as the first one.
+    assert_eq!(iter.estimate(), 9);
+
+    // Lower bound never exceeds remaining items.
+    let mut remaining = 5;
+    while remaining > 0 {
+        assert!(iter.size_hint().0 <= remaining);
+        iter.next().unwrap();
+        remaining -= 1;
+    }
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next(), None);
+}
+
+#[cfg(feature = "tracing")]
+#[inline]
+fn trace_reset(stats: Option<BlinkStats>, drop_count: usize) {
+    tracing::debug!(
+        used_bytes = stats.map(|stats| stats.used),
+        chunk_count = stats.map(|stats| stats.chunks),
+        drop_count,
+        "Blink reset"
+    );
+}
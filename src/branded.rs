@@ -0,0 +1,165 @@
+//! This module provides branded access to [`BlinkAlloc`]
+//! that ties allocations to a single reset cycle at the type level.
+
+use core::{
+    alloc::Layout,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+use allocator_api2::alloc::{AllocError, Allocator};
+
+#[cfg(not(no_global_oom_handling))]
+use crate::oom::handle_alloc_error;
+
+use crate::local::BlinkAlloc;
+
+/// Invariant lifetime that can't be shortened or extended.
+type Brand<'id> = PhantomData<fn(&'id ()) -> &'id ()>;
+
+/// Access to [`BlinkAlloc`] branded with unique lifetime `'id`.
+/// Created by [`BlinkAlloc::brand`].
+///
+/// Allocations made through this type are wrapped into [`Branded`]
+/// with the same lifetime.
+/// Each call to [`BlinkAlloc::brand`] produces distinct `'id`,
+/// so values from different reset cycles can't be mixed,
+/// and no value can escape the closure that is followed by reset.
+pub struct BrandedBlink<'id, A: Allocator> {
+    blink: &'id BlinkAlloc<A>,
+    brand: Brand<'id>,
+}
+
+impl<'id, A> Clone for BrandedBlink<'id, A>
+where
+    A: Allocator,
+{
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'id, A> Copy for BrandedBlink<'id, A> where A: Allocator {}
+
+impl<'id, A> BrandedBlink<'id, A>
+where
+    A: Allocator,
+{
+    #[inline(always)]
+    pub(crate) fn new(blink: &'id BlinkAlloc<A>) -> Self {
+        BrandedBlink {
+            blink,
+            brand: PhantomData,
+        }
+    }
+
+    /// Returns reference to the branded allocator.
+    ///
+    /// Collections allocated with it are bound to `'id`
+    /// and can't escape the branded closure either.
+    #[inline(always)]
+    pub fn allocator(&self) -> &'id BlinkAlloc<A> {
+        self.blink
+    }
+
+    /// Allocates memory with specified layout.
+    ///
+    /// See [`BlinkAlloc::allocate`].
+    #[inline(always)]
+    pub fn allocate(&self, layout: Layout) -> Result<Branded<'id, NonNull<[u8]>>, AllocError> {
+        self.blink.allocate(layout).map(Branded::new)
+    }
+
+    /// Moves `value` into allocated memory.
+    /// If allocation fails, returns `Err(value)`.
+    ///
+    /// Value is never dropped, same as values written into
+    /// memory allocated with [`BlinkAlloc::allocate_slice`].
+    #[inline(always)]
+    pub fn try_put<T>(&self, value: T) -> Result<Branded<'id, &'id mut T>, T> {
+        match self.blink.allocate(Layout::new::<T>()) {
+            Ok(ptr) => {
+                let ptr = ptr.cast::<T>();
+                // Safety: `ptr` is valid for writes of `T`
+                // and is not used by anything else until reset.
+                unsafe {
+                    ptr.as_ptr().write(value);
+                    Ok(Branded::new(&mut *ptr.as_ptr()))
+                }
+            }
+            Err(_) => Err(value),
+        }
+    }
+
+    /// Moves `value` into allocated memory.
+    /// If allocation fails, diverges.
+    ///
+    /// Value is never dropped, same as values written into
+    /// memory allocated with [`BlinkAlloc::allocate_slice`].
+    #[cfg(not(no_global_oom_handling))]
+    #[inline(always)]
+    pub fn put<T>(&self, value: T) -> Branded<'id, &'id mut T> {
+        match self.try_put(value) {
+            Ok(value) => value,
+            Err(_) => handle_alloc_error(Layout::new::<T>()),
+        }
+    }
+}
+
+/// Value branded with lifetime `'id` of [`BrandedBlink`] it was allocated from.
+///
+/// Functions may require values and allocator with the same brand
+/// to ensure they come from the same reset cycle.
+pub struct Branded<'id, P> {
+    value: P,
+    brand: Brand<'id>,
+}
+
+impl<'id, P> Branded<'id, P> {
+    #[inline(always)]
+    fn new(value: P) -> Self {
+        Branded {
+            value,
+            brand: PhantomData,
+        }
+    }
+
+    /// Returns reference to the branded value.
+    #[inline(always)]
+    pub fn get(&self) -> &P {
+        &self.value
+    }
+
+    /// Unwraps branded value.
+    ///
+    /// Lifetime of the value itself still keeps it
+    /// from escaping the branded closure.
+    #[inline(always)]
+    pub fn into_inner(self) -> P {
+        self.value
+    }
+}
+
+impl<'id, P> Deref for Branded<'id, P>
+where
+    P: Deref,
+{
+    type Target = P::Target;
+
+    #[inline(always)]
+    fn deref(&self) -> &P::Target {
+        &self.value
+    }
+}
+
+impl<'id, P> DerefMut for Branded<'id, P>
+where
+    P: DerefMut,
+{
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut P::Target {
+        &mut self.value
+    }
+}
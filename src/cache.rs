@@ -4,10 +4,10 @@ use core::{
     sync::atomic::{AtomicUsize, Ordering},
 };
 
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 
 use allocator_api2::alloc::{Allocator, Global};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 
 use crate::local::BlinkAlloc;
 
@@ -40,6 +40,9 @@ where
 /// blocking + wait-free algorithm.
 pub struct BlinkAllocCache<A: Allocator = Global> {
     inner: RwLock<Inner<A>>,
+
+    /// Creates new instances on cache miss.
+    factory: Option<Box<dyn Fn() -> BlinkAlloc<A> + Send + Sync>>,
 }
 
 impl<A> Default for BlinkAllocCache<A>
@@ -64,7 +67,49 @@ where
                 push_array: Vec::new(),
                 next_push: AtomicUsize::new(0),
             }),
+            factory: None,
+        }
+    }
+
+    /// Creates a new empty [`BlinkAllocCache`]
+    /// that creates [`BlinkAlloc`] instances with `factory`
+    /// on cache miss in [`pop_or_new`](BlinkAllocCache::pop_or_new).
+    ///
+    /// Useful when underlying allocator does not implement [`Default`]
+    /// or instances need custom configuration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # use blink_alloc::{BlinkAlloc, BlinkAllocCache};
+    /// # use allocator_api2::alloc::Global;
+    /// let cache = BlinkAllocCache::new_with_factory(|| BlinkAlloc::with_chunk_size_in(4096, Global));
+    ///
+    /// let blink = cache.pop_or_new().unwrap();
+    /// assert_eq!(blink.last_chunk_cap(), 0);
+    /// cache.push(blink);
+    /// ```
+    pub fn new_with_factory(factory: impl Fn() -> BlinkAlloc<A> + Send + Sync + 'static) -> Self {
+        BlinkAllocCache {
+            factory: Some(Box::new(factory)),
+            ..Self::new()
+        }
+    }
+
+    /// Acquires some [`BlinkAlloc`] instance from the cache.
+    /// Creates new instance with the factory if the cache is empty.
+    /// Returns none if the cache is empty and was created without factory,
+    /// see [`new_with_factory`](BlinkAllocCache::new_with_factory).
+    ///
+    /// Factory is called without holding cache lock,
+    /// so it may be called concurrently from multiple threads.
+    pub fn pop_or_new(&self) -> Option<BlinkAlloc<A>> {
+        if let Some(blink) = self.pop() {
+            return Some(blink);
         }
+
+        self.factory.as_ref().map(|factory| factory())
     }
 
     /// Acquires some [`BlinkAlloc`] instance from the cache.
@@ -131,6 +176,85 @@ where
             .push(UnsafeCell::new(ManuallyDrop::new(blink)));
     }
 
+    /// Resets [`BlinkAlloc`] instance keeping its last chunk
+    /// and puts it into the cache.
+    ///
+    /// Prevents stale allocations from persisting in cached instances.
+    #[inline]
+    pub fn push_reset(&self, mut blink: BlinkAlloc<A>) {
+        blink.reset();
+        self.push(blink);
+    }
+
+    /// Resets [`BlinkAlloc`] instance deallocating all its chunks
+    /// and puts it into the cache.
+    #[inline]
+    pub fn push_reset_all(&self, mut blink: BlinkAlloc<A>) {
+        blink.reset_final();
+        self.push(blink);
+    }
+
+    /// Returns total capacity of chunks owned by all cached [`BlinkAlloc`] instances.
+    ///
+    /// Useful to decide when cached instances should be shrunk or dropped
+    /// to keep memory budget.
+    ///
+    /// Blocks concurrent pushes and pops while cached instances are inspected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use blink_alloc::{BlinkAlloc, BlinkAllocCache};
+    /// # use std::alloc::Layout;
+    /// let cache = BlinkAllocCache::new();
+    /// let blink = BlinkAlloc::new();
+    /// blink.allocate(Layout::new::<[u8; 1024]>()).unwrap();
+    /// cache.push(blink);
+    /// cache.push(BlinkAlloc::new());
+    ///
+    /// assert!(cache.total_cached_capacity() >= 1024);
+    /// assert!(cache.total_cached_used() >= 1024);
+    /// ```
+    pub fn total_cached_capacity(&self) -> usize {
+        let mut total = 0;
+        self.for_each_cached(|blink| total += blink.stats().capacity);
+        total
+    }
+
+    /// Returns total number of bytes used from chunks
+    /// by all cached [`BlinkAlloc`] instances.
+    ///
+    /// Instances pushed with [`push_reset`](BlinkAllocCache::push_reset)
+    /// use no bytes.
+    ///
+    /// Blocks concurrent pushes and pops while cached instances are inspected.
+    pub fn total_cached_used(&self) -> usize {
+        let mut total = 0;
+        self.for_each_cached(|blink| total += blink.used_bytes());
+        total
+    }
+
+    /// Calls `f` for each cached [`BlinkAlloc`] instance.
+    fn for_each_cached(&self, mut f: impl FnMut(&BlinkAlloc<A>)) {
+        // Exclusive lock is required since concurrent pop
+        // may move out instance that is being inspected.
+        let inner = self.inner.write();
+
+        let popped = inner.next_pop.load(Ordering::Relaxed);
+        for cell in inner.pop_array.iter().skip(popped) {
+            // Safety: Instances past `next_pop` are not popped.
+            // Exclusive lock prevents concurrent pop.
+            f(unsafe { &*cell.get() });
+        }
+
+        let pushed = inner.next_push.load(Ordering::Relaxed);
+        for cell in inner.push_array.iter().take(pushed) {
+            // Safety: `next_push` equals the number of elements for which push started.
+            // Exclusive lock ensures initialization finished.
+            f(unsafe { (*cell.get()).assume_init_ref() });
+        }
+    }
+
     fn flush(inner: &mut Inner<A>) {
         let pushed = replace(inner.next_push.get_mut(), 0).min(inner.push_array.len());
         let popped = replace(inner.next_pop.get_mut(), 0).min(inner.pop_array.len());
@@ -152,6 +276,188 @@ where
     }
 }
 
+impl<A> IntoIterator for BlinkAllocCache<A>
+where
+    A: Allocator,
+{
+    type Item = BlinkAlloc<A>;
+    type IntoIter = BlinkAllocCacheIter<A>;
+
+    /// Drains all cached [`BlinkAlloc`] instances.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use blink_alloc::{BlinkAlloc, BlinkAllocCache};
+    /// let cache = BlinkAllocCache::new();
+    /// cache.push(BlinkAlloc::new());
+    /// cache.push(BlinkAlloc::new());
+    ///
+    /// let mut count = 0;
+    /// for mut blink in cache {
+    ///     blink.reset_final();
+    ///     count += 1;
+    /// }
+    /// assert_eq!(count, 2);
+    /// ```
+    fn into_iter(self) -> BlinkAllocCacheIter<A> {
+        let mut inner = self.inner.into_inner();
+        Self::flush(&mut inner);
+
+        BlinkAllocCacheIter {
+            iter: inner.pop_array.into_iter(),
+        }
+    }
+}
+
+/// Iterator over [`BlinkAlloc`] instances drained from [`BlinkAllocCache`].
+///
+/// Created by [`BlinkAllocCache::into_iter`].
+pub struct BlinkAllocCacheIter<A: Allocator = Global> {
+    iter: alloc::vec::IntoIter<UnsafeCell<ManuallyDrop<BlinkAlloc<A>>>>,
+}
+
+impl<A> Iterator for BlinkAllocCacheIter<A>
+where
+    A: Allocator,
+{
+    type Item = BlinkAlloc<A>;
+
+    #[inline]
+    fn next(&mut self) -> Option<BlinkAlloc<A>> {
+        let cell = self.iter.next()?;
+        Some(ManuallyDrop::into_inner(cell.into_inner()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<A> ExactSizeIterator for BlinkAllocCacheIter<A> where A: Allocator {}
+
+impl<A> Drop for BlinkAllocCacheIter<A>
+where
+    A: Allocator,
+{
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+/// Multi-thread cache for [`BlinkAlloc`] instances
+/// with separate hot and cold tiers.
+///
+/// Pushed instances are placed into the small hot tier.
+/// When hot tier exceeds its capacity, oldest instances
+/// migrate to the larger cold tier.
+/// Pop tries the hot tier first, so frequently reused
+/// instances stay hot.
+///
+/// Instances can be moved from hot tier to cold
+/// explicitly with [`TieredBlinkAllocCache::cool`].
+///
+/// # Example
+///
+/// ```
+/// # use blink_alloc::{BlinkAlloc, TieredBlinkAllocCache};
+/// let cache = TieredBlinkAllocCache::with_hot_capacity(1);
+/// cache.push(BlinkAlloc::new());
+/// cache.push(BlinkAlloc::new());
+/// assert_eq!(cache.hot_len(), 1);
+/// assert_eq!(cache.cold_len(), 1);
+///
+/// cache.cool();
+/// assert_eq!(cache.hot_len(), 0);
+/// assert_eq!(cache.cold_len(), 2);
+///
+/// assert!(cache.pop().is_some());
+/// assert!(cache.pop().is_some());
+/// assert!(cache.pop().is_none());
+/// ```
+pub struct TieredBlinkAllocCache<A: Allocator = Global> {
+    /// Recently pushed instances.
+    /// Newest instances are at the end.
+    hot: Mutex<Vec<BlinkAlloc<A>>>,
+
+    /// Instances migrated from the hot tier.
+    cold: Mutex<Vec<BlinkAlloc<A>>>,
+
+    /// Maximum number of instances in the hot tier.
+    hot_capacity: usize,
+}
+
+impl<A> Default for TieredBlinkAllocCache<A>
+where
+    A: Allocator,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A> TieredBlinkAllocCache<A>
+where
+    A: Allocator,
+{
+    /// Creates a new empty [`TieredBlinkAllocCache`].
+    /// Hot tier capacity equals to the number of hardware threads.
+    pub fn new() -> Self {
+        let hot_capacity = std::thread::available_parallelism().map_or(1, |n| n.get());
+        Self::with_hot_capacity(hot_capacity)
+    }
+
+    /// Creates a new empty [`TieredBlinkAllocCache`]
+    /// with specified hot tier capacity.
+    pub const fn with_hot_capacity(hot_capacity: usize) -> Self {
+        TieredBlinkAllocCache {
+            hot: Mutex::new(Vec::new()),
+            cold: Mutex::new(Vec::new()),
+            hot_capacity,
+        }
+    }
+
+    /// Acquires some [`BlinkAlloc`] instance from the cache.
+    /// Tries hot tier first.
+    /// Returns none if the cache is empty.
+    pub fn pop(&self) -> Option<BlinkAlloc<A>> {
+        if let Some(blink) = self.hot.lock().pop() {
+            return Some(blink);
+        }
+        self.cold.lock().pop()
+    }
+
+    /// Puts [`BlinkAlloc`] instance into the hot tier.
+    /// Migrates oldest instances to the cold tier
+    /// if hot tier capacity is exceeded.
+    pub fn push(&self, blink: BlinkAlloc<A>) {
+        let mut hot = self.hot.lock();
+        hot.push(blink);
+
+        if hot.len() > self.hot_capacity {
+            let excess = hot.len() - self.hot_capacity;
+            self.cold.lock().extend(hot.drain(..excess));
+        }
+    }
+
+    /// Migrates all instances from the hot tier to the cold tier.
+    pub fn cool(&self) {
+        let mut hot = self.hot.lock();
+        self.cold.lock().append(&mut hot);
+    }
+
+    /// Returns number of instances in the hot tier.
+    pub fn hot_len(&self) -> usize {
+        self.hot.lock().len()
+    }
+
+    /// Returns number of instances in the cold tier.
+    pub fn cold_len(&self) -> usize {
+        self.cold.lock().len()
+    }
+}
+
 fn prevent_overflow(atomic: &AtomicUsize, current: usize, upper: usize) {
     #[cold]
     fn cold_store(atomic: &AtomicUsize, upper: usize) {
@@ -0,0 +1,178 @@
+//! This module provides blink allocator wrapper
+//! that counts calls for use in tests.
+
+use core::{
+    alloc::Layout,
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use allocator_api2::alloc::{AllocError, Allocator};
+
+use crate::{api::BlinkAllocator, arena::BlinkStats};
+
+/// Wrapper over blink allocator that counts allocations and resets.
+///
+/// All calls are forwarded to the wrapped allocator.
+/// Useful in tests to check how many times collections
+/// request memory from the allocator.
+///
+/// Only available with "test-util" feature.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+/// # #[cfg(feature = "alloc")] fn main() {
+/// # use blink_alloc::{BlinkAlloc, CountingBlinkAlloc};
+/// # use allocator_api2::vec::Vec;
+/// let blink = CountingBlinkAlloc::new(BlinkAlloc::new());
+///
+/// let mut vec = Vec::with_capacity_in(4, &blink);
+/// vec.extend([1u32, 2, 3, 4]);
+/// drop(vec);
+///
+/// assert_eq!(blink.allocation_count(), 1);
+/// assert_eq!(blink.byte_count(), 16);
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+pub struct CountingBlinkAlloc<A> {
+    inner: A,
+    allocations: AtomicUsize,
+    bytes: AtomicUsize,
+    resets: AtomicUsize,
+}
+
+impl<A> CountingBlinkAlloc<A> {
+    /// Wraps allocator with zeroed counters.
+    #[inline(always)]
+    pub const fn new(inner: A) -> Self {
+        CountingBlinkAlloc {
+            inner,
+            allocations: AtomicUsize::new(0),
+            bytes: AtomicUsize::new(0),
+            resets: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns reference to the wrapped allocator.
+    #[inline(always)]
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Unwraps this wrapper, returning the wrapped allocator.
+    #[inline(always)]
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    /// Returns number of requests for new memory.
+    ///
+    /// Counts allocations and grows.
+    /// Shrinks and deallocations are not counted.
+    #[inline(always)]
+    pub fn allocation_count(&self) -> usize {
+        self.allocations.load(Ordering::Relaxed)
+    }
+
+    /// Returns number of bytes requested.
+    ///
+    /// Counts sizes of allocation layouts and growth of grown allocations.
+    #[inline(always)]
+    pub fn byte_count(&self) -> usize {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns number of [`BlinkAllocator::reset`] calls.
+    #[inline(always)]
+    pub fn reset_count(&self) -> usize {
+        self.resets.load(Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    fn count(&self, bytes: usize) {
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+}
+
+unsafe impl<A> Allocator for CountingBlinkAlloc<A>
+where
+    A: Allocator,
+{
+    #[inline(always)]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.count(layout.size());
+        self.inner.allocate(layout)
+    }
+
+    #[inline(always)]
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.count(layout.size());
+        self.inner.allocate_zeroed(layout)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // Safety: Covered by this method's contract.
+        unsafe { self.inner.deallocate(ptr, layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.count(new_layout.size() - old_layout.size());
+        // Safety: Covered by this method's contract.
+        unsafe { self.inner.grow(ptr, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn grow_zeroed(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.count(new_layout.size() - old_layout.size());
+        // Safety: Covered by this method's contract.
+        unsafe { self.inner.grow_zeroed(ptr, old_layout, new_layout) }
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        // Safety: Covered by this method's contract.
+        unsafe { self.inner.shrink(ptr, old_layout, new_layout) }
+    }
+}
+
+unsafe impl<A> BlinkAllocator for CountingBlinkAlloc<A>
+where
+    A: BlinkAllocator,
+{
+    #[inline(always)]
+    fn reset(&mut self) {
+        *self.resets.get_mut() += 1;
+        self.inner.reset();
+    }
+
+    #[inline(always)]
+    fn blink_stats(&self) -> Option<BlinkStats> {
+        self.inner.blink_stats()
+    }
+
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
@@ -2,9 +2,11 @@
 //! an intrusive linked list of drop functions.
 //!
 
+#[cfg(not(debug_assertions))]
+use core::marker::PhantomData;
 use core::{
     cell::Cell,
-    mem::MaybeUninit,
+    mem::{size_of, MaybeUninit},
     ptr::{self, addr_of_mut, slice_from_raw_parts_mut, NonNull},
 };
 
@@ -22,12 +24,65 @@ struct Drops {
 }
 
 impl Drops {
-    unsafe fn drop(ptr: NonNull<Self>) -> Option<NonNull<Self>> {
-        let Drops { count, drop, next } = *ptr.as_ref();
+    unsafe fn drop(ptr: NonNull<Self>) {
+        let Drops { count, drop, .. } = *ptr.as_ref();
         // Safety: `DropItem` constructed as part of `DropItemValue<T>`.
         // And `drop` is set to `drop_in_place::<T>`.
         unsafe { (drop)(ptr, count) };
-        next
+    }
+}
+
+/// Chain of items being dropped.
+///
+/// If dropping an item panics, remaining items
+/// are dropped when the chain is dropped during unwinding.
+/// Panic in one of those aborts, same as in `Vec` destructor.
+struct DropChain {
+    next: Option<NonNull<Drops>>,
+    stop: Option<NonNull<Drops>>,
+}
+
+impl DropChain {
+    /// Drops items starting from `next` until `stop` is reached.
+    ///
+    /// Returns number of items dropped.
+    ///
+    /// # Safety
+    ///
+    /// All items in the chain must be valid `DropItem`s
+    /// that didn't move since they were added to the list.
+    #[inline(always)]
+    unsafe fn drop_all(&mut self) -> usize {
+        let mut count = 0;
+        while self.next != self.stop {
+            let Some(item_ptr) = self.next else {
+                debug_assert!(false, "Checkpoint item is not found in the list");
+                // Nothing left to drop.
+                self.stop = None;
+                break;
+            };
+
+            // Safety: `item` is a valid pointer to `DropItem`.
+            // And it didn't move since it was added to the list.
+            // Move to the next item first, so it is dropped even if this one panics.
+            unsafe {
+                self.next = item_ptr.as_ref().next;
+                Drops::drop(item_ptr);
+            }
+            count += 1;
+        }
+        count
+    }
+}
+
+impl Drop for DropChain {
+    #[inline(always)]
+    fn drop(&mut self) {
+        // Only non-empty when dropping an item panics.
+        // Safety: Same as in `drop_all`.
+        unsafe {
+            self.drop_all();
+        }
     }
 }
 
@@ -58,10 +113,15 @@ impl<T> DropItem<T> {
 }
 
 impl<T> DropItem<[T; 0]> {
+    /// Initializes header of slice item.
+    /// Returns pointer to the item and the slice of values after the header.
+    ///
+    /// Item is returned as pointer to keep provenance over the values,
+    /// which are accessed through it when dropped.
     pub unsafe fn init_slice<'a>(
-        mut ptr: NonNull<DropItem<[T; 0]>>,
+        ptr: NonNull<DropItem<[T; 0]>>,
         count: usize,
-    ) -> (&'a mut Self, &'a mut [T]) {
+    ) -> (NonNull<Self>, &'a mut [T]) {
         debug_assert_ne!(
             count, 0,
             "DropItem<[T]> should not be constructed with count 0"
@@ -75,22 +135,34 @@ impl<T> DropItem<[T; 0]> {
             },
         );
         let slice = core::slice::from_raw_parts_mut(ptr.as_ptr().add(1).cast(), count);
-        (ptr.as_mut(), slice)
+        (ptr, slice)
     }
 }
 
+/// Head of the [`DropList`] at some point.
+#[derive(Clone, Copy)]
+pub struct DropCheckpoint {
+    root: Option<NonNull<Drops>>,
+}
+
 /// Intrusive linked list of drop functions.
 pub struct DropList {
     // Root item of the list.
     // Contains `None` if list is empty.
     // Lifetime of the items is bound to `DropList::reset` method calls.
     root: Cell<Option<NonNull<Drops>>>,
+
+    // Set while items are being dropped.
+    #[cfg(debug_assertions)]
+    draining: Cell<bool>,
 }
 
 impl DropList {
     pub const fn new() -> Self {
         DropList {
             root: Cell::new(None),
+            #[cfg(debug_assertions)]
+            draining: Cell::new(false),
         }
     }
 
@@ -107,17 +179,203 @@ impl DropList {
         &mut *addr_of_mut!((*item.as_ptr()).value)
     }
 
-    /// Drops all items in the list.
-    pub fn reset(&mut self) {
-        let mut next = self.root.take();
+    /// Adds new slice drop item initialized with [`DropItem::init_slice`].
+    ///
+    /// # Safety
+    ///
+    /// `item` must be valid until next call to [`DropList::reset`].
+    pub unsafe fn add_slice<T>(&self, item: NonNull<DropItem<[T; 0]>>) {
+        let next = self.root.take();
+        addr_of_mut!((*item.as_ptr()).drops.next).write(next);
+        self.root.set(Some(item.cast()));
+    }
+
+    /// Returns `true` if the list has no items.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.root.get().is_none()
+    }
+
+    /// Finds slice item that drops exactly the values of `slice`.
+    /// Returns pointer to the number of values dropped by the item.
+    /// Returns `None` if there is no such item.
+    ///
+    /// Walks the list from the most recently added item.
+    ///
+    /// Item is matched by address of its values and their count.
+    /// Drop functions are not compared since function pointers
+    /// are not guaranteed to be unique.
+    pub fn find_slice<T>(&self, slice: NonNull<[T]>) -> Option<NonNull<usize>> {
+        let offset = size_of::<DropItem<[T; 0]>>();
 
+        let mut next = self.root.get();
         while let Some(item_ptr) = next {
             // Safety: `item` is a valid pointer to `DropItem`.
             // And it didn't move since it was added to the list.
+            let item = unsafe { item_ptr.as_ref() };
+
+            // Values of slice item are placed right after the header.
+            let values = item_ptr.as_ptr().cast::<u8>().wrapping_add(offset);
+            if values == slice.as_ptr().cast::<u8>() && item.count == slice.len() {
+                // Safety: Pointer to the field of valid item.
+                let count = unsafe { addr_of_mut!((*item_ptr.as_ptr()).count) };
+                return NonNull::new(count);
+            }
+            next = item.next;
+        }
+        None
+    }
+
+    /// Returns current head of the list
+    /// to drop only items added after this call with [`DropList::rollback`].
+    #[inline(always)]
+    pub fn checkpoint(&self) -> DropCheckpoint {
+        DropCheckpoint {
+            root: self.root.get(),
+        }
+    }
+
+    /// Drops items added after the checkpoint.
+    ///
+    /// # Safety
+    ///
+    /// `checkpoint` must be created from this list
+    /// and no reset could happen since then.
+    pub unsafe fn rollback(&mut self, checkpoint: DropCheckpoint) {
+        let _draining = self.drain();
+
+        // Items added while dropping are dropped as well.
+        loop {
+            let next = self.root.replace(checkpoint.root);
+            if next == checkpoint.root {
+                break;
+            }
+
+            let mut chain = DropChain {
+                next,
+                stop: checkpoint.root,
+            };
+
+            // Safety: Items are valid until reset.
             unsafe {
-                next = Drops::drop(item_ptr);
+                chain.drop_all();
+            }
+        }
+    }
+
+    /// Drops all items in the list.
+    ///
+    /// Returns number of items dropped.
+    /// Slice item counts as one.
+    pub fn reset(&mut self) -> usize {
+        // Safety: `self` is borrowed mutably.
+        unsafe { self.reset_unchecked() }
+    }
+
+    /// Drops all items in the list through shared reference.
+    ///
+    /// Returns number of items dropped.
+    ///
+    /// Dropped values may add new items to the list,
+    /// those are dropped before this method returns.
+    /// Resetting the list from dropped values is not allowed
+    /// and triggers debug assertion.
+    ///
+    /// # Safety
+    ///
+    /// Values in the list must not be used after this call.
+    pub unsafe fn reset_unchecked(&self) -> usize {
+        let _draining = self.drain();
+        let mut count = 0;
+
+        // Items added while dropping are dropped as well.
+        while let Some(root) = self.root.take() {
+            let mut chain = DropChain {
+                next: Some(root),
+                stop: None,
+            };
+
+            // Safety: Items are valid until reset.
+            count += unsafe { chain.drop_all() };
+        }
+
+        count
+    }
+
+    /// Drops all items in the list in order they were added.
+    ///
+    /// Reverses the list in place before dropping,
+    /// so no additional memory is required.
+    ///
+    /// Returns number of items dropped.
+    pub fn ordered_reset(&mut self) -> usize {
+        let _draining = self.drain();
+        let mut count = 0;
+
+        // Items added while dropping are dropped as well.
+        while let Some(root) = self.root.take() {
+            let mut next = Some(root);
+            let mut reversed = None;
+
+            while let Some(mut item_ptr) = next {
+                // Safety: `item` is a valid pointer to `DropItem`.
+                // And it didn't move since it was added to the list.
+                let item = unsafe { item_ptr.as_mut() };
+                next = core::mem::replace(&mut item.next, reversed);
+                reversed = Some(item_ptr);
+            }
+
+            let mut chain = DropChain {
+                next: reversed,
+                stop: None,
+            };
+
+            // Safety: Items are valid until reset.
+            count += unsafe { chain.drop_all() };
+        }
+
+        count
+    }
+
+    /// Marks the list as being drained until returned guard is dropped.
+    ///
+    /// In debug builds panics if the list is already being drained,
+    /// i.e. some dropped value tries to reset the list.
+    #[inline(always)]
+    #[track_caller]
+    fn drain(&self) -> Draining<'_> {
+        #[cfg(debug_assertions)]
+        {
+            assert!(
+                !self.draining.replace(true),
+                "`DropList` cannot be reset while its values are being dropped"
+            );
+            Draining {
+                draining: &self.draining,
             }
         }
+
+        #[cfg(not(debug_assertions))]
+        Draining {
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Guard that marks [`DropList`] as being drained.
+struct Draining<'a> {
+    #[cfg(debug_assertions)]
+    draining: &'a Cell<bool>,
+
+    #[cfg(not(debug_assertions))]
+    marker: PhantomData<&'a Cell<bool>>,
+}
+
+#[cfg(debug_assertions)]
+impl Drop for Draining<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.draining.set(false);
     }
 }
 
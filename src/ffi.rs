@@ -0,0 +1,38 @@
+//! Minimal C ABI for [`BlinkAlloc`].
+//!
+//! Allocator handle is created with [`BlinkAlloc::into_raw`]
+//! and reclaimed with [`BlinkAlloc::from_raw`].
+
+use core::{alloc::Layout, ptr::null_mut};
+
+use allocator_api2::alloc::Global;
+
+use crate::local::BlinkAlloc;
+
+/// Allocates memory from [`BlinkAlloc<Global>`] behind the `handle`.
+/// Returns null pointer if `size` and `align` do not form valid layout
+/// or allocation fails.
+///
+/// # Safety
+///
+/// `handle` must be returned by [`BlinkAlloc::into_raw`] for `BlinkAlloc<Global>`
+/// and not yet passed to [`BlinkAlloc::from_raw`].
+/// `handle` must not be used concurrently from multiple threads.
+#[no_mangle]
+pub unsafe extern "C" fn blink_alloc_alloc_raw(
+    handle: *mut (),
+    size: usize,
+    align: usize,
+) -> *mut u8 {
+    let Ok(layout) = Layout::from_size_align(size, align) else {
+        return null_mut();
+    };
+
+    // Safety: `handle` points to live `BlinkAlloc<Global>`.
+    let blink = unsafe { &*handle.cast::<BlinkAlloc<Global>>() };
+
+    match blink.allocate(layout) {
+        Ok(ptr) => ptr.as_ptr().cast(),
+        Err(_) => null_mut(),
+    }
+}
@@ -0,0 +1,221 @@
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    ptr::{null_mut, NonNull},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use allocator_api2::alloc::Allocator;
+
+use crate::sync::SyncBlinkAlloc;
+
+switch_std_default! {
+    /// [`GlobalAlloc`] implementation based on two [`SyncBlinkAlloc`]s
+    /// designed for frame-based applications.
+    ///
+    /// One of the buffers is active and serves all allocations.
+    /// [`DoubleBufferedGlobalAlloc::flip`] makes the other buffer active,
+    /// resetting it first.
+    ///
+    /// Buffer that was active before the flip is not reset until the next flip.
+    /// Thus memory allocated during frame N stays valid
+    /// during frame N+1 and is reused in frame N+2.
+    ///
+    /// It can be installed as `#[global_allocator]`,
+    /// but then [`DoubleBufferedGlobalAlloc::flip`] invalidates memory
+    /// allocated by any code in the program.
+    /// See safety section of [`DoubleBufferedGlobalAlloc::flip`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::alloc::{GlobalAlloc, Layout};
+    /// use blink_alloc::DoubleBufferedGlobalAlloc;
+    ///
+    /// let alloc = DoubleBufferedGlobalAlloc::new();
+    /// let layout = Layout::new::<u32>();
+    ///
+    /// unsafe {
+    ///     // Frame 0.
+    ///     let a = alloc.alloc(layout).cast::<u32>();
+    ///     a.write(1);
+    ///     alloc.flip();
+    ///
+    ///     // Frame 1. Memory of frame 0 is still valid.
+    ///     let b = alloc.alloc(layout).cast::<u32>();
+    ///     b.write(2);
+    ///     assert_eq!(a.read(), 1);
+    ///     alloc.flip();
+    ///
+    ///     // Frame 2. Memory of frame 0 is reused.
+    ///     assert_eq!(b.read(), 2);
+    ///     assert_eq!(alloc.alloc(layout).cast::<u32>(), a);
+    /// }
+    /// ```
+    pub struct DoubleBufferedGlobalAlloc<A: Allocator = +std::alloc::System> {
+        buffers: [SyncBlinkAlloc<A>; 2],
+        active: AtomicUsize,
+    }
+}
+
+#[cfg(feature = "std")]
+impl DoubleBufferedGlobalAlloc<std::alloc::System> {
+    /// Create a new [`DoubleBufferedGlobalAlloc`].
+    ///
+    /// Const function can be used to initialize a static variable.
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        DoubleBufferedGlobalAlloc::new_in(std::alloc::System, std::alloc::System)
+    }
+
+    /// Create a new [`DoubleBufferedGlobalAlloc`].
+    ///
+    /// This method allows to specify initial chunk size of each buffer.
+    ///
+    /// Const function can be used to initialize a static variable.
+    pub const fn with_chunk_size(chunk_size: usize) -> Self {
+        DoubleBufferedGlobalAlloc::with_chunk_size_in(
+            chunk_size,
+            std::alloc::System,
+            std::alloc::System,
+        )
+    }
+}
+
+impl<A> DoubleBufferedGlobalAlloc<A>
+where
+    A: Allocator,
+{
+    /// Create a new [`DoubleBufferedGlobalAlloc`]
+    /// with specified underlying allocators for each buffer.
+    ///
+    /// Const function can be used to initialize a static variable.
+    pub const fn new_in(front: A, back: A) -> Self {
+        DoubleBufferedGlobalAlloc {
+            buffers: [SyncBlinkAlloc::new_in(front), SyncBlinkAlloc::new_in(back)],
+            active: AtomicUsize::new(0),
+        }
+    }
+
+    /// Create a new [`DoubleBufferedGlobalAlloc`]
+    /// with specified underlying allocators for each buffer.
+    ///
+    /// This method allows to specify initial chunk size of each buffer.
+    ///
+    /// Const function can be used to initialize a static variable.
+    pub const fn with_chunk_size_in(chunk_size: usize, front: A, back: A) -> Self {
+        DoubleBufferedGlobalAlloc {
+            buffers: [
+                SyncBlinkAlloc::with_chunk_size_in(chunk_size, front),
+                SyncBlinkAlloc::with_chunk_size_in(chunk_size, back),
+            ],
+            active: AtomicUsize::new(0),
+        }
+    }
+
+    /// Resets idle buffer and makes it active.
+    ///
+    /// Memory allocated since previous flip stays valid until the next flip.
+    /// Memory allocated before previous flip is invalidated.
+    ///
+    /// # Safety
+    ///
+    /// Memory allocated before previous flip must not be used
+    /// or deallocated after this call.
+    ///
+    /// Must be externally synchronized with other threads accessing this allocator.
+    ///
+    /// When installed as `#[global_allocator]` this applies to every allocation
+    /// in the program, including ones made by the standard library
+    /// and dependencies, e.g. thread-local storage, buffered I/O
+    /// or lazily initialized statics.
+    /// No such memory may outlive two flips,
+    /// and no other thread may allocate during the flip.
+    #[inline]
+    pub unsafe fn flip(&self) {
+        let idle = self.active.load(Ordering::Acquire) ^ 1;
+
+        // Safety: Memory allocated from idle buffer is not used anymore.
+        unsafe { self.buffers[idle].reset_unchecked() };
+        self.active.store(idle, Ordering::Release);
+    }
+
+    #[inline(always)]
+    fn active(&self) -> &SyncBlinkAlloc<A> {
+        &self.buffers[self.active.load(Ordering::Acquire)]
+    }
+
+    /// Returns buffer whose last chunk contains memory at `ptr`.
+    ///
+    /// Only memory of the last chunk can be reclaimed or grown in place,
+    /// so older chunks are not checked.
+    /// This keeps the lookup independent of the number of chunks.
+    #[inline(always)]
+    fn owner(&self, ptr: *mut u8) -> Option<&SyncBlinkAlloc<A>> {
+        self.buffers
+            .iter()
+            .find(|buffer| buffer.last_chunk_owns(ptr))
+    }
+}
+
+unsafe impl<A> GlobalAlloc for DoubleBufferedGlobalAlloc<A>
+where
+    A: Allocator,
+{
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        match self.active().allocate(layout) {
+            Ok(ptr) => ptr.as_ptr().cast(),
+            Err(_) => null_mut(),
+        }
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if let Some(owner) = self.owner(ptr) {
+            owner.deallocate(NonNull::new_unchecked(ptr), layout.size());
+        }
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        match self.active().allocate_zeroed(layout) {
+            Ok(ptr) => ptr.as_ptr().cast(),
+            Err(_) => null_mut(),
+        }
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let Ok(new_layout) = Layout::from_size_align(new_size, layout.align()) else {
+            return null_mut();
+        };
+
+        let active = self.active();
+
+        let result = match NonNull::new(ptr) {
+            None => active.allocate(new_layout),
+            Some(ptr) if active.last_chunk_owns(ptr.as_ptr()) => {
+                active.resize(ptr, layout, new_layout)
+            }
+            Some(ptr) => {
+                // Memory from the last frame or from older chunks
+                // is moved to the last chunk of the active buffer.
+                let result = active.allocate(new_layout);
+                if let Ok(new_ptr) = result {
+                    core::ptr::copy_nonoverlapping(
+                        ptr.as_ptr(),
+                        new_ptr.as_ptr().cast(),
+                        layout.size().min(new_size),
+                    );
+                    self.dealloc(ptr.as_ptr(), layout);
+                }
+                result
+            }
+        };
+
+        match result {
+            Ok(ptr) => ptr.as_ptr().cast(),
+            Err(_) => null_mut(),
+        }
+    }
+}
@@ -50,7 +50,16 @@ impl<A: Allocator> State<A> {
             true => self.blink.resize(ptr, old_layout, new_layout),
             false => {
                 cold();
-                if old_layout.size() >= new_layout.size() {
+                if self.blink.owns(ptr.as_ptr()) {
+                    // Memory allocated in blink mode is moved to the underlying allocator.
+                    let new_ptr = self.blink.inner().allocate(new_layout)?;
+                    core::ptr::copy_nonoverlapping(
+                        ptr.as_ptr(),
+                        new_ptr.as_ptr().cast(),
+                        old_layout.size().min(new_layout.size()),
+                    );
+                    Ok(new_ptr)
+                } else if old_layout.size() <= new_layout.size() {
                     self.blink.inner().grow(ptr, old_layout, new_layout)
                 } else {
                     self.blink.inner().shrink(ptr, old_layout, new_layout)
@@ -65,7 +74,10 @@ impl<A: Allocator> State<A> {
             true => self.blink.deallocate(ptr, layout.size()),
             false => {
                 cold();
-                self.blink.inner().deallocate(ptr, layout)
+                // Memory allocated in blink mode is freed on reset.
+                if !self.blink.owns(ptr.as_ptr()) {
+                    self.blink.inner().deallocate(ptr, layout)
+                }
             }
         }
     }
@@ -298,7 +310,7 @@ where
         (*self.state.get()).enabled = true;
     }
 
-    /// Switches allocator to blink mode.
+    /// Switches allocator to direct mode.
     /// All allocations will be served by underlying allocator.
     ///
     /// The type is created in direct mode.
@@ -307,13 +319,21 @@ where
     ///
     /// However user must switch back to direct mode before returning from `main`.
     ///
+    /// Memory allocated in blink mode stays valid until [`reset`](UnsafeGlobalBlinkAlloc::reset).
+    /// Deallocating it in direct mode is a no-op
+    /// and reallocating moves it to the underlying allocator.
+    ///
+    /// Unlike `GlobalBlinkAlloc::direct_mode`
+    /// this method does not reset the allocator.
+    /// Pointers are checked against the chunks of this allocator
+    /// when deallocated in direct mode, which is affordable
+    /// for single-threaded allocator.
+    ///
     /// # Safety
     ///
     /// Must be externally synchronized with other threads accessing this allocator.
-    /// Memory allocated in blink mode must not be deallocated while in direct mode.
     #[inline(always)]
     pub unsafe fn direct_mode(&self) {
-        self.reset();
         (*self.state.get()).enabled = false;
     }
 }
@@ -339,12 +359,12 @@ where
     #[inline]
     unsafe fn dealloc(&self, ptr: *mut u8, layout: core::alloc::Layout) {
         let ptr = NonNull::new_unchecked(ptr);
-        (*self.state.get()).deallocate(ptr, layout);
         #[cfg(debug_assertions)]
-        if (*self.state.get()).enabled {
+        if (*self.state.get()).blink.owns(ptr.as_ptr()) {
             self.allocations
                 .set(self.allocations.get().saturating_sub(1));
         }
+        (*self.state.get()).deallocate(ptr, layout);
     }
 
     #[inline]
@@ -374,7 +394,18 @@ where
 
         let result = match NonNull::new(ptr) {
             None => (*self.state.get()).allocate(new_layout),
-            Some(ptr) => (*self.state.get()).resize(ptr, layout, new_layout),
+            Some(ptr) => {
+                let result = (*self.state.get()).resize(ptr, layout, new_layout);
+                #[cfg(debug_assertions)]
+                if result.is_ok()
+                    && !(*self.state.get()).enabled
+                    && (*self.state.get()).blink.owns(ptr.as_ptr())
+                {
+                    self.allocations
+                        .set(self.allocations.get().saturating_sub(1));
+                }
+                result
+            }
         };
 
         match result {
@@ -1,6 +1,8 @@
 //! This module provide types suitable for use as `#[global_allocator]`.
 //!
 
+#[cfg(feature = "sync")]
+pub mod double;
 pub mod local;
 #[cfg(feature = "sync")]
 pub mod sync;
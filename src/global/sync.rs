@@ -1,16 +1,18 @@
 use core::{
     alloc::{GlobalAlloc, Layout},
-    cell::UnsafeCell,
+    cell::{Cell, UnsafeCell},
+    marker::PhantomData,
     ptr::{null_mut, NonNull},
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
 };
 
-#[cfg(debug_assertions)]
-use core::sync::atomic::{AtomicU64, Ordering};
-
 use allocator_api2::alloc::{AllocError, Allocator};
 
 use crate::{cold, sync::SyncBlinkAlloc, LocalBlinkAlloc};
 
+#[cfg(not(no_global_oom_handling))]
+use crate::oom::handle_alloc_error;
+
 struct State<A: Allocator> {
     blink: SyncBlinkAlloc<A>,
     enabled: bool,
@@ -50,7 +52,7 @@ impl<A: Allocator> State<A> {
             true => self.blink.resize(ptr, old_layout, new_layout),
             false => {
                 cold();
-                if old_layout.size() >= new_layout.size() {
+                if old_layout.size() <= new_layout.size() {
                     self.blink.inner().grow(ptr, old_layout, new_layout)
                 } else {
                     self.blink.inner().shrink(ptr, old_layout, new_layout)
@@ -89,8 +91,15 @@ switch_std_default! {
     /// ```
     pub struct GlobalBlinkAlloc<A: Allocator = +std::alloc::System> {
         state: UnsafeCell<State<A>>,
+        fallback: Option<&'static (dyn GlobalAlloc + Sync)>,
+        fallback_allocations: AtomicU64,
+        fallback_live: AtomicUsize,
+        active_proxies: AtomicUsize,
+        resets: AtomicUsize,
         #[cfg(debug_assertions)]
         allocations: AtomicU64,
+        #[cfg(debug_assertions)]
+        mode_thread: AtomicUsize,
     }
 }
 
@@ -116,6 +125,7 @@ impl GlobalBlinkAlloc<std::alloc::System> {
     ///     let _ = vec![1, 2, 3];
     /// }
     /// ```
+    #[allow(clippy::new_without_default)]
     pub const fn new() -> Self {
         GlobalBlinkAlloc::new_in(std::alloc::System)
     }
@@ -172,8 +182,15 @@ where
                 blink: SyncBlinkAlloc::new_in(allocator),
                 enabled: false,
             }),
+            fallback: None,
+            fallback_allocations: AtomicU64::new(0),
+            fallback_live: AtomicUsize::new(0),
+            active_proxies: AtomicUsize::new(0),
+            resets: AtomicUsize::new(0),
             #[cfg(debug_assertions)]
             allocations: AtomicU64::new(0),
+            #[cfg(debug_assertions)]
+            mode_thread: AtomicUsize::new(0),
         }
     }
 
@@ -203,9 +220,91 @@ where
                 blink: SyncBlinkAlloc::with_chunk_size_in(chunk_size, allocator),
                 enabled: false,
             }),
+            fallback: None,
+            fallback_allocations: AtomicU64::new(0),
+            fallback_live: AtomicUsize::new(0),
+            active_proxies: AtomicUsize::new(0),
+            resets: AtomicUsize::new(0),
             #[cfg(debug_assertions)]
             allocations: AtomicU64::new(0),
+            #[cfg(debug_assertions)]
+            mode_thread: AtomicUsize::new(0),
+        }
+    }
+
+    /// Installs fallback allocator.
+    ///
+    /// In blink mode, when [`SyncBlinkAlloc`] fails to allocate memory,
+    /// allocation is served by the fallback allocator instead of failing.
+    /// Memory allocated from the fallback is returned to it on deallocation.
+    ///
+    /// Fallback allocations follow the same rules as blink allocations:
+    /// they must be deallocated before switching back to direct mode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blink_alloc::GlobalBlinkAlloc;
+    ///
+    /// #[global_allocator]
+    /// static GLOBAL_ALLOC: GlobalBlinkAlloc = GlobalBlinkAlloc::new().with_fallback(&std::alloc::System);
+    ///
+    /// fn main() {
+    ///     let _ = Box::new(42);
+    ///     assert_eq!(GLOBAL_ALLOC.fallback_alloc_count(), 0);
+    /// }
+    /// ```
+    pub const fn with_fallback(mut self, fallback: &'static (dyn GlobalAlloc + Sync)) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Returns total number of allocations served by the fallback allocator.
+    ///
+    /// See [`GlobalBlinkAlloc::with_fallback`].
+    #[inline(always)]
+    pub fn fallback_alloc_count(&self) -> u64 {
+        self.fallback_allocations.load(Ordering::Relaxed)
+    }
+
+    /// Returns number of fallback allocations that are not deallocated yet.
+    #[cfg(test)]
+    pub(crate) fn fallback_live_count(&self) -> usize {
+        self.fallback_live.load(Ordering::Relaxed)
+    }
+
+    /// Returns fallback allocator if `ptr` may have been allocated from it.
+    ///
+    /// Ownership is checked only while there are live fallback allocations.
+    #[inline(always)]
+    unsafe fn fallback_for(&self, ptr: *mut u8) -> Option<&'static (dyn GlobalAlloc + Sync)> {
+        let fallback = self.fallback?;
+        let state = &*self.state.get();
+        if !state.enabled
+            || self.fallback_live.load(Ordering::Relaxed) == 0
+            || state.blink.owns(ptr)
+        {
+            return None;
         }
+        Some(fallback)
+    }
+
+    /// Allocates memory from the fallback allocator if installed.
+    /// Fallback is used only in blink mode.
+    #[cold]
+    unsafe fn fallback_alloc(&self, alloc: impl FnOnce(&dyn GlobalAlloc) -> *mut u8) -> *mut u8 {
+        if !(*self.state.get()).enabled {
+            return null_mut();
+        }
+        let Some(fallback) = self.fallback else {
+            return null_mut();
+        };
+        let ptr = alloc(fallback);
+        if !ptr.is_null() {
+            self.fallback_allocations.fetch_add(1, Ordering::Relaxed);
+            self.fallback_live.fetch_add(1, Ordering::Relaxed);
+        }
+        ptr
     }
 
     /// Resets this allocator, deallocating all chunks except the last one.
@@ -247,6 +346,7 @@ where
     pub unsafe fn reset(&self) {
         #[cfg(debug_assertions)]
         {
+            self.check_mode_thread();
             assert_eq!(
                 self.allocations.load(Ordering::SeqCst),
                 0,
@@ -255,6 +355,9 @@ where
         }
 
         (*self.state.get()).blink.reset_unchecked();
+
+        // Chunks of kept thread-local proxies are freed.
+        self.resets.fetch_add(1, Ordering::Relaxed);
     }
 
     /// Switches allocator to blink mode.
@@ -272,6 +375,9 @@ where
     /// Memory allocated in direct mode must not be deallocated while in blink mode.
     #[inline(always)]
     pub unsafe fn blink_mode(&self) {
+        #[cfg(debug_assertions)]
+        self.check_mode_thread();
+
         (*self.state.get()).enabled = true;
     }
 
@@ -284,16 +390,88 @@ where
     ///
     /// However user must switch back to direct mode before returning from `main`.
     ///
+    /// This method resets the allocator, see [`reset`](GlobalBlinkAlloc::reset).
+    /// Unlike [`UnsafeGlobalBlinkAlloc::direct_mode`](crate::UnsafeGlobalBlinkAlloc::direct_mode)
+    /// memory allocated in blink mode does not outlive the switch.
+    /// Direct mode deallocations are not checked against blink chunks,
+    /// since that would lock the shared allocator on every deallocation.
+    ///
     /// # Safety
     ///
     /// Must be externally synchronized with other threads accessing this allocator.
-    /// Memory allocated in blink mode must not be deallocated while in direct mode.
+    /// Memory allocated in blink mode must be deallocated before switching
+    /// and must not be used after.
     #[inline(always)]
     pub unsafe fn direct_mode(&self) {
         self.reset();
         (*self.state.get()).enabled = false;
     }
 
+    /// Switches allocator to blink mode
+    /// and returns guard that switches it back to direct mode on drop.
+    ///
+    /// In debug builds the guard records the current thread.
+    /// While the guard is alive, [`reset`](GlobalBlinkAlloc::reset)
+    /// and mode switches from other threads panic.
+    /// Allocations from other threads are allowed.
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`blink_mode`](GlobalBlinkAlloc::blink_mode).
+    /// Memory allocated while the guard is alive must be deallocated
+    /// before the guard is dropped.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds panics if another guard is alive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blink_alloc::GlobalBlinkAlloc;
+    ///
+    /// #[global_allocator]
+    /// static GLOBAL_ALLOC: GlobalBlinkAlloc = GlobalBlinkAlloc::new();
+    ///
+    /// fn main() {
+    ///     let guard = unsafe { GLOBAL_ALLOC.enter_blink_mode() };
+    ///     let v = vec![1, 2, 3];
+    ///     assert_eq!(v.iter().sum::<i32>(), 6);
+    ///     drop(v);
+    ///
+    ///     // Safety: Memory allocated in blink mode is deallocated.
+    ///     drop(guard);
+    /// }
+    /// ```
+    pub unsafe fn enter_blink_mode(&self) -> BlinkModeGuard<'_, A> {
+        #[cfg(debug_assertions)]
+        {
+            let entered = self.mode_thread.swap(thread_marker(), Ordering::Relaxed);
+            assert_eq!(
+                entered, 0,
+                "`GlobalBlinkAlloc` blink mode is already entered"
+            );
+        }
+
+        (*self.state.get()).enabled = true;
+
+        BlinkModeGuard {
+            global: self,
+            marker: PhantomData,
+        }
+    }
+
+    /// Checks that blink mode is not entered by another thread.
+    #[cfg(debug_assertions)]
+    #[inline]
+    fn check_mode_thread(&self) {
+        let owner = self.mode_thread.load(Ordering::Relaxed);
+        assert!(
+            owner == 0 || owner == thread_marker(),
+            "`GlobalBlinkAlloc` is used from thread other than the one that entered blink mode"
+        );
+    }
+
     /// Creates a new thread-local blink allocator proxy
     /// that borrows from this multi-threaded allocator.
     ///
@@ -338,30 +516,562 @@ where
     pub fn local(&self) -> LocalBlinkAlloc<'_, A> {
         unsafe { (*self.state.get()).blink.local() }
     }
+
+    /// Activates thread-local [`LocalBlinkAlloc`] proxy for the current thread.
+    ///
+    /// While returned guard is alive, allocations made in blink mode
+    /// from the current thread are served by the proxy
+    /// without touching shared state.
+    /// Proxy is created in thread-local storage on first call
+    /// and reset when the last guard is dropped.
+    /// The same proxy and its chunk are reused by the next guard
+    /// on this thread, unless this allocator was reset in between.
+    /// Proxy is dropped on thread exit.
+    /// Memory for the proxy is allocated once per thread
+    /// from the underlying allocator.
+    ///
+    /// Guards may be nested.
+    ///
+    /// Deallocation in blink mode is routed to the proxy of the current thread.
+    /// Without one it goes to the shared allocator,
+    /// unless a guard is alive on another thread.
+    /// In that case the memory may belong to that thread's proxy chunk,
+    /// so it is left in place and reclaimed on reset.
+    ///
+    /// # Safety
+    ///
+    /// Every allocation made through the global allocator on this thread
+    /// while the guard is alive is served by the proxy,
+    /// including allocations made by code the caller does not control.
+    /// Proxy memory is reused after the guard is dropped,
+    /// which cannot be checked by the compiler.
+    ///
+    /// Memory allocated while the guard is alive must be deallocated
+    /// before the guard is dropped and must not be used after.
+    /// This allocator must not be reset while the guard is alive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current thread has active proxy
+    /// of another [`GlobalBlinkAlloc`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blink_alloc::GlobalBlinkAlloc;
+    ///
+    /// #[global_allocator]
+    /// static GLOBAL_ALLOC: GlobalBlinkAlloc = GlobalBlinkAlloc::new();
+    ///
+    /// fn main() {
+    ///     unsafe { GLOBAL_ALLOC.blink_mode() };
+    ///
+    ///     // Safety: Memory allocated in the scope is not used after the guard is dropped.
+    ///     let guard = unsafe { GLOBAL_ALLOC.with_thread_local_proxy() };
+    ///     let v = vec![1, 2, 3];
+    ///     assert_eq!(v.iter().sum::<i32>(), 6);
+    ///     drop(v);
+    ///     drop(guard);
+    ///
+    ///     unsafe { GLOBAL_ALLOC.direct_mode() };
+    /// }
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    pub unsafe fn with_thread_local_proxy(&'static self) -> ThreadLocalBlinkGuard<A> {
+        match self.try_with_thread_local_proxy() {
+            Ok(guard) => guard,
+            Err(AllocError) => handle_alloc_error(Layout::new::<LocalBlinkAlloc<'static, A>>()),
+        }
+    }
+
+    /// Activates thread-local proxy of this allocator
+    /// like [`with_thread_local_proxy`](GlobalBlinkAlloc::with_thread_local_proxy).
+    ///
+    /// Returns `Err(AllocError)` if memory for the proxy
+    /// cannot be allocated.
+    ///
+    /// # Safety
+    ///
+    /// See [`with_thread_local_proxy`](GlobalBlinkAlloc::with_thread_local_proxy).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current thread has active proxy
+    /// of another [`GlobalBlinkAlloc`].
+    pub unsafe fn try_with_thread_local_proxy(
+        &'static self,
+    ) -> Result<ThreadLocalBlinkGuard<A>, AllocError> {
+        let owner = self as *const Self as *const ();
+        PROXY_SLOT.with(|slot| {
+            if slot.guards.get() > 0 {
+                assert!(
+                    slot.owner.get() == owner,
+                    "Thread-local proxy of another `GlobalBlinkAlloc` is active"
+                );
+                slot.guards.set(slot.guards.get() + 1);
+                return Ok(());
+            }
+
+            if slot.owner.get() != owner {
+                slot.release();
+
+                let layout = Layout::new::<LocalBlinkAlloc<'static, A>>();
+                let memory = (*self.state.get()).blink.inner().allocate(layout)?;
+
+                slot.memory.set(memory.as_ptr().cast());
+                slot.owner.set(owner);
+                slot.free.set(Some(free_proxy_slot::<A>));
+            }
+
+            let proxy = slot.memory.get().cast::<LocalBlinkAlloc<'static, A>>();
+            let resets = self.resets.load(Ordering::Relaxed);
+            if slot.live.get() && slot.resets.get() != resets {
+                // Chunks of the kept proxy were freed by reset.
+                (*proxy).forget_chunks();
+                core::ptr::drop_in_place(proxy);
+                slot.live.set(false);
+            }
+            if !slot.live.get() {
+                proxy.write(self.local());
+                slot.live.set(true);
+                slot.resets.set(resets);
+            }
+            slot.guards.set(1);
+            self.active_proxies.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        })?;
+
+        Ok(ThreadLocalBlinkGuard {
+            marker: PhantomData,
+        })
+    }
+
+    /// Returns active thread-local proxy of this allocator.
+    /// Proxies are used only in blink mode.
+    #[inline(always)]
+    unsafe fn thread_proxy(&self) -> Option<&LocalBlinkAlloc<'_, A>> {
+        if self.active_proxies.load(Ordering::Relaxed) == 0 || !(*self.state.get()).enabled {
+            return None;
+        }
+
+        let owner = self as *const Self as *const ();
+        PROXY_SLOT
+            .try_with(|slot| {
+                if slot.guards.get() == 0 || slot.owner.get() != owner {
+                    return None;
+                }
+                Some(slot.memory.get().cast::<LocalBlinkAlloc<'_, A>>())
+            })
+            .ok()
+            .flatten()
+            .map(|proxy| &*proxy)
+    }
+
+    #[inline(always)]
+    unsafe fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        match self.thread_proxy() {
+            Some(proxy) => proxy.allocate(layout),
+            None => (*self.state.get()).allocate(layout),
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        match self.thread_proxy() {
+            Some(proxy) => Allocator::allocate_zeroed(proxy, layout),
+            None => (*self.state.get()).allocate_zeroed(layout),
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let state = &*self.state.get();
+        if state.enabled && self.active_proxies.load(Ordering::Relaxed) != 0 {
+            if let Some(proxy) = self.thread_proxy() {
+                return proxy.resize(ptr, old_layout, new_layout);
+            }
+
+            // Memory may belong to a proxy chunk of another thread
+            // and must not be given back to the shared allocator.
+            if new_layout.size() <= old_layout.size() {
+                let slice = core::ptr::slice_from_raw_parts_mut(ptr.as_ptr(), old_layout.size());
+                return Ok(NonNull::new_unchecked(slice));
+            }
+            let new_ptr = state.blink.allocate(new_layout)?;
+            core::ptr::copy_nonoverlapping(
+                ptr.as_ptr(),
+                new_ptr.as_ptr().cast(),
+                old_layout.size(),
+            );
+            return Ok(new_ptr);
+        }
+        state.resize(ptr, old_layout, new_layout)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        let state = &*self.state.get();
+        if state.enabled && self.active_proxies.load(Ordering::Relaxed) != 0 {
+            // Memory may belong to a proxy chunk of another thread
+            // and must not be given back to the shared allocator.
+            if let Some(proxy) = self.thread_proxy() {
+                proxy.deallocate(ptr, layout.size());
+            }
+            return;
+        }
+        state.deallocate(ptr, layout)
+    }
 }
 
-unsafe impl<A> GlobalAlloc for GlobalBlinkAlloc<A>
+switch_std_default! {
+    /// Guard that keeps thread-local proxy of [`GlobalBlinkAlloc`] active.
+    ///
+    /// See [`GlobalBlinkAlloc::with_thread_local_proxy`].
+    pub struct ThreadLocalBlinkGuard<A: Allocator = +std::alloc::System> {
+        marker: PhantomData<*const GlobalBlinkAlloc<A>>,
+    }
+}
+
+impl<A> Drop for ThreadLocalBlinkGuard<A>
 where
     A: Allocator,
 {
     #[inline]
-    unsafe fn alloc(&self, layout: core::alloc::Layout) -> *mut u8 {
-        match (*self.state.get()).allocate(layout) {
-            Ok(ptr) => {
-                #[cfg(debug_assertions)]
-                if (*self.state.get()).enabled {
-                    self.allocations.fetch_add(1, Ordering::SeqCst);
+    fn drop(&mut self) {
+        PROXY_SLOT.with(|slot| {
+            let guards = slot.guards.get() - 1;
+            slot.guards.set(guards);
+            if guards == 0 {
+                // Safety: Proxy is initialized while guards are alive.
+                // Memory allocated from it is deallocated.
+                // Owner is alive while guards are alive.
+                unsafe {
+                    (*slot.memory.get().cast::<LocalBlinkAlloc<'_, A>>()).reset();
+                    let global = &*slot.owner.get().cast::<GlobalBlinkAlloc<A>>();
+                    global.active_proxies.fetch_sub(1, Ordering::Relaxed);
                 }
-                ptr.as_ptr().cast()
             }
-            Err(_) => null_mut(),
+        });
+    }
+}
+
+switch_std_default! {
+    /// Guard that keeps [`GlobalBlinkAlloc`] in blink mode.
+    /// Switches allocator back to direct mode on drop.
+    ///
+    /// See [`GlobalBlinkAlloc::enter_blink_mode`].
+    pub struct BlinkModeGuard<'a, A: Allocator = +std::alloc::System> {
+        global: &'a GlobalBlinkAlloc<A>,
+        marker: PhantomData<*const ()>,
+    }
+}
+
+impl<A> Drop for BlinkModeGuard<'_, A>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn drop(&mut self) {
+        // Safety: Guard is created in `enter_blink_mode`
+        // which requires memory allocated in blink mode
+        // to be deallocated before the guard is dropped.
+        unsafe { self.global.direct_mode() };
+
+        #[cfg(debug_assertions)]
+        self.global.mode_thread.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Returns value unique for each live thread.
+#[cfg(debug_assertions)]
+#[inline]
+fn thread_marker() -> usize {
+    std::thread_local! {
+        static MARKER: u8 = const { 0 };
+    }
+    MARKER.with(|marker| marker as *const u8 as usize)
+}
+
+type FreeProxySlot = unsafe fn(*const (), *mut (), Option<usize>);
+
+/// Thread-local slot for proxy activated by
+/// [`GlobalBlinkAlloc::with_thread_local_proxy`].
+struct ProxySlot {
+    /// Address of `GlobalBlinkAlloc` that owns the slot.
+    owner: Cell<*const ()>,
+    /// Memory for `LocalBlinkAlloc` of the owner.
+    memory: Cell<*mut ()>,
+    /// Deallocates slot memory.
+    free: Cell<Option<FreeProxySlot>>,
+    /// Number of live guards.
+    guards: Cell<usize>,
+    /// Whether proxy is initialized.
+    live: Cell<bool>,
+    /// Number of owner resets when proxy was created.
+    resets: Cell<usize>,
+}
+
+impl ProxySlot {
+    /// Drops proxy if initialized and deallocates slot memory if allocated.
+    #[inline]
+    fn release(&self) {
+        debug_assert_eq!(self.guards.get(), 0);
+        if let Some(free) = self.free.take() {
+            let live = match self.live.replace(false) {
+                true => Some(self.resets.get()),
+                false => None,
+            };
+            // Safety: Memory was allocated by the owner for this slot.
+            unsafe { free(self.owner.get(), self.memory.get(), live) };
         }
+        self.owner.set(core::ptr::null());
+    }
+}
+
+impl Drop for ProxySlot {
+    #[inline]
+    fn drop(&mut self) {
+        // Guards cannot outlive the thread, unless leaked.
+        if self.guards.get() == 0 {
+            self.release();
+        }
+    }
+}
+
+std::thread_local! {
+    static PROXY_SLOT: ProxySlot = const {
+        ProxySlot {
+            owner: Cell::new(core::ptr::null()),
+            memory: Cell::new(core::ptr::null_mut()),
+            free: Cell::new(None),
+            guards: Cell::new(0),
+            live: Cell::new(false),
+            resets: Cell::new(0),
+        }
+    };
+}
+
+/// Drops initialized proxy and returns proxy slot memory
+/// to the underlying allocator of the owner.
+///
+/// # Safety
+///
+/// `owner` must point to `GlobalBlinkAlloc<A>` that allocated `memory`.
+/// `live` must be `Some` with number of owner resets
+/// when proxy was created if proxy is initialized.
+unsafe fn free_proxy_slot<A: Allocator + 'static>(
+    owner: *const (),
+    memory: *mut (),
+    live: Option<usize>,
+) {
+    let global = &*owner.cast::<GlobalBlinkAlloc<A>>();
+    if let Some(resets) = live {
+        let proxy = memory.cast::<LocalBlinkAlloc<'static, A>>();
+        if global.resets.load(Ordering::Relaxed) != resets {
+            // Chunks of the proxy were freed by reset.
+            (*proxy).forget_chunks();
+        }
+        core::ptr::drop_in_place(proxy);
+    }
+    let layout = Layout::new::<LocalBlinkAlloc<'static, A>>();
+    (*global.state.get())
+        .blink
+        .inner()
+        .deallocate(NonNull::new_unchecked(memory.cast()), layout);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn check_blink_mode_guard_thread() {
+    static GLOBAL: GlobalBlinkAlloc = GlobalBlinkAlloc::new();
+    let layout = Layout::new::<[u8; 16]>();
+
+    unsafe {
+        let guard = GLOBAL.enter_blink_mode();
+        assert!((*GLOBAL.state.get()).enabled);
+
+        // Allocations from other threads are fine.
+        std::thread::spawn(move || {
+            let ptr = GLOBAL.alloc(layout);
+            assert!(!ptr.is_null());
+            GLOBAL.dealloc(ptr, layout);
+        })
+        .join()
+        .unwrap();
+
+        // Reset and mode switches are not.
+        let reset = std::thread::spawn(|| GLOBAL.reset()).join();
+        assert!(reset.is_err());
+        let direct = std::thread::spawn(|| GLOBAL.direct_mode()).join();
+        assert!(direct.is_err());
+        assert!((*GLOBAL.state.get()).enabled);
+
+        GLOBAL.reset();
+        drop(guard);
+        assert!(!(*GLOBAL.state.get()).enabled);
+
+        // Released by the guard.
+        assert_eq!(GLOBAL.mode_thread.load(Ordering::Relaxed), 0);
+        std::thread::spawn(|| drop(GLOBAL.enter_blink_mode()))
+            .join()
+            .unwrap();
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[test]
+fn check_thread_local_proxy() {
+    static GLOBAL: GlobalBlinkAlloc = GlobalBlinkAlloc::new();
+    let layout = Layout::new::<[u8; 16]>();
+
+    unsafe {
+        GLOBAL.blink_mode();
+        let blink = &(*GLOBAL.state.get()).blink;
+        blink.update_max_local_alloc(4096);
+
+        let guard = GLOBAL.with_thread_local_proxy();
+        let a = GLOBAL.alloc(layout);
+        let requested = blink.allocated_since_reset();
+
+        // Served by the proxy chunk.
+        let nested = GLOBAL.with_thread_local_proxy();
+        let b = GLOBAL.alloc(layout);
+        let b = GLOBAL.realloc(b, layout, 64);
+        assert_eq!(blink.allocated_since_reset(), requested);
+        drop(nested);
+
+        let c = GLOBAL.alloc_zeroed(layout);
+        assert_eq!(*c, 0);
+        assert_eq!(blink.allocated_since_reset(), requested);
+
+        GLOBAL.dealloc(a, layout);
+        GLOBAL.dealloc(b, Layout::new::<[u8; 64]>());
+        GLOBAL.dealloc(c, layout);
+        drop(guard);
+
+        // Served by the shared allocator.
+        let d = GLOBAL.alloc(layout);
+        assert_eq!(blink.allocated_since_reset(), requested + 16);
+        GLOBAL.dealloc(d, layout);
+
+        GLOBAL.direct_mode();
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[test]
+fn check_thread_local_proxy_reuse() {
+    static GLOBAL: GlobalBlinkAlloc = GlobalBlinkAlloc::new();
+    let layout = Layout::new::<[u8; 16]>();
+
+    unsafe {
+        GLOBAL.blink_mode();
+
+        let guard = GLOBAL.with_thread_local_proxy();
+        let a = GLOBAL.alloc(layout);
+        GLOBAL.dealloc(a, layout);
+        drop(guard);
+
+        // Proxy is reset and kept for the next guard.
+        assert!(PROXY_SLOT.with(|slot| slot.live.get()));
+        let guard = GLOBAL.with_thread_local_proxy();
+        let b = GLOBAL.alloc(layout);
+        assert_eq!(a, b);
+        GLOBAL.dealloc(b, layout);
+        drop(guard);
+
+        // Proxy kept over reset is recreated.
+        GLOBAL.reset();
+        let guard = GLOBAL.with_thread_local_proxy();
+        let c = GLOBAL.alloc(layout);
+        c.write_bytes(1, 16);
+        GLOBAL.dealloc(c, layout);
+        drop(guard);
+
+        GLOBAL.direct_mode();
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+#[test]
+fn check_thread_local_proxy_cross_thread_dealloc() {
+    use core::sync::atomic::AtomicPtr;
+    use std::sync::Barrier;
+
+    static GLOBAL: GlobalBlinkAlloc = GlobalBlinkAlloc::new();
+    let layout = Layout::new::<[u8; 16]>();
+
+    unsafe {
+        GLOBAL.blink_mode();
+
+        let barrier = Barrier::new(2);
+        let sent = AtomicPtr::new(null_mut());
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                let guard = GLOBAL.with_thread_local_proxy();
+                let ptr = GLOBAL.alloc(layout);
+                ptr.write_bytes(7, 16);
+                sent.store(ptr, Ordering::Relaxed);
+                barrier.wait();
+                barrier.wait();
+
+                // Proxy memory is not reused while the guard is alive.
+                let other = GLOBAL.alloc(layout);
+                assert_ne!(other, ptr);
+                GLOBAL.dealloc(other, layout);
+                drop(guard);
+            });
+
+            // Deallocated on thread without proxy while proxy chunk is in use.
+            barrier.wait();
+            GLOBAL.dealloc(sent.load(Ordering::Relaxed), layout);
+            barrier.wait();
+        });
+
+        // No guards are alive, memory goes back to the shared allocator.
+        let a = GLOBAL.alloc(layout);
+        GLOBAL.dealloc(a, layout);
+        let b = GLOBAL.alloc(layout);
+        assert_eq!(a, b);
+        GLOBAL.dealloc(b, layout);
+
+        GLOBAL.direct_mode();
+    }
+}
+
+unsafe impl<A> GlobalAlloc for GlobalBlinkAlloc<A>
+where
+    A: Allocator,
+{
+    #[inline]
+    unsafe fn alloc(&self, layout: core::alloc::Layout) -> *mut u8 {
+        let ptr = match self.allocate(layout) {
+            Ok(ptr) => ptr.as_ptr().cast(),
+            Err(_) => self.fallback_alloc(|fallback| fallback.alloc(layout)),
+        };
+
+        #[cfg(debug_assertions)]
+        if !ptr.is_null() && (*self.state.get()).enabled {
+            self.allocations.fetch_add(1, Ordering::SeqCst);
+        }
+        ptr
     }
 
     #[inline]
     unsafe fn dealloc(&self, ptr: *mut u8, layout: core::alloc::Layout) {
-        let ptr = NonNull::new_unchecked(ptr);
-        (*self.state.get()).deallocate(ptr, layout);
+        match self.fallback_for(ptr) {
+            Some(fallback) => {
+                fallback.dealloc(ptr, layout);
+                self.fallback_live.fetch_sub(1, Ordering::Relaxed);
+            }
+            None => self.deallocate(NonNull::new_unchecked(ptr), layout),
+        }
+
         #[cfg(debug_assertions)]
         {
             if (*self.state.get()).enabled {
@@ -372,16 +1082,16 @@ where
 
     #[inline]
     unsafe fn alloc_zeroed(&self, layout: core::alloc::Layout) -> *mut u8 {
-        match (*self.state.get()).allocate_zeroed(layout) {
-            Ok(ptr) => {
-                #[cfg(debug_assertions)]
-                if (*self.state.get()).enabled {
-                    self.allocations.fetch_add(1, Ordering::SeqCst);
-                }
-                ptr.as_ptr().cast()
-            }
-            Err(_) => null_mut(),
+        let ptr = match self.allocate_zeroed(layout) {
+            Ok(ptr) => ptr.as_ptr().cast(),
+            Err(_) => self.fallback_alloc(|fallback| fallback.alloc_zeroed(layout)),
+        };
+
+        #[cfg(debug_assertions)]
+        if !ptr.is_null() && (*self.state.get()).enabled {
+            self.allocations.fetch_add(1, Ordering::SeqCst);
         }
+        ptr
     }
 
     #[inline]
@@ -395,14 +1105,76 @@ where
             return null_mut();
         };
 
+        if let Some(fallback) = self.fallback_for(ptr) {
+            // Fallback allocation stays live on both success and failure.
+            return fallback.realloc(ptr, layout, new_size);
+        }
+
         let result = match NonNull::new(ptr) {
-            None => (*self.state.get()).allocate(new_layout),
-            Some(ptr) => (*self.state.get()).resize(ptr, layout, new_layout),
+            None => self.allocate(new_layout),
+            Some(ptr) => self.resize(ptr, layout, new_layout),
         };
 
         match result {
             Ok(ptr) => ptr.as_ptr().cast(),
-            Err(_) => null_mut(),
+            Err(_) => {
+                let new_ptr = self.fallback_alloc(|fallback| fallback.alloc(new_layout));
+                if !new_ptr.is_null() && !ptr.is_null() {
+                    // Old allocation stays valid on failed resize.
+                    core::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+                    self.deallocate(NonNull::new_unchecked(ptr), layout);
+                }
+                new_ptr
+            }
         }
     }
 }
+
+/// Defines function that provides access to thread-local
+/// [`LocalBlinkAlloc`] proxy of a static [`GlobalBlinkAlloc`].
+///
+/// Proxy is created lazily on first access from each thread
+/// and dropped on thread exit, returning its memory to the shared allocator.
+///
+/// Default underlying allocator is [`std::alloc::System`].
+/// Specify it after the static path if different:
+/// `thread_local_proxy!(fn with_proxy(GLOBAL: MyAllocator))`.
+///
+/// Static allocator must not be reset while threads that used the proxy are alive.
+///
+/// # Example
+///
+/// ```
+/// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+/// # use blink_alloc::{thread_local_proxy, GlobalBlinkAlloc};
+/// # use allocator_api2::vec::Vec;
+/// static GLOBAL: GlobalBlinkAlloc = GlobalBlinkAlloc::new();
+///
+/// thread_local_proxy!(fn with_proxy(GLOBAL));
+///
+/// fn main() {
+///     std::thread::spawn(|| {
+///         with_proxy(|proxy| {
+///             let mut vec = Vec::new_in(proxy);
+///             vec.extend(0..10);
+///             assert_eq!(vec.iter().sum::<i32>(), 45);
+///         });
+///     })
+///     .join()
+///     .unwrap();
+/// }
+/// ```
+#[macro_export]
+macro_rules! thread_local_proxy {
+    ($vis:vis fn $name:ident($global:path)) => {
+        $crate::thread_local_proxy!($vis fn $name($global: ::std::alloc::System));
+    };
+    ($vis:vis fn $name:ident($global:path: $alloc:ty)) => {
+        $vis fn $name<R>(f: impl FnOnce(&$crate::LocalBlinkAlloc<'static, $alloc>) -> R) -> R {
+            ::std::thread_local! {
+                static PROXY: $crate::LocalBlinkAlloc<'static, $alloc> = $global.local();
+            }
+            PROXY.with(f)
+        }
+    };
+}
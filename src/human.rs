@@ -0,0 +1,31 @@
+//! This module provides human-readable formatting of byte sizes.
+
+use core::fmt;
+
+/// Formats number of bytes with `KB`, `MB`, `GB` or `TB` suffix
+/// and one decimal digit, using multiples of 1024.
+/// Sizes below 1 KB are formatted as is with `B` suffix.
+#[derive(Clone, Copy)]
+pub(crate) struct HumanBytes(pub usize);
+
+impl fmt::Display for HumanBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+        let bytes = self.0 as u128;
+        if bytes < 1024 {
+            return write!(f, "{} B", bytes);
+        }
+
+        let mut unit = 1024u128;
+        let mut idx = 0;
+        while idx + 1 < UNITS.len() && bytes >= unit * 1024 {
+            unit *= 1024;
+            idx += 1;
+        }
+
+        // Round to one decimal digit.
+        let tenths = (bytes * 10 + unit / 2) / unit;
+        write!(f, "{}.{} {}", tenths / 10, tenths % 10, UNITS[idx])
+    }
+}
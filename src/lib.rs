@@ -50,9 +50,14 @@ macro_rules! switch_std_default {
 mod api;
 mod arena;
 mod blink;
+mod branded;
 mod drop_list;
 mod global;
+mod human;
 mod local;
+mod refcell;
+
+pub mod prelude;
 
 #[cfg(feature = "sync")]
 mod sync;
@@ -60,6 +65,18 @@ mod sync;
 #[cfg(all(feature = "sync", feature = "alloc"))]
 mod cache;
 
+#[cfg(feature = "metrics")]
+mod metrics;
+
+#[cfg(feature = "ffi")]
+mod ffi;
+
+#[cfg(feature = "test-util")]
+mod counting;
+
+#[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+mod alloc_sites;
+
 #[cfg(test)]
 mod tests;
 
@@ -68,24 +85,82 @@ mod oom;
 
 pub use self::{
     api::BlinkAllocator,
-    blink::{Blink, Emplace, IteratorExt, SendBlink},
+    arena::{BlinkStats, ChunkInfo, ChunkIter},
+    blink::{Blink, BlinkScope, BlinkWriter, Emplace, ExactFill, IteratorExt, SendBlink},
+    branded::{Branded, BrandedBlink},
     global::local::UnsafeGlobalBlinkAlloc,
     local::BlinkAlloc,
+    refcell::RefCellBlinkAlloc,
 };
 
 #[cfg(feature = "sync")]
-pub use self::sync::{LocalBlinkAlloc, SyncBlinkAlloc};
+pub use self::sync::{EpochGuard, LocalBlinkAlloc, SyncBlinkAlloc};
+
+#[cfg(all(feature = "async", not(no_global_oom_handling)))]
+pub use self::blink::EmplaceAsyncFuture;
+
+#[cfg(feature = "async")]
+pub use self::blink::TryEmplaceAsyncFuture;
+
+#[cfg(feature = "ffi")]
+pub use self::ffi::blink_alloc_alloc_raw;
+
+#[cfg(feature = "test-util")]
+pub use self::counting::CountingBlinkAlloc;
 
 #[cfg(feature = "sync")]
-pub use self::global::sync::GlobalBlinkAlloc;
+pub use self::global::{
+    double::DoubleBufferedGlobalAlloc,
+    sync::{BlinkModeGuard, GlobalBlinkAlloc, ThreadLocalBlinkGuard},
+};
+
+#[cfg(all(feature = "sync", feature = "alloc"))]
+pub use self::cache::{BlinkAllocCache, BlinkAllocCacheIter, TieredBlinkAllocCache};
+
+/// [`Blink`] adaptor over [`BlinkAlloc`] that uses global allocator.
+#[cfg(feature = "alloc")]
+pub type FrameBlink = Blink<BlinkAlloc<allocator_api2::alloc::Global>>;
+
+/// [`Blink`] adaptor over [`SyncBlinkAlloc`] that uses global allocator.
+#[cfg(all(feature = "sync", feature = "alloc"))]
+pub type SyncFrameBlink = Blink<SyncBlinkAlloc<allocator_api2::alloc::Global>>;
 
+/// [`Blink`] adaptor over [`LocalBlinkAlloc`] proxy
+/// of [`SyncBlinkAlloc`] that uses global allocator.
 #[cfg(all(feature = "sync", feature = "alloc"))]
-pub use self::cache::BlinkAllocCache;
+pub type LocalFrameBlink<'a> = Blink<LocalBlinkAlloc<'a, allocator_api2::alloc::Global>>;
+
+/// Cache line size of the target platform.
+///
+/// Used as alignment of [`BlinkAlloc::allocate_cache_aligned`]
+/// to keep allocations from sharing cache lines.
+#[cfg(any(target_arch = "aarch64", target_arch = "powerpc64"))]
+pub const CACHE_LINE_SIZE: usize = 128;
+
+/// Cache line size of the target platform.
+///
+/// Used as alignment of [`BlinkAlloc::allocate_cache_aligned`]
+/// to keep allocations from sharing cache lines.
+#[cfg(target_arch = "s390x")]
+pub const CACHE_LINE_SIZE: usize = 256;
+
+/// Cache line size of the target platform.
+///
+/// Used as alignment of [`BlinkAlloc::allocate_cache_aligned`]
+/// to keep allocations from sharing cache lines.
+#[cfg(not(any(
+    target_arch = "aarch64",
+    target_arch = "powerpc64",
+    target_arch = "s390x"
+)))]
+pub const CACHE_LINE_SIZE: usize = 64;
 
+#[cfg(not(no_global_oom_handling))]
 pub(crate) trait ResultExt<T> {
     fn safe_ok(self) -> T;
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<T> ResultExt<T> for Result<T, core::convert::Infallible> {
     #[inline]
     fn safe_ok(self) -> T {
@@ -106,6 +181,42 @@ unsafe fn in_place<'a, T, I>(ptr: *mut T, init: I, f: impl FnOnce(I) -> T) -> &'
 #[cold]
 fn cold() {}
 
+/// Returns address of the pointer.
+///
+/// With `"strict-provenance"` feature uses [`pointer::addr`]
+/// which doesn't expose pointer's provenance.
+/// Requires Rust 1.84 or newer.
+#[cfg(feature = "strict-provenance")]
+#[inline(always)]
+fn addr<T>(ptr: *const T) -> usize {
+    ptr.addr()
+}
+
+/// Returns address of the pointer.
+#[cfg(not(feature = "strict-provenance"))]
+#[inline(always)]
+fn addr<T>(ptr: *const T) -> usize {
+    ptr as usize
+}
+
+/// Returns pointer with given address and without provenance.
+///
+/// With `"strict-provenance"` feature uses [`core::ptr::without_provenance_mut`].
+/// Requires Rust 1.84 or newer.
+#[cfg(feature = "strict-provenance")]
+#[inline(always)]
+fn without_provenance_mut<T>(addr: usize) -> *mut T {
+    core::ptr::without_provenance_mut(addr)
+}
+
+/// Returns pointer with given address and without provenance.
+#[cfg(not(feature = "strict-provenance"))]
+#[inline(always)]
+fn without_provenance_mut<T>(addr: usize) -> *mut T {
+    // Offsetting null pointer does not create provenance.
+    core::ptr::null_mut::<u8>().wrapping_add(addr).cast()
+}
+
 // #[cfg(debug_assertions)]
 // #[track_caller]
 // unsafe fn unreachable_unchecked() -> ! {
@@ -1,14 +1,37 @@
 //! This module provides multi-threaded blink allocator\
 //! with sync resets.
 
-use core::{alloc::Layout, mem::ManuallyDrop, ptr::NonNull};
+use core::{
+    alloc::Layout,
+    cell::Cell,
+    fmt,
+    mem::{size_of, ManuallyDrop, MaybeUninit},
+    ptr::NonNull,
+    sync::atomic::{compiler_fence, Ordering},
+};
 
 use allocator_api2::alloc::{AllocError, Allocator};
 
 #[cfg(feature = "alloc")]
 use allocator_api2::alloc::Global;
 
-use crate::{api::BlinkAllocator, arena::ArenaLocal};
+#[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+use crate::alloc_sites::AllocSites;
+
+use crate::{
+    api::BlinkAllocator,
+    arena::{self, ArenaLocal, BlinkStats, ChunkIter},
+    branded::BrandedBlink,
+    cold,
+    drop_list::{DropCheckpoint, DropItem, DropList},
+    human::HumanBytes,
+};
+
+#[cfg(not(no_global_oom_handling))]
+use crate::{
+    oom::{handle_alloc_error, size_overflow},
+    ResultExt,
+};
 
 switch_alloc_default! {
     /// Single-threaded blink allocator.
@@ -84,26 +107,255 @@ switch_alloc_default! {
     /// # }
     /// # #[cfg(not(feature = "alloc"))] fn main() {}
     /// ```
+    ///
+    /// # Example with `Box`
+    ///
+    /// Dropping a box drops the value as usual.
+    /// Its memory is reclaimed immediately only if it was the last allocation,
+    /// otherwise it is reclaimed on reset.
+    /// Boxes borrow the allocator, so it can't be reset while they are alive.
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # #[cfg(feature = "alloc")]
+    /// # fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use allocator_api2::boxed::Box;
+    /// let mut blink = BlinkAlloc::new();
+    /// let boxed = Box::new_in(String::from("hello"), &blink);
+    /// assert_eq!(*boxed, "hello");
+    /// drop(boxed);
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
     pub struct BlinkAlloc<A: Allocator = +Global> {
         arena: ArenaLocal,
         allocator: A,
+        high_water_mark: Cell<usize>,
+        allocated: Cell<usize>,
+        finalizers: Finalizers,
+        huge: HugeList,
+        huge_alloc_threshold: usize,
+        min_align: usize,
+        auto_reset_period: usize,
+        alloc_count: Cell<usize>,
+        #[cfg(feature = "oom-handler")]
+        oom_handler: fn(Layout),
+        #[cfg(feature = "metrics")]
+        gauges: crate::metrics::Gauges,
+        #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+        sites: AllocSites,
+    }
+}
+
+/// Finalizers registered with [`BlinkAlloc::on_reset`].
+struct Finalizers(DropList);
+
+// Safety: Only `Send` finalizers are added to the list.
+unsafe impl Send for Finalizers {}
+
+/// Calls wrapped closure when dropped.
+struct Finalizer<F: FnOnce()>(ManuallyDrop<F>);
+
+impl<F> Drop for Finalizer<F>
+where
+    F: FnOnce(),
+{
+    #[inline(always)]
+    fn drop(&mut self) {
+        // Safety: Closure is taken only once, here.
+        let f = unsafe { ManuallyDrop::take(&mut self.0) };
+        f();
+    }
+}
+
+/// Zeroes `len` bytes at `ptr` with volatile writes,
+/// so compiler cannot elide them even if memory is freed right after.
+///
+/// # Safety
+///
+/// `ptr` must be valid for writes of `len` bytes.
+#[inline]
+unsafe fn zero_volatile(ptr: *mut u8, len: usize) {
+    for i in 0..len {
+        // Safety: `ptr + i` is within `len` bytes valid for writes.
+        unsafe { ptr.add(i).write_volatile(0) };
+    }
+}
+
+/// Header placed before allocation served directly by underlying allocator.
+struct HugeHeader {
+    prev: Option<NonNull<HugeHeader>>,
+    layout: Layout,
+}
+
+/// List of allocations served directly by underlying allocator.
+struct HugeList(Cell<Option<NonNull<HugeHeader>>>);
+
+// Safety: List owns the allocations and is not shared.
+unsafe impl Send for HugeList {}
+
+impl HugeList {
+    #[inline(always)]
+    const fn new() -> Self {
+        HugeList(Cell::new(None))
+    }
+
+    #[inline]
+    fn alloc(
+        &self,
+        layout: Layout,
+        allocator: impl Allocator,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let (block, offset) = Layout::new::<HugeHeader>()
+            .extend(layout)
+            .map_err(|_| AllocError)?;
+        let block = block.pad_to_align();
+
+        let slice = allocator.allocate(block)?;
+        let header = slice.cast::<HugeHeader>();
+
+        // Safety: `slice` is allocated with `block` layout
+        // that fits header at the start and `layout` at `offset`.
+        unsafe {
+            header.as_ptr().write(HugeHeader {
+                prev: self.0.get(),
+                layout: block,
+            });
+            self.0.set(Some(header));
+
+            let ptr = slice.cast::<u8>().as_ptr().add(offset);
+            let slice = core::ptr::slice_from_raw_parts_mut(ptr, block.size() - offset);
+            Ok(NonNull::new_unchecked(slice))
+        }
+    }
+
+    /// Calls `f` with memory of each allocation in the list
+    /// following the header.
+    #[inline]
+    fn for_each(&self, mut f: impl FnMut(*mut u8, usize)) {
+        let mut next = self.0.get();
+        while let Some(header) = next {
+            // Safety: `header` points to live allocation of `layout` bytes.
+            unsafe {
+                let HugeHeader { prev, layout } = header.as_ptr().read();
+                let data = header.as_ptr().add(1).cast::<u8>();
+                f(data, layout.size() - size_of::<HugeHeader>());
+                next = prev;
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        self.0.get().is_none()
+    }
+
+    /// Checks if `ptr` points into memory of an allocation in the list.
+    #[inline]
+    fn owns(&self, ptr: *const u8) -> bool {
+        let mut found = false;
+        self.for_each(|data, len| {
+            found |= data.cast_const() <= ptr && ptr < data.wrapping_add(len).cast_const();
+        });
+        found
+    }
+
+    /// Zeroes memory of all allocations in the list.
+    #[inline]
+    fn zero(&self) {
+        // Safety: Memory following the header belongs to the allocation.
+        self.for_each(|data, len| unsafe { zero_volatile(data, len) });
+    }
+
+    /// Deallocates all allocations in the list.
+    ///
+    /// # Safety
+    ///
+    /// Same allocator must be used for all allocations in the list.
+    /// Allocations must not be used after this call.
+    #[inline]
+    unsafe fn free(&self, allocator: impl Allocator) {
+        let mut next = self.0.take();
+        while let Some(header) = next {
+            // Safety: `header` points to live allocation of `layout` bytes.
+            unsafe {
+                let HugeHeader { prev, layout } = header.as_ptr().read();
+                allocator.deallocate(header.cast(), layout);
+                next = prev;
+            }
+        }
+    }
+
+    /// Forgets all allocations in the list.
+    #[inline(always)]
+    fn leak(&self) {
+        self.0.set(None);
     }
 }
 
+/// Position of [`BlinkAlloc`] to roll back to.
+#[derive(Clone, Copy)]
+pub(crate) struct Checkpoint {
+    arena: arena::Checkpoint,
+    finalizers: DropCheckpoint,
+    #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+    sites: usize,
+}
+
 impl<A> Drop for BlinkAlloc<A>
 where
     A: Allocator,
 {
     #[inline]
     fn drop(&mut self) {
+        self.finalizers.0.reset();
+
         // Safety:
         // Same instance is used for all allocations and resets.
         unsafe {
+            self.huge.free(&self.allocator);
             self.arena.reset(false, &self.allocator);
         }
     }
 }
 
+/// Shows number of chunks, used bytes and capacity of chunks
+/// and minimum size of new chunks.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] fn main() {
+/// # use blink_alloc::BlinkAlloc;
+/// # use std::alloc::Layout;
+/// let blink = BlinkAlloc::with_chunk_size(4096);
+/// assert_eq!(blink.to_string(), "BlinkAlloc(0 chunks, 0 B used of 0 B capacity, min_chunk=4.0 KB)");
+///
+/// blink.allocate(Layout::new::<[u8; 2048]>()).unwrap();
+/// println!("{}", blink);
+/// # }
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// ```
+impl<A> fmt::Display for BlinkAlloc<A>
+where
+    A: Allocator,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stats = self.stats();
+        write!(
+            f,
+            "BlinkAlloc({} chunk{}, {} used of {} capacity, min_chunk={})",
+            stats.chunks,
+            if stats.chunks == 1 { "" } else { "s" },
+            HumanBytes(stats.used),
+            HumanBytes(stats.capacity),
+            HumanBytes(self.arena.min_chunk_size()),
+        )
+    }
+}
+
 impl<A> Default for BlinkAlloc<A>
 where
     A: Allocator + Default,
@@ -128,12 +380,34 @@ impl BlinkAlloc<Global> {
     /// Creates new blink allocator that uses global allocator
     /// to allocate memory chunks.
     /// With this method you can specify initial chunk size.
+    /// Sizes smaller than 64 bytes are bumped to 64 bytes
+    /// to avoid allocating many tiny chunks.
     ///
     /// See [`BlinkAlloc::new_in`] for using custom allocator.
     #[inline]
     pub const fn with_chunk_size(chunk_size: usize) -> Self {
         BlinkAlloc::with_chunk_size_in(chunk_size, Global)
     }
+
+    /// Creates new blink allocator that uses global allocator
+    /// to allocate memory chunks.
+    /// Allocates first chunk of at least `size` bytes immediately,
+    /// so first allocations do not pay for chunk allocation.
+    ///
+    /// See [`BlinkAlloc::with_warmup_in`] for using custom allocator.
+    #[inline]
+    pub fn with_warmup_size(size: usize) -> Result<Self, AllocError> {
+        BlinkAlloc::with_warmup_in(size, Global)
+    }
+
+    /// Creates new blink allocator that uses `bytes` as the first chunk
+    /// and global allocator for chunks allocated when it overflows.
+    ///
+    /// See [`BlinkAlloc::with_initial_chunk_in`] for using custom allocator.
+    #[inline]
+    pub fn with_initial_chunk(bytes: &'static mut [MaybeUninit<u8>]) -> Self {
+        BlinkAlloc::with_initial_chunk_in(bytes, Global)
+    }
 }
 
 impl<A> BlinkAlloc<A>
@@ -149,6 +423,20 @@ where
         BlinkAlloc {
             arena: ArenaLocal::new(),
             allocator,
+            high_water_mark: Cell::new(0),
+            allocated: Cell::new(0),
+            finalizers: Finalizers(DropList::new()),
+            huge: HugeList::new(),
+            huge_alloc_threshold: usize::MAX,
+            min_align: 1,
+            auto_reset_period: 0,
+            alloc_count: Cell::new(0),
+            #[cfg(feature = "oom-handler")]
+            oom_handler: |_| {},
+            #[cfg(feature = "metrics")]
+            gauges: crate::metrics::Gauges::new(),
+            #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+            sites: AllocSites::new(),
         }
     }
 
@@ -161,6 +449,8 @@ where
     /// Creates new blink allocator that uses global allocator
     /// to allocate memory chunks.
     /// With this method you can specify initial chunk size.
+    /// Sizes smaller than 64 bytes are bumped to 64 bytes
+    /// to avoid allocating many tiny chunks.
     ///
     /// See [`BlinkAlloc::new_in`] for using custom allocator.
     #[inline]
@@ -168,148 +458,1688 @@ where
         BlinkAlloc {
             arena: ArenaLocal::with_chunk_size(chunk_size),
             allocator,
+            high_water_mark: Cell::new(0),
+            allocated: Cell::new(0),
+            finalizers: Finalizers(DropList::new()),
+            huge: HugeList::new(),
+            huge_alloc_threshold: usize::MAX,
+            min_align: 1,
+            auto_reset_period: 0,
+            alloc_count: Cell::new(0),
+            #[cfg(feature = "oom-handler")]
+            oom_handler: |_| {},
+            #[cfg(feature = "metrics")]
+            gauges: crate::metrics::Gauges::new(),
+            #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+            sites: AllocSites::new(),
         }
     }
 
-    /// Allocates memory with specified layout from this allocator.
-    /// If needed it will allocate new chunk using underlying allocator.
-    /// If chunk allocation fails, it will return `Err`.
-    #[inline(always)]
-    pub fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
-        // Safety:
-        // Same instance is used for all allocations and resets.
-        if let Some(ptr) = unsafe { self.arena.alloc_fast(layout) } {
-            return Ok(ptr);
-        }
-        unsafe { self.arena.alloc_slow(layout, &self.allocator) }
-    }
-
-    /// Resizes memory allocation.
-    /// Potentially happens in-place.
+    /// Creates new blink allocator that uses provided allocator
+    /// to allocate memory chunks.
+    /// Allocates first chunk of at least `size` bytes immediately,
+    /// so first allocations do not pay for chunk allocation.
     ///
-    /// # Safety
+    /// The chunk is kept on [`reset`](BlinkAlloc::reset)
+    /// like any last chunk.
+    /// Minimum size of following chunks is not affected.
+    /// Returns `Err` if chunk allocation fails.
     ///
-    /// `ptr` must be a pointer previously returned by [`allocate`](BlinkAlloc::allocate).
-    /// `old_size` must be in range `layout.size()..=slice.len()`
-    /// where `layout` is the layout used in the call to [`allocate`](BlinkAlloc::allocate).
-    /// and `slice` is the slice pointer returned by [`allocate`](BlinkAlloc::allocate).
+    /// # Example
     ///
-    /// On success, the old pointer is invalidated and the new pointer is returned.
-    /// On error old allocation is still valid.
-    #[inline(always)]
-    pub unsafe fn resize(
-        &self,
-        ptr: NonNull<u8>,
-        old_layout: Layout,
-        new_layout: Layout,
-    ) -> Result<NonNull<[u8]>, AllocError> {
-        if let Some(ptr) = unsafe { self.arena.resize_fast(ptr, old_layout, new_layout) } {
-            return Ok(ptr);
-        }
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use allocator_api2::alloc::Global;
+    /// # use std::alloc::Layout;
+    /// let blink = BlinkAlloc::with_warmup_in(4096, Global).unwrap();
+    /// assert_eq!(blink.chunks().count(), 1);
+    /// assert_eq!(blink.used_bytes(), 0);
+    ///
+    /// assert!(blink.try_allocate_in_current_chunk(Layout::new::<[u8; 4096]>()).is_some());
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn with_warmup_in(size: usize, allocator: A) -> Result<Self, AllocError> {
+        let mut blink = BlinkAlloc::new_in(allocator);
+        let layout = Layout::from_size_align(size, 1).map_err(|_| AllocError)?;
 
         // Safety:
         // Same instance is used for all allocations and resets.
-        // `ptr` was allocated by this allocator.
+        // No memory was handed out yet.
         unsafe {
-            self.arena
-                .resize_slow(ptr, old_layout, new_layout, &self.allocator)
+            blink.arena.alloc_slow(layout, &blink.allocator)?;
+            blink.arena.reset(true, &blink.allocator);
         }
+        #[cfg(feature = "metrics")]
+        blink.gauges.set(blink.stats());
+        Ok(blink)
     }
 
-    /// Deallocates memory previously allocated from this allocator.
+    /// Creates new blink allocator that uses `bytes` as the first chunk
+    /// and provided allocator for chunks allocated when it overflows.
     ///
-    /// This call may not actually free memory.
-    /// All memory is guaranteed to be freed on [`reset`](BlinkAlloc::reset) call.
+    /// Useful in embedded systems where static buffer,
+    /// e.g. in BSS or dedicated linker section, serves as the arena.
+    /// Memory must be writable, hence it is taken as
+    /// exclusive reference to uninitialized bytes.
     ///
-    /// # Safety
+    /// The buffer is never deallocated.
+    /// Like any chunk it is kept on [`reset`](BlinkAlloc::reset)
+    /// only while it is the last one, so once arena overflows
+    /// following resets keep the newer chunk instead.
+    /// Buffer too small to fit chunk header is ignored.
     ///
-    /// `ptr` must be a pointer previously returned by [`allocate`](BlinkAlloc::allocate).
-    /// `size` must be in range `layout.size()..=slice.len()`
-    /// where `layout` is the layout used in the call to [`allocate`](BlinkAlloc::allocate).
-    /// and `slice` is the slice pointer returned by [`allocate`](BlinkAlloc::allocate).
-    #[inline(always)]
-    pub unsafe fn deallocate(&self, ptr: NonNull<u8>, size: usize) {
-        // Safety:
-        // `ptr` was allocated by this allocator.
-        unsafe {
-            self.arena.dealloc(ptr, size);
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use allocator_api2::alloc::Global;
+    /// # use std::{alloc::Layout, mem::MaybeUninit};
+    /// static mut ARENA: [MaybeUninit<u8>; 4096] = [MaybeUninit::uninit(); 4096];
+    ///
+    /// // Safety: `ARENA` is not accessed anywhere else.
+    /// let bytes = unsafe { &mut *std::ptr::addr_of_mut!(ARENA) };
+    /// let blink = BlinkAlloc::with_initial_chunk_in(bytes, Global);
+    /// assert_eq!(blink.chunks().count(), 1);
+    ///
+    /// blink.allocate(Layout::new::<[u8; 1024]>()).unwrap();
+    /// assert_eq!(blink.chunks().count(), 1);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn with_initial_chunk_in(bytes: &'static mut [MaybeUninit<u8>], allocator: A) -> Self {
+        let mut blink = BlinkAlloc::new_in(allocator);
+        blink.arena.set_borrowed_chunk(bytes);
+        #[cfg(feature = "metrics")]
+        blink.gauges.set(blink.stats());
+        blink
+    }
+
+    /// Creates new blink allocator that uses provided allocator
+    /// to allocate memory chunks.
+    /// Allocates first chunk of at least `size` bytes immediately.
+    ///
+    /// See [`BlinkAlloc::with_warmup_in`] for fallible version.
+    ///
+    /// # Panics
+    ///
+    /// Diverges if chunk allocation fails.
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn with_warmup_in_panic(size: usize, allocator: A) -> Self {
+        let layout = Layout::from_size_align(size, 1)
+            .map_err(|_| size_overflow())
+            .safe_ok();
+        match BlinkAlloc::with_warmup_in(size, allocator) {
+            Ok(blink) => blink,
+            Err(AllocError) => handle_alloc_error(layout),
         }
     }
 
-    /// Resets this allocator, deallocating all chunks except the last one.
-    /// Last chunk will be reused.
-    /// With steady memory usage after few iterations
-    /// one chunk should be sufficient for all allocations between resets.
+    /// Sets size threshold above which allocations are served
+    /// directly by the underlying allocator instead of chunks.
+    ///
+    /// Such allocations are not linked into chunk list and are
+    /// deallocated on every reset, even when the last chunk is kept.
+    /// This prevents single giant transient allocation
+    /// from being retained as reused chunk.
+    ///
+    /// Allocations above the threshold are not accounted in
+    /// [`stats`](BlinkAlloc::stats), [`chunks`](BlinkAlloc::chunks)
+    /// and [`used_bytes`](BlinkAlloc::used_bytes).
+    /// Rollback to a [`Blink`](crate::Blink) checkpoint does not free them,
+    /// they live until the next reset.
+    ///
+    /// By default there is no threshold.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let mut blink = BlinkAlloc::new().with_huge_alloc_threshold(4096);
+    /// blink.allocate(Layout::new::<[u8; 65536]>()).unwrap();
+    /// assert_eq!(blink.chunks().count(), 0);
+    ///
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
     #[inline(always)]
-    pub fn reset(&mut self) {
-        // Safety:
-        // Same instance is used for all allocations and resets.
-        unsafe {
-            self.arena.reset(true, &self.allocator);
-        }
+    pub const fn with_huge_alloc_threshold(mut self, threshold: usize) -> Self {
+        self.huge_alloc_threshold = threshold;
+        self
     }
 
-    /// Resets this allocator, deallocating all chunks.
+    /// Returns size threshold above which allocations are served
+    /// directly by the underlying allocator.
+    ///
+    /// See [`with_huge_alloc_threshold`](BlinkAlloc::with_huge_alloc_threshold).
     #[inline(always)]
-    pub fn reset_final(&mut self) {
-        // Safety:
-        // Same instance is used for all allocations and resets.
-        unsafe {
-            self.arena.reset(false, &self.allocator);
-        }
+    pub const fn huge_alloc_threshold(&self) -> usize {
+        self.huge_alloc_threshold
     }
 
-    /// Resets this allocator, deallocating all chunks except the last one.
-    /// Last chunk will be reused.
-    /// With steady memory usage after few iterations
-    /// one chunk should be sufficient for all allocations between resets.
+    /// Sets minimum alignment of all allocations made from this allocator.
     ///
-    /// # Safety
+    /// Layouts with smaller alignment are aligned to `min_align`.
+    /// Useful for SIMD workloads where all allocations
+    /// should be aligned to vector width.
     ///
-    /// Blink-allocators guarantee that memory can be used while shared
-    /// borrow to the allocator is held, preventing safe `fn reset` call.
+    /// # Panics
     ///
-    /// With this method it becomes caller responsibility to ensure
-    /// that allocated memory won't be used after reset.
+    /// Panics if `min_align` is not a power of two.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new().with_min_align(32);
+    /// for _ in 0..4 {
+    ///     let ptr = blink.allocate(std::alloc::Layout::new::<u8>()).unwrap();
+    ///     assert_eq!(ptr.as_ptr() as *mut u8 as usize % 32, 0);
+    /// }
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
     #[inline(always)]
-    pub unsafe fn reset_unchecked(&self) {
-        // Safety:
-        // Same instance is used for all allocations and resets.
-        unsafe {
-            self.arena.reset_unchecked(true, &self.allocator);
-        }
+    pub const fn with_min_align(mut self, min_align: usize) -> Self {
+        assert!(
+            min_align.is_power_of_two(),
+            "Minimum alignment must be a power of two"
+        );
+        self.min_align = min_align;
+        self
     }
 
-    /// Unwrap this allocator, returning the underlying allocator.
-    /// Leaks allocated chunks.
+    /// Makes memory layout of this allocator reproducible.
     ///
-    /// To deallocate all chunks call [`reset_final`](BlinkAlloc::reset_final) first.
-    pub fn into_inner(self) -> A {
-        let me = ManuallyDrop::new(self);
-        unsafe { core::ptr::read(&me.allocator) }
-    }
-}
-
-unsafe impl<A> Allocator for BlinkAlloc<A>
-where
-    A: Allocator,
-{
+    /// Spare memory that underlying allocator may provide
+    /// beyond requested chunk size is not used.
+    /// So chunk capacities and growth depend only on requested layouts.
+    /// Identical sequence of requests then yields identical
+    /// offsets of allocations from base of their chunks,
+    /// for layouts with alignment not greater than pointer alignment.
+    ///
+    /// Useful for snapshot tests of structures built in the arena.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// fn offsets() -> Vec<usize> {
+    ///     let blink = BlinkAlloc::new().with_deterministic_layout();
+    ///     (0..100u32)
+    ///         .map(|i| {
+    ///             let layout = Layout::array::<u32>(i as usize % 7 + 1).unwrap();
+    ///             let ptr = blink.allocate(layout).unwrap();
+    ///             let base = blink.chunks().next().unwrap().base();
+    ///             ptr.as_ptr() as *mut u8 as usize - base.as_ptr() as usize
+    ///         })
+    ///         .collect()
+    /// }
+    ///
+    /// assert_eq!(offsets(), offsets());
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
     #[inline(always)]
-    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
-        BlinkAlloc::allocate(self, layout)
+    pub fn with_deterministic_layout(mut self) -> Self {
+        self.arena.set_exact_chunks();
+        self
     }
 
+    /// Increases alignment of the layout up to the minimum alignment
+    /// of this allocator.
     #[inline(always)]
-    unsafe fn shrink(
-        &self,
-        ptr: NonNull<u8>,
-        old_layout: Layout,
-        new_layout: Layout,
-    ) -> Result<NonNull<[u8]>, AllocError> {
-        BlinkAlloc::resize(self, ptr, old_layout, new_layout)
+    fn min_aligned(&self, layout: Layout) -> Result<Layout, AllocError> {
+        if layout.align() >= self.min_align {
+            return Ok(layout);
+        }
+        layout.align_to(self.min_align).map_err(|_| AllocError)
+    }
+
+    /// Returns capacity of the most recently allocated chunk.
+    /// Returns 0 if allocator has no chunks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// assert_eq!(blink.last_chunk_cap(), 0);
+    ///
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 1024]>()).unwrap();
+    /// assert!(blink.last_chunk_cap() >= 1024);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn last_chunk_cap(&self) -> usize {
+        self.arena.last_chunk_size()
+    }
+
+    /// Returns snapshot of memory statistics of this allocator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 1024]>()).unwrap();
+    ///
+    /// let stats = blink.stats();
+    /// assert_eq!(stats.chunks, 1);
+    /// assert!(stats.used >= 1024);
+    /// assert!(stats.capacity >= stats.used);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn stats(&self) -> BlinkStats {
+        self.arena.stats()
+    }
+
+    /// Returns iterator over memory chunks owned by this allocator.
+    /// Chunks are listed from the newest to the oldest.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 1024]>()).unwrap();
+    ///
+    /// for chunk in blink.chunks() {
+    ///     assert!(chunk.used() <= chunk.cap());
+    /// }
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn chunks(&self) -> ChunkIter<'_> {
+        self.arena.chunks()
+    }
+
+    /// Calls `f` with base pointer and number of used bytes
+    /// of each chunk that has at least one byte allocated.
+    /// Chunks are visited from the newest to the oldest.
+    ///
+    /// Unlike [`chunks`](BlinkAlloc::chunks) this yields only used prefix
+    /// of each chunk, which covers all live allocations.
+    /// Allocations above [`huge_alloc_threshold`](BlinkAlloc::huge_alloc_threshold)
+    /// are visited after chunks.
+    ///
+    /// Useful for external tools that scan arena contents,
+    /// e.g. to compute checksums or snapshot memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let blink = BlinkAlloc::new();
+    /// let ptr = blink.allocate(Layout::new::<[u8; 16]>()).unwrap();
+    /// unsafe { ptr.cast::<[u8; 16]>().as_ptr().write([1; 16]) };
+    ///
+    /// let mut sum = 0;
+    /// blink.for_each_used_byte_range(|base, len| {
+    ///     let bytes = unsafe { std::slice::from_raw_parts(base, len) };
+    ///     sum += bytes.iter().map(|&b| b as u32).sum::<u32>();
+    /// });
+    /// assert_eq!(sum, 16);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn for_each_used_byte_range(&self, mut f: impl FnMut(*const u8, usize)) {
+        self.arena.for_each_used_byte_range(&mut f);
+        self.huge.for_each(|data, len| f(data, len));
+    }
+
+    /// Calls `f` with memory of each chunk and number of used bytes in it.
+    /// Chunks are visited from the newest to the oldest.
+    ///
+    /// `chunk_bytes` covers usable memory of the chunk,
+    /// starting from [`ChunkInfo::base`](crate::ChunkInfo::base).
+    /// `used_bytes <= chunk_bytes.len()` always holds,
+    /// and `chunk_bytes[..used_bytes]` covers all allocations from the chunk.
+    /// Bytes are not guaranteed to be initialized,
+    /// e.g. alignment padding and memory above `used_bytes` may be uninitialized.
+    ///
+    /// Takes mutable borrow so that no allocation is mutably borrowed
+    /// while `f` inspects chunk memory.
+    ///
+    /// Allocations above [`huge_alloc_threshold`](BlinkAlloc::huge_alloc_threshold)
+    /// are not placed in chunks and are not visited.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let mut blink = BlinkAlloc::new();
+    /// let ptr = blink.allocate(Layout::new::<[u8; 16]>()).unwrap();
+    /// unsafe { ptr.cast::<[u8; 16]>().as_ptr().write([1; 16]) };
+    ///
+    /// let mut sum = 0;
+    /// blink.for_each_chunk(|chunk_bytes, used_bytes| {
+    ///     assert!(used_bytes <= chunk_bytes.len());
+    ///     let used = &chunk_bytes[..used_bytes];
+    ///     // Safety: All used bytes are written above.
+    ///     sum += used.iter().map(|b| unsafe { b.assume_init() } as u32).sum::<u32>();
+    /// });
+    /// assert_eq!(sum, 16);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn for_each_chunk<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&[MaybeUninit<u8>], usize),
+    {
+        for chunk in self.arena.chunks() {
+            // Safety: Chunk memory is valid for `cap` bytes
+            // and is not mutably borrowed while `self` is borrowed mutably.
+            let chunk_bytes = unsafe {
+                core::slice::from_raw_parts(
+                    chunk.base().as_ptr().cast::<MaybeUninit<u8>>(),
+                    chunk.cap(),
+                )
+            };
+            f(chunk_bytes, chunk.used());
+        }
+    }
+
+    /// Returns smallest chunk size that would serve all allocations
+    /// currently made from this allocator with a single chunk.
+    ///
+    /// Call this after a completed workload and pass the value to
+    /// [`BlinkAlloc::with_chunk_size`] for the next run of the same workload
+    /// to avoid allocating more than one chunk.
+    ///
+    /// The value is advisory.
+    /// Padding for over-aligned allocations may not be accounted for exactly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let blink = BlinkAlloc::new();
+    /// for _ in 0..100 {
+    ///     blink.allocate(Layout::new::<[u64; 8]>()).unwrap();
+    /// }
+    /// let chunk_size = blink.minimum_viable_chunk_size();
+    ///
+    /// let blink = BlinkAlloc::with_chunk_size(chunk_size);
+    /// for _ in 0..100 {
+    ///     blink.allocate(Layout::new::<[u64; 8]>()).unwrap();
+    /// }
+    /// assert_eq!(blink.chunks().count(), 1);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn minimum_viable_chunk_size(&self) -> usize {
+        self.arena.minimum_viable_chunk_size()
+    }
+
+    /// Pushes capacity of each chunk owned by this allocator into `sizes`.
+    /// Chunks are listed from the newest to the oldest.
+    ///
+    /// Useful to diagnose arena growth patterns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 1024]>()).unwrap();
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 4096]>()).unwrap();
+    ///
+    /// let mut sizes = Vec::new();
+    /// blink.collect_chunk_sizes(&mut sizes);
+    /// assert_eq!(sizes.len(), 2);
+    /// assert!(sizes[0] > sizes[1]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn collect_chunk_sizes(&self, sizes: &mut alloc::vec::Vec<usize>) {
+        self.arena.collect_chunk_sizes(sizes)
+    }
+
+    /// Allocates memory with specified layout from this allocator.
+    /// If needed it will allocate new chunk using underlying allocator.
+    /// If chunk allocation fails, it will return `Err`.
+    ///
+    /// Zero-sized allocations never fail. They return dangling pointer
+    /// aligned to `layout.align()` without allocating chunks
+    /// or consuming chunk memory.
+    ///
+    /// With "debug-alloc-tracking" feature in debug builds
+    /// records location of the caller.
+    /// See [`for_each_live_allocation`](BlinkAlloc::for_each_live_allocation).
+    #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
+    pub fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let layout = self.min_aligned(layout)?;
+        self.count_allocation();
+
+        if layout.size() > self.huge_alloc_threshold {
+            let ptr = self.huge.alloc(layout, &self.allocator)?;
+            self.allocated.set(self.allocated.get() + layout.size());
+            self.record_site(layout, ptr);
+            return Ok(ptr);
+        }
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        let ptr = match unsafe { self.arena.alloc_fast(layout) } {
+            Some(ptr) => ptr,
+            None => self.alloc_slow(layout)?,
+        };
+        self.allocated.set(self.allocated.get() + layout.size());
+        self.record_site(layout, ptr);
+        Ok(ptr)
+    }
+
+    /// Allocates memory with specified layout from the current chunk.
+    /// Returns `None` if the current chunk doesn't have enough room.
+    ///
+    /// Never calls underlying allocator,
+    /// making it suitable for latency-bounded contexts
+    /// like audio callbacks.
+    /// Allocations above [huge allocation threshold](BlinkAlloc::with_huge_alloc_threshold)
+    /// always return `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let blink = BlinkAlloc::with_chunk_size(1024);
+    /// assert!(blink.try_allocate_in_current_chunk(Layout::new::<u32>()).is_none());
+    ///
+    /// blink.allocate(Layout::new::<u32>()).unwrap();
+    /// assert!(blink.try_allocate_in_current_chunk(Layout::new::<u32>()).is_some());
+    /// assert!(blink.try_allocate_in_current_chunk(Layout::new::<[u8; 4096]>()).is_none());
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
+    pub fn try_allocate_in_current_chunk(&self, layout: Layout) -> Option<NonNull<[u8]>> {
+        let layout = self.min_aligned(layout).ok()?;
+        if layout.size() > self.huge_alloc_threshold {
+            return None;
+        }
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        let ptr = unsafe { self.arena.alloc_fast(layout)? };
+        self.allocated.set(self.allocated.get() + layout.size());
+        self.record_site(layout, ptr);
+        Some(ptr)
+    }
+
+    /// Records location of the caller for non-empty allocation.
+    #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
+    fn record_site(&self, _layout: Layout, _ptr: NonNull<[u8]>) {
+        #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+        if _layout.size() != 0 {
+            self.sites
+                .record(_ptr.cast(), core::panic::Location::caller());
+        }
+    }
+
+    /// Allocates from unused chunk kept by `reset_partial`
+    /// or from new chunk.
+    /// With "oom-handler" feature calls OOM handler on failure
+    /// and retries once.
+    #[inline(always)]
+    fn alloc_slow(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if let Some(ptr) = self.arena.alloc_unused(layout) {
+            return Ok(ptr);
+        }
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        let result = unsafe { self.arena.alloc_slow(layout, &self.allocator) };
+
+        #[cfg(feature = "oom-handler")]
+        let result = result.or_else(|_| {
+            (self.oom_handler)(layout);
+
+            // Safety:
+            // Same instance is used for all allocations and resets.
+            unsafe { self.arena.alloc_slow(layout, &self.allocator) }
+        });
+
+        #[cfg(feature = "metrics")]
+        if result.is_ok() {
+            self.gauges.add_chunk(self.arena.last_chunk_size());
+        }
+
+        result
+    }
+
+    /// Sets function called when underlying allocator
+    /// fails to allocate new chunk in [`allocate`](BlinkAlloc::allocate).
+    ///
+    /// If handler returns, allocation is retried once.
+    /// If it fails again, `allocate` returns `Err`.
+    /// Handler may free memory elsewhere to make retry succeed,
+    /// or panic or abort to handle OOM in one place.
+    ///
+    /// Default handler does nothing.
+    ///
+    /// Only available with "oom-handler" feature.
+    #[cfg(feature = "oom-handler")]
+    #[inline(always)]
+    pub fn set_oom_handler(&mut self, handler: fn(Layout)) {
+        self.oom_handler = handler;
+    }
+
+    /// Sets number of allocations after which
+    /// next allocation resets this allocator first,
+    /// deallocating all chunks except the last one.
+    ///
+    /// This bounds memory usage of long-lived allocators
+    /// that don't have a natural point to call [`reset`](BlinkAlloc::reset).
+    /// Only calls to [`allocate`](BlinkAlloc::allocate) are counted.
+    ///
+    /// Zero period disables auto-reset.
+    ///
+    /// # Safety
+    ///
+    /// Auto-reset invalidates all memory allocated before it,
+    /// while references to that memory may still be alive.
+    /// Caller must ensure that memory allocated from this allocator
+    /// is not used after `n` subsequent allocations.
+    ///
+    /// This allocator must not be used by [`Blink`](crate::Blink)
+    /// as it keeps values to drop in allocated memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let mut blink = BlinkAlloc::new();
+    ///
+    /// // Safety: Allocated memory is not used.
+    /// unsafe { blink.set_auto_reset_period(2) };
+    ///
+    /// blink.allocate(Layout::new::<u32>()).unwrap();
+    /// blink.allocate(Layout::new::<u32>()).unwrap();
+    /// assert_eq!(blink.alloc_count_since_reset(), 2);
+    ///
+    /// // Resets the allocator before allocating.
+    /// blink.allocate(Layout::new::<u32>()).unwrap();
+    /// assert_eq!(blink.alloc_count_since_reset(), 1);
+    /// assert_eq!(blink.allocated_since_reset(), 4);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub unsafe fn set_auto_reset_period(&mut self, n: usize) {
+        self.auto_reset_period = n;
+    }
+
+    /// Disables auto-reset set with
+    /// [`set_auto_reset_period`](BlinkAlloc::set_auto_reset_period).
+    #[inline(always)]
+    pub fn clear_auto_reset_period(&mut self) {
+        self.auto_reset_period = 0;
+    }
+
+    /// Returns number of [`allocate`](BlinkAlloc::allocate) calls since last reset.
+    #[inline(always)]
+    pub fn alloc_count_since_reset(&self) -> usize {
+        self.alloc_count.get()
+    }
+
+    #[inline(always)]
+    fn count_allocation(&self) {
+        let count = self.alloc_count.get();
+        if self.auto_reset_period != 0 && count >= self.auto_reset_period {
+            cold();
+
+            // Safety: Caller of `set_auto_reset_period` guarantees
+            // that memory allocated so far is not used anymore.
+            unsafe { self.reset_unchecked() };
+            self.alloc_count.set(1);
+        } else {
+            self.alloc_count.set(count + 1);
+        }
+    }
+
+    /// Records current usage into the high-water mark.
+    ///
+    /// Called only before usage may decrease, i.e. on resets,
+    /// rollbacks, deallocations and shrinks,
+    /// so that allocations do not pay for tracking.
+    #[inline(always)]
+    fn record_high_water_mark(&self) {
+        let used = self.arena.used_bytes();
+        if used > self.high_water_mark.get() {
+            self.high_water_mark.set(used);
+        }
+    }
+
+    /// Returns number of bytes requested from this allocator since last reset.
+    ///
+    /// Counts sizes of allocation layouts and growth of resized allocations.
+    /// Unlike [`stats`](BlinkAlloc::stats) that reports memory reserved in chunks,
+    /// this reports memory actually asked for.
+    /// Difference between the two is memory lost to fragmentation and padding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let mut blink = BlinkAlloc::new();
+    /// blink.allocate(Layout::new::<u8>()).unwrap();
+    /// blink.allocate(Layout::new::<u64>()).unwrap();
+    /// assert_eq!(blink.allocated_since_reset(), 9);
+    /// assert!(blink.stats().used >= 9);
+    ///
+    /// blink.reset();
+    /// assert_eq!(blink.allocated_since_reset(), 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn allocated_since_reset(&self) -> usize {
+        self.allocated.get()
+    }
+
+    /// Returns number of bytes consumed from chunks since last reset.
+    ///
+    /// Memory of all chunks except the last one is counted in full,
+    /// as it is not used for allocations anymore.
+    #[inline(always)]
+    pub fn used_bytes(&self) -> usize {
+        self.arena.used_bytes()
+    }
+
+    /// Returns `true` if nothing is allocated from this allocator
+    /// since creation or last reset.
+    ///
+    /// Allocator may still keep a chunk for reuse,
+    /// check [`last_chunk_cap`](BlinkAlloc::last_chunk_cap)
+    /// to tell fresh allocator from reset one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// let mut blink = BlinkAlloc::new();
+    /// assert!(blink.is_empty());
+    ///
+    /// blink.allocate(std::alloc::Layout::new::<u32>()).unwrap();
+    /// assert!(!blink.is_empty());
+    ///
+    /// blink.reset();
+    /// assert!(blink.is_empty());
+    /// assert_ne!(blink.last_chunk_cap(), 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.arena.is_empty() && self.huge.is_empty()
+    }
+
+    /// Returns peak value of [`used_bytes`](BlinkAlloc::used_bytes).
+    ///
+    /// Peak is recorded when usage may decrease,
+    /// i.e. on resets, rollbacks, deallocations and shrinks,
+    /// and combined with current usage when this method is called.
+    ///
+    /// Unlike total capacity of chunks that never decreases on resets,
+    /// this value reflects actual memory usage of the workload.
+    /// It is not cleared on reset, use
+    /// [`reset_watermark`](BlinkAlloc::reset_watermark) for that.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let mut blink = BlinkAlloc::new();
+    /// blink.allocate(Layout::new::<[u8; 1024]>()).unwrap();
+    /// blink.reset();
+    /// blink.allocate(Layout::new::<[u8; 16]>()).unwrap();
+    ///
+    /// assert!(blink.high_water_mark() >= 1024);
+    /// blink.reset_watermark();
+    /// assert!(blink.high_water_mark() < 1024);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn high_water_mark(&self) -> usize {
+        self.high_water_mark.get().max(self.arena.used_bytes())
+    }
+
+    /// Resets [`high_water_mark`](BlinkAlloc::high_water_mark)
+    /// to current [`used_bytes`](BlinkAlloc::used_bytes).
+    #[inline(always)]
+    pub fn reset_watermark(&self) {
+        self.high_water_mark.set(self.arena.used_bytes());
+    }
+
+    /// Allocates contiguous block of memory for `count` items with the same layout.
+    /// Block is aligned to `layout.align()` and items are placed
+    /// at `layout.pad_to_align().size()` stride,
+    /// which is `layout.size()` for layouts of Rust types.
+    ///
+    /// This is equivalent to allocating `Layout::array::<T>(count)`
+    /// for runtime `Layout`.
+    /// Returns `Err` if size of the block overflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let blink = BlinkAlloc::new();
+    /// let layout = Layout::new::<u64>();
+    /// let block = blink.allocate_many_same_layout(10, layout).unwrap();
+    /// assert!(block.len() >= 80);
+    ///
+    /// let first = block.cast::<u64>();
+    /// for i in 0..10 {
+    ///     unsafe { first.as_ptr().add(i).write(i as u64) };
+    /// }
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
+    pub fn allocate_many_same_layout(
+        &self,
+        count: usize,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let stride = layout.pad_to_align().size();
+        let size = stride.checked_mul(count).ok_or(AllocError)?;
+        let layout = Layout::from_size_align(size, layout.align()).map_err(|_| AllocError)?;
+        self.allocate(layout)
+    }
+
+    /// Allocates `size` bytes aligned to [`CACHE_LINE_SIZE`](crate::CACHE_LINE_SIZE).
+    ///
+    /// Allocation starts at cache line boundary,
+    /// so no allocation made before shares its first cache line.
+    /// Round `size` up to multiple of cache line size
+    /// to keep following allocations off its last cache line.
+    ///
+    /// Returns `Err` if size overflows when padded to alignment
+    /// or if chunk allocation fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::{BlinkAlloc, CACHE_LINE_SIZE};
+    /// let blink = BlinkAlloc::new();
+    /// let ptr = blink.allocate_cache_aligned(24).unwrap();
+    /// assert_eq!(ptr.as_ptr() as *mut u8 as usize % CACHE_LINE_SIZE, 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
+    pub fn allocate_cache_aligned(&self, size: usize) -> Result<NonNull<[u8]>, AllocError> {
+        let layout =
+            Layout::from_size_align(size, crate::CACHE_LINE_SIZE).map_err(|_| AllocError)?;
+        self.allocate(layout)
+    }
+
+    /// Allocates memory with specified layout,
+    /// asking for `min_extra` bytes of spare capacity beyond `layout.size()`.
+    ///
+    /// Allocation is extended in place up to `min_extra` bytes,
+    /// bounded by the end of the chunk it is placed in.
+    /// So returned slice is at least `layout.size() + min_extra` bytes long
+    /// when the chunk has enough room.
+    /// Useful for collections that can use extra capacity right away.
+    ///
+    /// Zero-sized and huge allocations are not extended.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let blink = BlinkAlloc::with_chunk_size(1024);
+    /// let ptr = blink.allocate_at_least(Layout::new::<[u32; 4]>(), 48).unwrap();
+    /// assert!(ptr.len() >= 64);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
+    pub fn allocate_at_least(
+        &self,
+        layout: Layout,
+        min_extra: usize,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let layout = self.min_aligned(layout)?;
+        let ptr = self.allocate(layout)?;
+        if layout.size() == 0 || layout.size() > self.huge_alloc_threshold {
+            return Ok(ptr);
+        }
+
+        // Allocation is the last one in the current chunk.
+        let extra = min_extra.min(self.arena.last_chunk_free());
+        if extra == 0 {
+            return Ok(ptr);
+        }
+
+        // Safety: Extended size fits into the chunk.
+        let new_layout =
+            unsafe { Layout::from_size_align_unchecked(ptr.len() + extra, layout.align()) };
+        let old_layout = unsafe { Layout::from_size_align_unchecked(ptr.len(), layout.align()) };
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        // `ptr` was just allocated with `old_layout`.
+        match unsafe { self.arena.resize_fast(ptr.cast(), old_layout, new_layout) } {
+            None => Ok(ptr),
+            Some(new_ptr) => {
+                self.allocated.set(self.allocated.get() + extra);
+                Ok(new_ptr)
+            }
+        }
+    }
+
+    /// Allocates memory for an array of `len` values of type `T`.
+    /// Returns typed slice pointer to uninitialized memory.
+    ///
+    /// Returns `Err` if size of the array overflows
+    /// or if chunk allocation fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// let array = blink.allocate_array::<u32>(10).unwrap();
+    /// assert_eq!(array.len(), 10);
+    ///
+    /// assert!(blink.allocate_array::<u32>(usize::MAX).is_err());
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
+    pub fn allocate_array<T>(&self, len: usize) -> Result<NonNull<[T]>, AllocError> {
+        let layout = Layout::array::<T>(len).map_err(|_| AllocError)?;
+        let ptr = self.allocate(layout)?;
+        let slice = core::ptr::slice_from_raw_parts_mut(ptr.as_ptr().cast::<T>(), len);
+
+        // Safety: `slice` points to non-null allocation.
+        Ok(unsafe { NonNull::new_unchecked(slice) })
+    }
+
+    /// Allocates memory for `len` values of type `T`.
+    /// Returns slice of uninitialized values.
+    ///
+    /// Memory stays valid until this allocator is reset.
+    /// Values written into the slice are never dropped by this allocator.
+    ///
+    /// Returns `Err` if size of the slice overflows
+    /// or if chunk allocation fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// let slice = blink.allocate_slice::<u32>(4).unwrap();
+    /// for (i, item) in slice.iter_mut().enumerate() {
+    ///     item.write(i as u32);
+    /// }
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn allocate_slice<T>(&self, len: usize) -> Result<&mut [MaybeUninit<T>], AllocError> {
+        let ptr = self.allocate_array::<T>(len)?;
+
+        // Safety: `ptr` points to allocation of `len` values of type `T`
+        // that is not used by anything else until reset.
+        Ok(unsafe { &mut *(ptr.as_ptr() as *mut [MaybeUninit<T>]) })
+    }
+
+    /// Allocates memory for `len` values of type `T`
+    /// and initializes them with `T::default()`.
+    ///
+    /// Memory stays valid until this allocator is reset.
+    /// Values are never dropped by this allocator.
+    ///
+    /// Returns `Err` if size of the slice overflows
+    /// or if chunk allocation fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// let blink = BlinkAlloc::new();
+    /// let slice = blink.allocate_slice_default::<u32>(4).unwrap();
+    /// assert_eq!(slice, [0; 4]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
+    #[allow(clippy::mut_from_ref)]
+    pub fn allocate_slice_default<T: Default>(&self, len: usize) -> Result<&mut [T], AllocError> {
+        let slice = self.allocate_slice::<T>(len)?;
+        for item in slice.iter_mut() {
+            item.write(T::default());
+        }
+
+        // Safety: All values are initialized.
+        Ok(unsafe { &mut *(slice as *mut [MaybeUninit<T>] as *mut [T]) })
+    }
+
+    /// Allocates zero-initialized memory for an array of `len` values of type `T`.
+    /// Returns typed slice pointer.
+    ///
+    /// Returns `Err` if size of the array overflows
+    /// or if chunk allocation fails.
+    #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
+    pub fn allocate_array_zeroed<T>(&self, len: usize) -> Result<NonNull<[T]>, AllocError> {
+        let ptr = self.allocate_array::<T>(len)?;
+
+        // Safety: `ptr` points to allocation of `len` values of type `T`.
+        unsafe {
+            core::ptr::write_bytes(ptr.as_ptr().cast::<T>(), 0, len);
+        }
+        Ok(ptr)
+    }
+
+    /// Resizes memory allocation.
+    /// Potentially happens in-place.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a pointer previously returned by [`allocate`](BlinkAlloc::allocate).
+    /// `old_size` must be in range `layout.size()..=slice.len()`
+    /// where `layout` is the layout used in the call to [`allocate`](BlinkAlloc::allocate).
+    /// and `slice` is the slice pointer returned by [`allocate`](BlinkAlloc::allocate).
+    ///
+    /// On success, the old pointer is invalidated and the new pointer is returned.
+    /// On error old allocation is still valid.
+    ///
+    /// Shrinking the last allocation gives memory back to the chunk
+    /// and returns slice of `new_layout.size()` bytes,
+    /// otherwise shrink keeps and returns old memory in full.
+    #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
+    pub unsafe fn resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let old_layout = self.min_aligned(old_layout)?;
+        let new_layout = self.min_aligned(new_layout)?;
+
+        if new_layout.size() < old_layout.size() {
+            // Shrinking may give memory back to the chunk.
+            self.record_high_water_mark();
+        }
+
+        let new_ptr = match unsafe { self.arena.resize_fast(ptr, old_layout, new_layout) } {
+            Some(ptr) => ptr,
+            None if new_layout.size() > self.huge_alloc_threshold => {
+                let new_ptr = self.huge.alloc(new_layout, &self.allocator)?;
+
+                // Safety: Both allocations are valid for copied bytes
+                // and do not overlap.
+                unsafe {
+                    core::ptr::copy_nonoverlapping(
+                        ptr.as_ptr(),
+                        new_ptr.as_ptr().cast(),
+                        old_layout.size().min(new_layout.size()),
+                    );
+                }
+                new_ptr
+            }
+            // Safety:
+            // Same instance is used for all allocations and resets.
+            // `ptr` was allocated by this allocator.
+            None => {
+                let new_ptr = unsafe {
+                    self.arena
+                        .resize_slow(ptr, old_layout, new_layout, &self.allocator)?
+                };
+                #[cfg(feature = "metrics")]
+                self.gauges.add_chunk(self.arena.last_chunk_size());
+                new_ptr
+            }
+        };
+        let grown = new_layout.size().saturating_sub(old_layout.size());
+        self.allocated.set(self.allocated.get() + grown);
+
+        #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+        if new_layout.size() != 0 {
+            self.sites
+                .resize(ptr, new_ptr.cast(), core::panic::Location::caller());
+        }
+
+        Ok(new_ptr)
+    }
+
+    /// Deallocates memory previously allocated from this allocator.
+    ///
+    /// This call may not actually free memory.
+    /// All memory is guaranteed to be freed on [`reset`](BlinkAlloc::reset) call.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a pointer previously returned by [`allocate`](BlinkAlloc::allocate).
+    /// `size` must be in range `layout.size()..=slice.len()`
+    /// where `layout` is the layout used in the call to [`allocate`](BlinkAlloc::allocate).
+    /// and `slice` is the slice pointer returned by [`allocate`](BlinkAlloc::allocate).
+    #[inline(always)]
+    pub unsafe fn deallocate(&self, ptr: NonNull<u8>, size: usize) {
+        #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+        self.sites.remove(ptr);
+
+        // Deallocation may give memory back to the chunk.
+        self.record_high_water_mark();
+
+        // Safety:
+        // `ptr` was allocated by this allocator.
+        unsafe {
+            self.arena.dealloc(ptr, size);
+        }
+    }
+
+    /// Checks if `ptr` points into memory allocated by this allocator.
+    #[inline]
+    pub(crate) fn owns(&self, ptr: *const u8) -> bool {
+        self.arena.owns(ptr) || self.huge.owns(ptr)
+    }
+
+    /// Resets this allocator, deallocating all chunks except the last one.
+    /// Last chunk will be reused.
+    /// With steady memory usage after few iterations
+    /// one chunk should be sufficient for all allocations between resets.
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        self.record_high_water_mark();
+        self.finalizers.0.reset();
+        #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+        self.sites.report();
+        self.allocated.set(0);
+        self.alloc_count.set(0);
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        unsafe {
+            self.huge.free(&self.allocator);
+            self.arena.reset(true, &self.allocator);
+        }
+
+        #[cfg(feature = "metrics")]
+        self.gauges.reset(self.stats());
+    }
+
+    /// Calls `f` with access to this allocator branded with unique lifetime
+    /// and resets this allocator after `f` returns.
+    ///
+    /// Allocations made through [`BrandedBlink`] are bound to the brand
+    /// and borrow checker rejects code where they escape `f`
+    /// or are mixed with allocations of another brand.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::{BlinkAlloc, Branded, BrandedBlink};
+    /// # use allocator_api2::alloc::Global;
+    /// fn sum<'id>(_arena: BrandedBlink<'id, Global>, values: &[Branded<'id, &'id mut u32>]) -> u32 {
+    ///     values.iter().map(|value| **value).sum()
+    /// }
+    ///
+    /// let mut blink = BlinkAlloc::new();
+    /// let total = blink.brand(|arena| {
+    ///     let a = arena.put(1u32);
+    ///     let b = arena.put(2u32);
+    ///     sum(arena, &[a, b])
+    /// });
+    /// assert_eq!(total, 3);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    ///
+    /// Allocations can't escape the closure.
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```compile_fail")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// # use blink_alloc::BlinkAlloc;
+    /// let mut blink = BlinkAlloc::new();
+    /// let value = blink.brand(|arena| arena.put(1u32).into_inner());
+    /// ```
+    ///
+    /// Allocations of different brands can't be mixed.
+    ///
+    #[cfg_attr(feature = "alloc", doc = "```compile_fail")]
+    #[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+    /// # use blink_alloc::{BlinkAlloc, Branded, BrandedBlink};
+    /// # use allocator_api2::alloc::Global;
+    /// fn same<'id>(_arena: BrandedBlink<'id, Global>, _value: Branded<'id, &'id mut u32>) {}
+    ///
+    /// let mut a = BlinkAlloc::new();
+    /// let mut b = BlinkAlloc::new();
+    /// a.brand(|a| b.brand(|b| same(a, b.put(1u32))));
+    /// ```
+    #[inline]
+    pub fn brand<R>(&mut self, f: impl for<'id> FnOnce(BrandedBlink<'id, A>) -> R) -> R {
+        let result = f(BrandedBlink::new(self));
+        self.reset();
+        result
+    }
+
+    /// Registers finalizer to run on next reset of this allocator.
+    /// Returns `Err(f)` if memory for finalizer cannot be allocated.
+    ///
+    /// Finalizers run before chunks are reset or deallocated,
+    /// in reverse order of registration.
+    /// Useful to drop values stored in raw allocations.
+    ///
+    /// Finalizers registered by running finalizers
+    /// run during the same reset.
+    /// Resetting this allocator from a finalizer is not allowed
+    /// and panics in debug builds.
+    ///
+    /// Finalizer is stored in this allocator's memory.
+    #[inline]
+    pub fn try_on_reset<F>(&self, f: F) -> Result<(), F>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let Ok(ptr) = self.allocate(Layout::new::<DropItem<Finalizer<F>>>()) else {
+            return Err(f);
+        };
+
+        // Safety: `ptr` is allocated for `DropItem<Finalizer<F>>`.
+        // Item stays valid until finalizers are run on reset.
+        unsafe {
+            let item = DropItem::init_value(ptr.cast(), f, |slot, f| {
+                slot.write(Finalizer(ManuallyDrop::new(f)));
+            });
+            self.finalizers.0.add(item);
+        }
+        Ok(())
+    }
+
+    /// Registers finalizer to run on next reset of this allocator.
+    ///
+    /// Finalizers run before chunks are reset or deallocated,
+    /// in reverse order of registration.
+    /// Useful to drop values stored in raw allocations.
+    ///
+    /// Finalizer is stored in this allocator's memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
+    /// let mut blink = BlinkAlloc::new();
+    /// let flag = Arc::new(AtomicBool::new(false));
+    ///
+    /// let set_flag = flag.clone();
+    /// blink.on_reset(move || set_flag.store(true, Ordering::Relaxed));
+    ///
+    /// blink.reset();
+    /// assert!(flag.load(Ordering::Relaxed));
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    pub fn on_reset<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if self.try_on_reset(f).is_err() {
+            handle_alloc_error(Layout::new::<DropItem<Finalizer<F>>>());
+        }
+    }
+
+    /// Resets this allocator like [`reset`](BlinkAlloc::reset),
+    /// zeroing memory of all chunks first.
+    ///
+    /// Use this when allocations may hold sensitive data,
+    /// such as cryptographic keys or passwords,
+    /// that must not linger in memory that is reused
+    /// or returned to the underlying allocator.
+    ///
+    /// Whole capacity of each chunk is zeroed, not only the used part,
+    /// since memory given back by deallocation or shrink may still hold data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let mut blink = BlinkAlloc::new();
+    /// let secret = blink.allocate(Layout::new::<[u8; 32]>()).unwrap();
+    /// unsafe { secret.cast::<[u8; 32]>().as_ptr().write([0x42; 32]) };
+    /// blink.reset_zeroed();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn reset_zeroed(&mut self) {
+        self.record_high_water_mark();
+        // Finalizers live in chunk memory.
+        self.finalizers.0.reset();
+
+        for chunk in self.chunks() {
+            // Safety: Chunk memory is owned by this allocator.
+            // Allocations cannot be used while `self` is borrowed mutably.
+            unsafe {
+                zero_volatile(chunk.base().as_ptr(), chunk.cap());
+            }
+        }
+        self.huge.zero();
+
+        // Keep following resets from being reordered before zeroing.
+        compiler_fence(Ordering::SeqCst);
+
+        self.reset();
+    }
+
+    /// Resets this allocator, keeping single chunk sized
+    /// to fit memory used before this call.
+    ///
+    /// Unlike [`reset`](BlinkAlloc::reset) which retains the last chunk
+    /// regardless of its size, this method replaces the retained chunk
+    /// with a smaller one if it is larger than required.
+    /// Useful after spiky allocation phase is over to give
+    /// memory back to the underlying allocator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let mut blink = BlinkAlloc::new();
+    /// blink.allocate(Layout::new::<[u8; 65536]>()).unwrap();
+    /// blink.reset();
+    ///
+    /// blink.allocate(Layout::new::<[u8; 1024]>()).unwrap();
+    /// blink.shrink_to_fit();
+    /// assert!(blink.last_chunk_cap() < 65536);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn shrink_to_fit(&mut self) {
+        self.finalizers.0.reset();
+        #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+        self.sites.report();
+        self.allocated.set(0);
+        self.alloc_count.set(0);
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        unsafe {
+            self.huge.free(&self.allocator);
+            self.arena.reset_shrink(&self.allocator);
+        }
+
+        #[cfg(feature = "metrics")]
+        self.gauges.reset(self.stats());
+    }
+
+    /// Resets this allocator, keeping `keep_n` most recently allocated chunks
+    /// and deallocating older ones.
+    ///
+    /// Kept chunks are reused before new chunks are allocated.
+    /// Useful when working set spans few chunks
+    /// and older chunks are pure overhead.
+    /// `reset_partial(1)` is the same as [`reset`](BlinkAlloc::reset)
+    /// and `reset_partial(0)` is the same as [`reset_final`](BlinkAlloc::reset_final).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let mut blink = BlinkAlloc::with_chunk_size(1024);
+    /// for _ in 0..8 {
+    ///     blink.allocate(Layout::new::<[u8; 1024]>()).unwrap();
+    /// }
+    /// assert!(blink.chunks().count() > 2);
+    ///
+    /// blink.reset_partial(2);
+    /// assert_eq!(blink.chunks().count(), 2);
+    /// assert!(blink.is_empty());
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn reset_partial(&mut self, keep_n: usize) {
+        self.record_high_water_mark();
+        self.finalizers.0.reset();
+        #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+        self.sites.report();
+        self.allocated.set(0);
+        self.alloc_count.set(0);
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        unsafe {
+            self.huge.free(&self.allocator);
+            self.arena.reset_partial(keep_n, &self.allocator);
+        }
+
+        #[cfg(feature = "metrics")]
+        self.gauges.reset(self.stats());
+    }
+
+    /// Resets this allocator, deallocating all chunks.
+    #[inline(always)]
+    pub fn reset_final(&mut self) {
+        self.record_high_water_mark();
+        self.finalizers.0.reset();
+        #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+        self.sites.report();
+        self.allocated.set(0);
+        self.alloc_count.set(0);
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        unsafe {
+            self.huge.free(&self.allocator);
+            self.arena.reset(false, &self.allocator);
+        }
+
+        #[cfg(feature = "metrics")]
+        self.gauges.reset(self.stats());
+    }
+
+    /// Resets this allocator without deallocating chunks.
+    /// If `keep_last` is `true`, the last chunk is kept and reused,
+    /// otherwise all chunks are forgotten.
+    ///
+    /// Forgotten chunks and allocations above
+    /// [`huge_alloc_threshold`](BlinkAlloc::huge_alloc_threshold)
+    /// are never returned to the underlying allocator.
+    /// This is appropriate when the underlying allocator
+    /// reclaims its memory on its own, e.g. a pool or another arena
+    /// that is reset or dropped as a whole.
+    /// Otherwise the chunks are leaked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use core::alloc::Layout;
+    /// let mut pool = BlinkAlloc::new();
+    /// let mut blink = BlinkAlloc::new_in(&pool);
+    /// blink.allocate(Layout::new::<[u8; 64]>()).unwrap();
+    ///
+    /// // Chunks are reclaimed by `pool`.
+    /// blink.reset_leak(false);
+    /// drop(blink);
+    /// pool.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn reset_leak(&mut self, keep_last: bool) {
+        self.record_high_water_mark();
+        self.finalizers.0.reset();
+        #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+        self.sites.report();
+        self.allocated.set(0);
+        self.alloc_count.set(0);
+
+        self.huge.leak();
+        self.arena.reset_leak(keep_last);
+
+        #[cfg(feature = "metrics")]
+        self.gauges.reset(self.stats());
+    }
+
+    /// Resets this allocator, deallocating all chunks except the last one.
+    /// Last chunk will be reused.
+    /// With steady memory usage after few iterations
+    /// one chunk should be sufficient for all allocations between resets.
+    ///
+    /// # Safety
+    ///
+    /// Blink-allocators guarantee that memory can be used while shared
+    /// borrow to the allocator is held, preventing safe `fn reset` call.
+    ///
+    /// With this method it becomes caller responsibility to ensure
+    /// that allocated memory won't be used after reset.
+    #[inline(always)]
+    pub unsafe fn reset_unchecked(&self) {
+        self.record_high_water_mark();
+        // Safety: Caller guarantees that allocated memory is not used.
+        unsafe { self.finalizers.0.reset_unchecked() };
+        #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+        self.sites.report();
+        self.allocated.set(0);
+        self.alloc_count.set(0);
+
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        unsafe {
+            self.huge.free(&self.allocator);
+            self.arena.reset_unchecked(true, &self.allocator);
+        }
+
+        #[cfg(feature = "metrics")]
+        self.gauges.reset(self.stats());
+    }
+
+    /// Returns current position of the arena.
+    #[inline(always)]
+    pub(crate) fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            arena: self.arena.checkpoint(),
+            finalizers: self.finalizers.0.checkpoint(),
+            #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+            sites: self.sites.checkpoint(),
+        }
+    }
+
+    /// Runs finalizers registered after the checkpoint
+    /// and deallocates all memory allocated after the checkpoint.
+    ///
+    /// # Safety
+    ///
+    /// `checkpoint` must be created from this allocator
+    /// and no reset could happen since then.
+    /// Memory allocated after the checkpoint must not be used.
+    #[inline(always)]
+    pub(crate) unsafe fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.record_high_water_mark();
+        // Safety:
+        // Same instance is used for all allocations and resets.
+        unsafe {
+            self.finalizers.0.rollback(checkpoint.finalizers);
+            self.arena.rollback(checkpoint.arena, &self.allocator);
+        }
+
+        #[cfg(feature = "metrics")]
+        self.gauges.set(self.stats());
+
+        #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+        self.sites.rollback(checkpoint.sites);
+    }
+
+    /// Calls `f` with address of each allocation that is not deallocated
+    /// and location of the code that made it,
+    /// in order allocations were made.
+    ///
+    /// Allocations are forgotten on reset,
+    /// after locations of live ones are printed to stderr.
+    /// Location is the caller of [`allocate`](BlinkAlloc::allocate)
+    /// or [`resize`](BlinkAlloc::resize) for allocations
+    /// made through [`Allocator`] trait, e.g. by collections,
+    /// which points into the collection code.
+    ///
+    /// Allocations made from `f` are not recorded.
+    ///
+    /// Useful to find out where allocation that is used after reset was made.
+    /// Recording allocation sites is slow and takes memory
+    /// from system allocator, it is intended for development only.
+    ///
+    /// Only available with "debug-alloc-tracking" feature in debug builds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let blink = BlinkAlloc::new();
+    /// let ptr = blink.allocate(Layout::new::<u32>()).unwrap();
+    ///
+    /// let mut sites = Vec::new();
+    /// blink.for_each_live_allocation(|addr, location| sites.push((addr, location.line())));
+    /// assert_eq!(sites, [(ptr.as_ptr() as *mut u8 as usize, line!() - 4)]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+    #[inline]
+    pub fn for_each_live_allocation(
+        &self,
+        f: impl FnMut(usize, &'static core::panic::Location<'static>),
+    ) {
+        self.sites.for_each(f)
+    }
+
+    /// Unwrap this allocator, returning the underlying allocator.
+    /// Leaks allocated chunks.
+    ///
+    /// To deallocate all chunks call [`reset_final`](BlinkAlloc::reset_final) first.
+    pub fn into_inner(self) -> A {
+        let me = ManuallyDrop::new(self);
+        unsafe { core::ptr::read(&me.allocator) }
+    }
+
+    /// Moves allocator to the heap and returns opaque pointer to it.
+    ///
+    /// Pointer can be handed to foreign code and passed back
+    /// to [`BlinkAlloc::from_raw`] to reclaim the allocator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::BlinkAlloc;
+    /// # use allocator_api2::alloc::Global;
+    /// let handle = BlinkAlloc::into_raw(BlinkAlloc::new());
+    ///
+    /// // Safety: `handle` is returned by `into_raw` with the same allocator type.
+    /// let mut blink = unsafe { BlinkAlloc::<Global>::from_raw(handle) };
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub fn into_raw(alloc: Self) -> *mut () {
+        alloc::boxed::Box::into_raw(alloc::boxed::Box::new(alloc)).cast()
+    }
+
+    /// Reconstructs allocator from pointer returned by [`BlinkAlloc::into_raw`].
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be returned by [`BlinkAlloc::into_raw`]
+    /// for `BlinkAlloc` with the same underlying allocator type.
+    /// `ptr` must not be used after this call.
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    pub unsafe fn from_raw(ptr: *mut ()) -> Self {
+        // Safety: `ptr` is created by `Box::into_raw` in `into_raw`.
+        *unsafe { alloc::boxed::Box::from_raw(ptr.cast::<Self>()) }
+    }
+}
+
+unsafe impl<A> Allocator for BlinkAlloc<A>
+where
+    A: Allocator,
+{
+    #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        BlinkAlloc::allocate(self, layout)
+    }
+
+    #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        BlinkAlloc::resize(self, ptr, old_layout, new_layout)
     }
 
     #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
     unsafe fn grow(
         &self,
         ptr: NonNull<u8>,
@@ -325,11 +2155,14 @@ where
     }
 }
 
+// Core provides blanket implementation for `&mut A` on nightly.
+#[cfg(not(feature = "nightly"))]
 unsafe impl<A> Allocator for &mut BlinkAlloc<A>
 where
     A: Allocator,
 {
     #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
     fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
         BlinkAlloc::allocate(self, layout)
     }
@@ -340,6 +2173,7 @@ where
     }
 
     #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
     unsafe fn shrink(
         &self,
         ptr: NonNull<u8>,
@@ -350,6 +2184,7 @@ where
     }
 
     #[inline(always)]
+    #[cfg_attr(all(debug_assertions, feature = "debug-alloc-tracking"), track_caller)]
     unsafe fn grow(
         &self,
         ptr: NonNull<u8>,
@@ -365,6 +2200,109 @@ where
     }
 }
 
+/// Allows to use [`BlinkAlloc`] as global allocator.
+/// All allocations are served from the arena.
+///
+/// Only available with "global-alloc" feature.
+///
+/// # Safety
+///
+/// [`BlinkAlloc`] is not thread-safe and thus is not `Sync`,
+/// while `#[global_allocator]` static must be `Sync`.
+/// To use it as global allocator wrap it into a type
+/// that implements `Sync` and forwards [`GlobalAlloc`](core::alloc::GlobalAlloc) methods.
+/// Implementing `Sync` for such wrapper is only sound
+/// if the program never allocates from more than one thread,
+/// which must be ensured by the user.
+/// Note that some standard library functions spawn threads internally.
+///
+/// Chunks must be allocated from allocator that doesn't
+/// use global allocator, e.g. [`System`](std::alloc::System),
+/// otherwise allocating new chunk would recurse.
+///
+/// Memory is never reclaimed as nothing resets the allocator,
+/// only the last allocation may be given back on deallocation.
+/// For a global allocator with resets see
+/// [`UnsafeGlobalBlinkAlloc`](crate::UnsafeGlobalBlinkAlloc).
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "std")] mod inner {
+/// use std::alloc::{GlobalAlloc, Layout, System};
+/// use blink_alloc::BlinkAlloc;
+///
+/// struct SingleThreaded(BlinkAlloc<System>);
+///
+/// // Safety: This program allocates from main thread only.
+/// unsafe impl Sync for SingleThreaded {}
+///
+/// unsafe impl GlobalAlloc for SingleThreaded {
+///     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+///         self.0.alloc(layout)
+///     }
+///
+///     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+///         self.0.dealloc(ptr, layout)
+///     }
+///
+///     unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+///         self.0.realloc(ptr, layout, new_size)
+///     }
+/// }
+///
+/// #[global_allocator]
+/// static GLOBAL_ALLOC: SingleThreaded = SingleThreaded(BlinkAlloc::new_in(System));
+///
+/// pub fn main() {
+///     let _ = Box::new(42);
+///     let _ = vec![1, 2, 3];
+/// }
+/// # }
+/// # #[cfg(feature = "std")] fn main() { inner::main() }
+/// # #[cfg(not(feature = "std"))] fn main() {}
+/// ```
+#[cfg(feature = "global-alloc")]
+unsafe impl<A> core::alloc::GlobalAlloc for BlinkAlloc<A>
+where
+    A: Allocator,
+{
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        match BlinkAlloc::allocate(self, layout) {
+            Ok(ptr) => ptr.as_ptr().cast(),
+            Err(_) => core::ptr::null_mut(),
+        }
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // Safety: `ptr` was allocated by this allocator with `layout`.
+        unsafe { BlinkAlloc::deallocate(self, NonNull::new_unchecked(ptr), layout.size()) }
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        match BlinkAlloc::allocate_zeroed(self, layout) {
+            Ok(ptr) => ptr.as_ptr().cast(),
+            Err(_) => core::ptr::null_mut(),
+        }
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let Ok(new_layout) = Layout::from_size_align(new_size, layout.align()) else {
+            return core::ptr::null_mut();
+        };
+
+        // Safety: `ptr` was allocated by this allocator with `layout`.
+        match unsafe { BlinkAlloc::resize(self, NonNull::new_unchecked(ptr), layout, new_layout) } {
+            Ok(ptr) => ptr.as_ptr().cast(),
+            Err(_) => core::ptr::null_mut(),
+        }
+    }
+}
+
 unsafe impl<A> BlinkAllocator for BlinkAlloc<A>
 where
     A: Allocator,
@@ -373,4 +2311,14 @@ where
     fn reset(&mut self) {
         BlinkAlloc::reset(self)
     }
+
+    #[inline(always)]
+    fn blink_stats(&self) -> Option<BlinkStats> {
+        Some(BlinkAlloc::stats(self))
+    }
+
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        BlinkAlloc::is_empty(self)
+    }
 }
@@ -0,0 +1,111 @@
+//! Exports allocator statistics through [`metrics`](::metrics) facade.
+//!
+//! Gauges `blink_alloc.chunks` and `blink_alloc.reserved_bytes`
+//! are updated when chunks are allocated or released and on reset.
+//! Counter `blink_alloc.resets` is incremented on each reset.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::cell::Cell;
+
+use crate::arena::BlinkStats;
+
+std::thread_local! {
+    static EXPORTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Clears export flag even if recorder panics.
+struct ExportGuard<'a>(&'a Cell<bool>);
+
+impl Drop for ExportGuard<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.0.set(false);
+    }
+}
+
+/// Calls `f` unless metrics are already being exported on this thread.
+///
+/// Recorder may allocate memory, possibly from the allocator
+/// that exports metrics when it is used as global allocator.
+/// Nested exports are skipped to avoid unbounded recursion.
+#[inline]
+fn export(f: impl FnOnce()) {
+    let _ = EXPORTING.try_with(|exporting| {
+        if exporting.replace(true) {
+            return;
+        }
+        let _guard = ExportGuard(exporting);
+        f();
+    });
+}
+
+/// Running chunk totals of an allocator.
+///
+/// Updated on chunk allocation without walking chunk lists,
+/// so allocation path does not depend on number of chunks and shards.
+/// Totals are recounted only when chunks are released.
+pub(crate) struct Gauges {
+    chunks: AtomicUsize,
+    capacity: AtomicUsize,
+}
+
+impl Gauges {
+    #[inline(always)]
+    pub(crate) const fn new() -> Self {
+        Gauges {
+            chunks: AtomicUsize::new(0),
+            capacity: AtomicUsize::new(0),
+        }
+    }
+
+    /// Accounts new chunk of `cap` bytes and reports totals.
+    #[inline]
+    pub(crate) fn add_chunk(&self, cap: usize) {
+        let chunks = self.chunks.fetch_add(1, Ordering::Relaxed) + 1;
+        let capacity = self.capacity.fetch_add(cap, Ordering::Relaxed) + cap;
+        export(|| set_gauges(chunks, capacity));
+    }
+
+    /// Accounts released chunks and reports totals.
+    ///
+    /// Chunk allocated concurrently may be released
+    /// before it is accounted, so reported totals saturate at zero.
+    #[cfg(feature = "sync")]
+    #[inline]
+    pub(crate) fn release(&self, stats: BlinkStats) {
+        let chunks = self.chunks.fetch_sub(stats.chunks, Ordering::Relaxed);
+        let chunks = chunks.saturating_sub(stats.chunks);
+        let capacity = self.capacity.fetch_sub(stats.capacity, Ordering::Relaxed);
+        let capacity = capacity.saturating_sub(stats.capacity);
+        export(|| set_gauges(chunks, capacity));
+    }
+
+    /// Replaces totals with recounted `stats` and reports them.
+    #[inline]
+    pub(crate) fn set(&self, stats: BlinkStats) {
+        self.store(stats);
+        export(|| set_gauges(stats.chunks, stats.capacity));
+    }
+
+    /// Replaces totals with recounted `stats` and reports allocator reset.
+    #[inline]
+    pub(crate) fn reset(&self, stats: BlinkStats) {
+        self.store(stats);
+        export(|| {
+            ::metrics::counter!("blink_alloc.resets").increment(1);
+            set_gauges(stats.chunks, stats.capacity);
+        });
+    }
+
+    #[inline(always)]
+    fn store(&self, stats: BlinkStats) {
+        self.chunks.store(stats.chunks, Ordering::Relaxed);
+        self.capacity.store(stats.capacity, Ordering::Relaxed);
+    }
+}
+
+#[inline]
+fn set_gauges(chunks: usize, capacity: usize) {
+    ::metrics::gauge!("blink_alloc.chunks").set(chunks as f64);
+    ::metrics::gauge!("blink_alloc.reserved_bytes").set(capacity as f64);
+}
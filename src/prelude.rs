@@ -0,0 +1,25 @@
+//! Re-exports commonly used types and traits.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] fn main() {
+//! use blink_alloc::prelude::*;
+//!
+//! let mut blink = FrameBlink::new();
+//! let slice = (0..4).collect_to_blink(&mut blink);
+//! assert_eq!(slice, [0, 1, 2, 3]);
+//! # }
+//! # #[cfg(not(feature = "alloc"))] fn main() {}
+//! ```
+
+pub use crate::{Blink, BlinkAlloc, BlinkAllocator, IteratorExt};
+
+#[cfg(feature = "alloc")]
+pub use crate::FrameBlink;
+
+#[cfg(feature = "sync")]
+pub use crate::{LocalBlinkAlloc, SyncBlinkAlloc};
+
+#[cfg(all(feature = "sync", feature = "alloc"))]
+pub use crate::{LocalFrameBlink, SyncFrameBlink};
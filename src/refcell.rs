@@ -0,0 +1,227 @@
+//! This module provides single-threaded blink allocator
+//! that can be reset through shared reference.
+
+use core::{
+    alloc::Layout,
+    cell::{Cell, RefCell},
+    ptr::NonNull,
+};
+
+use allocator_api2::alloc::{AllocError, Allocator};
+
+#[cfg(feature = "alloc")]
+use allocator_api2::alloc::Global;
+
+use crate::local::BlinkAlloc;
+
+switch_alloc_default! {
+    /// Single-threaded blink allocator with runtime-checked reset.
+    ///
+    /// Wraps [`BlinkAlloc`] into [`RefCell`] and counts live allocations.
+    /// Unlike [`BlinkAlloc::reset`], [`RefCellBlinkAlloc::reset`]
+    /// takes shared reference and panics if any allocation
+    /// made through [`Allocator`] trait is not yet deallocated.
+    ///
+    /// This trades compile-time borrow checking for runtime one.
+    /// Useful when allocator is shared between many places
+    /// and threading mutable reference for reset is impractical.
+    ///
+    /// Allocations that are never deallocated, e.g. leaked collections,
+    /// keep the allocator from being reset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::RefCellBlinkAlloc;
+    /// # use allocator_api2::vec::Vec;
+    /// let blink = RefCellBlinkAlloc::new();
+    /// let shared = &blink;
+    ///
+    /// let mut vec = Vec::new_in(shared);
+    /// vec.extend(0..10);
+    /// drop(vec);
+    ///
+    /// shared.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    pub struct RefCellBlinkAlloc<A: Allocator = +Global> {
+        blink: RefCell<BlinkAlloc<A>>,
+        live: Cell<usize>,
+    }
+}
+
+impl<A> Default for RefCellBlinkAlloc<A>
+where
+    A: Allocator + Default,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new_in(Default::default())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl RefCellBlinkAlloc<Global> {
+    /// Creates new blink allocator that uses global allocator
+    /// to allocate memory chunks.
+    ///
+    /// See [`RefCellBlinkAlloc::new_in`] for using custom allocator.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        RefCellBlinkAlloc::new_in(Global)
+    }
+
+    /// Creates new blink allocator that uses global allocator
+    /// to allocate memory chunks.
+    /// With this method you can specify initial chunk size.
+    #[inline(always)]
+    pub const fn with_chunk_size(chunk_size: usize) -> Self {
+        RefCellBlinkAlloc::from_blink(BlinkAlloc::with_chunk_size(chunk_size))
+    }
+}
+
+impl<A> RefCellBlinkAlloc<A>
+where
+    A: Allocator,
+{
+    /// Creates new blink allocator that uses provided allocator
+    /// to allocate memory chunks.
+    #[inline(always)]
+    pub const fn new_in(allocator: A) -> Self {
+        RefCellBlinkAlloc::from_blink(BlinkAlloc::new_in(allocator))
+    }
+
+    /// Wraps existing blink allocator.
+    #[inline(always)]
+    pub const fn from_blink(blink: BlinkAlloc<A>) -> Self {
+        RefCellBlinkAlloc {
+            blink: RefCell::new(blink),
+            live: Cell::new(0),
+        }
+    }
+
+    /// Returns number of allocations that are not yet deallocated.
+    #[inline(always)]
+    pub fn live_allocations(&self) -> usize {
+        self.live.get()
+    }
+
+    /// Allocates memory with specified layout from this allocator.
+    /// If needed it will allocate new chunk using underlying allocator.
+    /// If chunk allocation fails, it will return `Err`.
+    ///
+    /// Allocation is counted as live until deallocated.
+    #[inline(always)]
+    pub fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = self.blink.borrow().allocate(layout)?;
+        self.live.set(self.live.get() + 1);
+        Ok(ptr)
+    }
+
+    /// Resizes memory allocation.
+    /// Potentially happens in-place.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a pointer previously returned by [`allocate`](RefCellBlinkAlloc::allocate).
+    /// `old_size` must be in range `layout.size()..=slice.len()`
+    /// where `layout` is the layout used in the call to [`allocate`](RefCellBlinkAlloc::allocate).
+    /// and `slice` is the slice pointer returned by [`allocate`](RefCellBlinkAlloc::allocate).
+    ///
+    /// On success, the old pointer is invalidated and the new pointer is returned.
+    /// On error old allocation is still valid.
+    #[inline(always)]
+    pub unsafe fn resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        // Safety: Covered by this method's contract.
+        unsafe { self.blink.borrow().resize(ptr, old_layout, new_layout) }
+    }
+
+    /// Deallocates memory previously allocated from this allocator.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a pointer previously returned by [`allocate`](RefCellBlinkAlloc::allocate).
+    /// `size` must be in range `layout.size()..=slice.len()`
+    /// where `layout` is the layout used in the call to [`allocate`](RefCellBlinkAlloc::allocate).
+    /// and `slice` is the slice pointer returned by [`allocate`](RefCellBlinkAlloc::allocate).
+    #[inline(always)]
+    pub unsafe fn deallocate(&self, ptr: NonNull<u8>, size: usize) {
+        debug_assert_ne!(self.live.get(), 0);
+
+        // Safety: Covered by this method's contract.
+        unsafe { self.blink.borrow().deallocate(ptr, size) }
+        self.live.set(self.live.get() - 1);
+    }
+
+    /// Resets this allocator, deallocating all chunks except the last one.
+    /// Last chunk will be reused.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are live allocations.
+    #[inline]
+    #[track_caller]
+    pub fn reset(&self) {
+        assert_eq!(
+            self.live.get(),
+            0,
+            "Cannot reset `RefCellBlinkAlloc` while allocations are live"
+        );
+        self.blink.borrow_mut().reset();
+    }
+
+    /// Returns mutable reference to the wrapped blink allocator.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut BlinkAlloc<A> {
+        self.blink.get_mut()
+    }
+
+    /// Unwrap this allocator, returning the wrapped blink allocator.
+    #[inline(always)]
+    pub fn into_inner(self) -> BlinkAlloc<A> {
+        self.blink.into_inner()
+    }
+}
+
+unsafe impl<A> Allocator for RefCellBlinkAlloc<A>
+where
+    A: Allocator,
+{
+    #[inline(always)]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        RefCellBlinkAlloc::allocate(self, layout)
+    }
+
+    #[inline(always)]
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        RefCellBlinkAlloc::resize(self, ptr, old_layout, new_layout)
+    }
+
+    #[inline(always)]
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        RefCellBlinkAlloc::resize(self, ptr, old_layout, new_layout)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        RefCellBlinkAlloc::deallocate(self, ptr, layout.size());
+    }
+}
@@ -2,19 +2,21 @@
 
 use core::{
     alloc::Layout,
-    mem::ManuallyDrop,
+    mem::{ManuallyDrop, MaybeUninit},
     ptr::NonNull,
     sync::atomic::{AtomicUsize, Ordering},
 };
 
+use alloc::vec::Vec;
 use allocator_api2::alloc::{AllocError, Allocator};
+use parking_lot::Mutex;
 
 #[cfg(feature = "alloc")]
 use allocator_api2::alloc::Global;
 
 use crate::{
     api::BlinkAllocator,
-    arena::{ArenaLocal, ArenaSync},
+    arena::{ArenaLocal, ArenaShard, ArenaSync, BlinkStats, ChunkIter},
 };
 
 switch_alloc_default! {
@@ -57,6 +59,11 @@ switch_alloc_default! {
     /// this allocator. [`LocalBlinkAlloc`] will allocate chunks from this
     /// allocator, but is single-threaded by itself.
     ///
+    /// Every allocation takes [`RwLock`] for reading,
+    /// even if the allocator is only used from one thread.
+    /// Such code should allocate through [`LocalBlinkAlloc`] proxy
+    /// that takes the lock only to get a new chunk.
+    ///
     /// [`RwLock`]: parking_lot::RwLock
     /// [`AtomicUsize`]: core::sync::atomic::AtomicUsize
     /// [`BlinkAlloc`]: crate::local::BlinkAlloc
@@ -99,19 +106,104 @@ switch_alloc_default! {
     /// ```
     pub struct SyncBlinkAlloc<A: Allocator = +Global> {
         arena: ArenaSync,
+        // Additional arenas of sharded allocator.
+        shards: Vec<ArenaShard>,
         allocator: A,
         max_local_alloc: AtomicUsize,
+        local_spare: Mutex<ArenaLocal>,
+        min_align: usize,
+        epochs: Mutex<Epochs>,
+        #[cfg(any(test, feature = "allocation-tracking"))]
+        allocations: AtomicUsize,
+        #[cfg(feature = "metrics")]
+        gauges: crate::metrics::Gauges,
+    }
+}
+
+/// Epoch state of [`SyncBlinkAlloc`].
+struct Epochs {
+    current: u64,
+
+    // Number of live guards for each pinned epoch.
+    // Sorted by epoch.
+    pinned: Vec<(u64, usize)>,
+}
+
+impl Epochs {
+    const fn new() -> Self {
+        Epochs {
+            current: 0,
+            pinned: Vec::new(),
+        }
+    }
+
+    /// Returns oldest pinned epoch.
+    #[inline(always)]
+    fn oldest_pinned(&self) -> Option<u64> {
+        self.pinned.first().map(|&(epoch, _)| epoch)
+    }
+
+    #[inline]
+    fn pin(&mut self) -> u64 {
+        // Current epoch is never older than pinned ones.
+        match self.pinned.last_mut() {
+            Some((epoch, count)) if *epoch == self.current => *count += 1,
+            _ => self.pinned.push((self.current, 1)),
+        }
+        self.current
+    }
+
+    /// Returns `true` if oldest pinned epoch has changed.
+    #[inline]
+    fn unpin(&mut self, epoch: u64) -> bool {
+        let Ok(idx) = self
+            .pinned
+            .binary_search_by_key(&epoch, |&(epoch, _)| epoch)
+        else {
+            debug_assert!(false, "Epoch is not pinned");
+            return false;
+        };
+
+        let count = &mut self.pinned[idx].1;
+        *count -= 1;
+        if *count > 0 {
+            return false;
+        }
+
+        self.pinned.remove(idx);
+        idx == 0
     }
 }
 
 impl<A: Allocator> Drop for SyncBlinkAlloc<A> {
     fn drop(&mut self) {
+        self.local_spare.get_mut().reset_leak(false);
         unsafe {
             self.arena.reset(false, &self.allocator);
+            for shard in &mut self.shards {
+                shard.reset(false, &self.allocator);
+            }
         }
     }
 }
 
+/// Returns index of the current thread.
+///
+/// Threads are numbered in order they first call this function,
+/// so concurrently running threads tend to pick different shards.
+#[inline(always)]
+fn thread_index() -> usize {
+    static NEXT_INDEX: AtomicUsize = AtomicUsize::new(0);
+
+    std::thread_local! {
+        static INDEX: usize = NEXT_INDEX.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Thread-local may be already destroyed
+    // if allocation happens in thread-local destructor.
+    INDEX.try_with(|index| *index).unwrap_or(0)
+}
+
 #[test]
 fn check_sync() {
     fn for_sync_alloc<A: Allocator + Sync>() {
@@ -121,6 +213,19 @@ fn check_sync() {
     for_sync_alloc::<Global>();
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn check_local_with() {
+    let blink = SyncBlinkAlloc::new();
+    for size in [256, 1000, 4096, 100_000] {
+        for _ in 0..8 {
+            let local = blink.local_with(size);
+            local.allocate(Layout::new::<u8>()).unwrap();
+            assert!(local.arena.last_chunk_size() >= size);
+        }
+    }
+}
+
 impl<A> Default for SyncBlinkAlloc<A>
 where
     A: Allocator + Default,
@@ -141,6 +246,15 @@ impl SyncBlinkAlloc<Global> {
     pub const fn new() -> Self {
         SyncBlinkAlloc::new_in(Global)
     }
+
+    /// Creates new sharded blink allocator that uses global allocator
+    /// to allocate memory chunks.
+    ///
+    /// See [`SyncBlinkAlloc::new_sharded_in`] for details.
+    #[inline(always)]
+    pub fn new_sharded(shards: usize) -> Self {
+        SyncBlinkAlloc::new_sharded_in(shards, Global)
+    }
 }
 
 impl<A> SyncBlinkAlloc<A>
@@ -155,11 +269,81 @@ where
     pub const fn new_in(allocator: A) -> Self {
         SyncBlinkAlloc {
             arena: ArenaSync::new(),
+            shards: Vec::new(),
             allocator,
             max_local_alloc: AtomicUsize::new(0),
+            local_spare: Mutex::new(ArenaLocal::new()),
+            min_align: 1,
+            epochs: Mutex::new(Epochs::new()),
+            #[cfg(any(test, feature = "allocation-tracking"))]
+            allocations: AtomicUsize::new(0),
+            #[cfg(feature = "metrics")]
+            gauges: crate::metrics::Gauges::new(),
+        }
+    }
+
+    /// Creates new blink allocator with `shards` independent arenas
+    /// that uses provided allocator to allocate memory chunks.
+    ///
+    /// Each thread allocates from one of the arenas,
+    /// so concurrent allocations rarely contend on the same lock and cursor.
+    /// Threads are assigned to arenas in round-robin order,
+    /// so number of shards close to number of CPUs is a good choice.
+    ///
+    /// This trades memory for lower contention,
+    /// as every arena keeps its own chunk on [`reset`](SyncBlinkAlloc::reset).
+    /// Zero and one shard make regular allocator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::SyncBlinkAlloc;
+    /// # use allocator_api2::alloc::Global;
+    /// # use std::alloc::Layout;
+    /// let mut blink = SyncBlinkAlloc::new_sharded_in(4, Global);
+    ///
+    /// std::thread::scope(|scope| {
+    ///     for _ in 0..4 {
+    ///         scope.spawn(|| {
+    ///             blink.allocate(Layout::new::<[u8; 64]>()).unwrap();
+    ///         });
+    ///     }
+    /// });
+    /// assert!(blink.stats().used >= 256);
+    ///
+    /// blink.reset();
+    /// assert!(blink.is_empty());
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn new_sharded_in(shards: usize, allocator: A) -> Self {
+        let mut blink = SyncBlinkAlloc::new_in(allocator);
+        blink.shards = (1..shards).map(|_| ArenaShard(ArenaSync::new())).collect();
+        blink
+    }
+
+    /// Returns arena for allocations of the current thread.
+    #[inline(always)]
+    fn shard(&self) -> &ArenaSync {
+        if self.shards.is_empty() {
+            return &self.arena;
+        }
+
+        match thread_index() % (self.shards.len() + 1) {
+            0 => &self.arena,
+            idx => &self.shards[idx - 1],
         }
     }
 
+    /// Returns iterator over all arenas of this allocator.
+    #[inline(always)]
+    fn arenas(&self) -> impl Iterator<Item = &ArenaSync> {
+        core::iter::once(&self.arena).chain(self.shards.iter().map(|shard| &shard.0))
+    }
+
     /// Returns reference to the underlying allocator used by this blink allocator.
     #[inline(always)]
     pub const fn inner(&self) -> &A {
@@ -169,17 +353,85 @@ where
     /// Creates new blink allocator that uses global allocator
     /// to allocate memory chunks.
     /// With this method you can specify initial chunk size.
+    /// Sizes smaller than 64 bytes are bumped to 64 bytes
+    /// to avoid allocating many tiny chunks.
     ///
     /// See [`SyncBlinkAlloc::new_in`] for using custom allocator.
     #[inline(always)]
     pub const fn with_chunk_size_in(chunk_size: usize, allocator: A) -> Self {
         SyncBlinkAlloc {
             arena: ArenaSync::with_chunk_size(chunk_size),
+            shards: Vec::new(),
             allocator,
             max_local_alloc: AtomicUsize::new(0),
+            local_spare: Mutex::new(ArenaLocal::new()),
+            min_align: 1,
+            epochs: Mutex::new(Epochs::new()),
+            #[cfg(any(test, feature = "allocation-tracking"))]
+            allocations: AtomicUsize::new(0),
+            #[cfg(feature = "metrics")]
+            gauges: crate::metrics::Gauges::new(),
         }
     }
 
+    /// Makes this allocator align all allocations to the
+    /// [cache line size](crate::CACHE_LINE_SIZE).
+    ///
+    /// Allocations made from different threads never share cache line
+    /// and so do not suffer from false sharing.
+    /// Useful for lock-free data structures placed in the arena.
+    ///
+    /// This does not affect allocations made through
+    /// [`LocalBlinkAlloc`] proxies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::SyncBlinkAlloc;
+    /// let blink = SyncBlinkAlloc::new().with_cacheline_align();
+    /// let ptr = blink.allocate(std::alloc::Layout::new::<u8>()).unwrap();
+    /// assert_eq!(ptr.as_ptr() as *mut u8 as usize % 64, 0);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub const fn with_cacheline_align(self) -> Self {
+        self.with_min_align(crate::CACHE_LINE_SIZE)
+    }
+
+    /// Sets minimum alignment of all allocations made from this allocator.
+    ///
+    /// Layouts with smaller alignment are aligned to `min_align`.
+    /// Useful for SIMD workloads where all allocations
+    /// should be aligned to vector width.
+    ///
+    /// This does not affect allocations made through
+    /// [`LocalBlinkAlloc`] proxies.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_align` is not a power of two.
+    #[inline(always)]
+    pub const fn with_min_align(mut self, min_align: usize) -> Self {
+        assert!(
+            min_align.is_power_of_two(),
+            "Minimum alignment must be a power of two"
+        );
+        self.min_align = min_align;
+        self
+    }
+
+    /// Increases alignment of the layout up to the minimum alignment
+    /// of this allocator.
+    #[inline(always)]
+    fn min_aligned(&self, layout: Layout) -> Result<Layout, AllocError> {
+        if layout.align() >= self.min_align {
+            return Ok(layout);
+        }
+        layout.align_to(self.min_align).map_err(|_| AllocError)
+    }
+
     /// Creates a new thread-local blink allocator proxy
     /// that borrows from this multi-threaded allocator.
     ///
@@ -193,6 +445,11 @@ where
     /// Create a local allocator for each thread/task.
     /// Reset after all threads/tasks are finished.
     ///
+    /// When proxy is dropped, its last chunk is reset and kept
+    /// for the next proxy, which reuses the same memory.
+    /// Memory allocated through the proxy must not be used
+    /// after the proxy is dropped.
+    ///
     /// # Examples
     ///
     /// ```
@@ -218,24 +475,381 @@ where
     /// # #[cfg(not(feature = "alloc"))] fn main() {}
     /// ```
     #[inline(always)]
-    pub fn local(&self) -> LocalBlinkAlloc<A> {
+    pub fn local(&self) -> LocalBlinkAlloc<'_, A> {
+        self.local_with(self.max_local_alloc.load(Ordering::Relaxed))
+    }
+
+    /// Creates a new thread-local blink allocator proxy
+    /// that borrows from this multi-threaded allocator
+    /// with specified starting chunk size.
+    ///
+    /// Unlike [`SyncBlinkAlloc::local`] this does not consult
+    /// maximum local allocation size recorded by previous proxies.
+    /// Useful for callers who know their per-task memory footprint.
+    ///
+    /// Same as with [`SyncBlinkAlloc::local`], memory of the dropped proxy
+    /// is reused by the next one and must not be used after the proxy is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "nightly", feature(allocator_api))]
+    /// # use blink_alloc::SyncBlinkAlloc;
+    /// # use allocator_api2::vec::Vec;
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// let mut blink = SyncBlinkAlloc::new();
+    /// for i in 0..16 {
+    ///     let blink = blink.local_with(4096);
+    ///     let mut vec = Vec::new_in(&blink);
+    ///     vec.extend(0..i * 32);
+    /// }
+    /// blink.reset();
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline(always)]
+    pub fn local_with(&self, chunk_size: usize) -> LocalBlinkAlloc<'_, A> {
+        let mut arena = ArenaLocal::with_chunk_size(chunk_size);
+
+        // Reuse chunk donated by dropped proxy if it is large enough.
+        if let Some(mut spare) = self.local_spare.try_lock() {
+            if spare.last_chunk_size() >= chunk_size {
+                // Safety: Proxy that allocated from the spare chunk is dropped.
+                // Both arenas allocate chunks from this allocator.
+                unsafe { arena.steal_last_chunk(&mut spare) };
+            }
+        }
+
         LocalBlinkAlloc {
-            arena: ArenaLocal::with_chunk_size(self.max_local_alloc.load(Ordering::Relaxed)),
+            arena,
             shared: self,
         }
     }
 
+    /// Keeps the last chunk of dropped proxy for reuse by the next proxy.
+    #[inline]
+    fn donate_local_chunk(&self, arena: &mut ArenaLocal) {
+        if let Some(mut spare) = self.local_spare.try_lock() {
+            if spare.last_chunk_size() < arena.last_chunk_size() {
+                // Smaller spare chunk stays in this allocator until reset.
+                spare.reset_leak(false);
+
+                // Safety: Proxy that allocated from the chunk is being dropped.
+                // Both arenas allocate chunks from this allocator.
+                unsafe { spare.steal_last_chunk(arena) };
+            }
+        }
+    }
+
+    /// Checks if `ptr` points into memory of chunks owned by this allocator.
+    #[inline]
+    pub(crate) fn owns(&self, ptr: *const u8) -> bool {
+        self.arenas().any(|arena| arena.owns(ptr))
+    }
+
+    /// Checks if `ptr` points into memory of the last chunk of any arena.
+    /// Unlike [`owns`](SyncBlinkAlloc::owns) this does not walk chunk lists.
+    #[inline]
+    pub(crate) fn last_chunk_owns(&self, ptr: *const u8) -> bool {
+        self.arenas().any(|arena| arena.last_chunk_owns(ptr))
+    }
+
+    /// Returns capacity of the most recently allocated chunk.
+    /// Returns 0 if allocator has no chunks.
+    ///
+    /// For sharded allocator this reports chunk of the arena
+    /// used by the current thread.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::SyncBlinkAlloc;
+    /// let blink = SyncBlinkAlloc::new();
+    /// assert_eq!(blink.last_chunk_cap(), 0);
+    ///
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 1024]>()).unwrap();
+    /// assert!(blink.last_chunk_cap() >= 1024);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn last_chunk_cap(&self) -> usize {
+        self.shard().last_chunk_size()
+    }
+
+    /// Returns snapshot of memory statistics of this allocator.
+    ///
+    /// Chunk chain is read under the lock,
+    /// so snapshot is consistent even when other threads allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::SyncBlinkAlloc;
+    /// let blink = SyncBlinkAlloc::new();
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 1024]>()).unwrap();
+    ///
+    /// let stats = blink.stats();
+    /// assert_eq!(stats.chunks, 1);
+    /// assert!(stats.used >= 1024);
+    /// assert!(stats.capacity >= stats.used);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn stats(&self) -> BlinkStats {
+        self.arenas()
+            .map(ArenaSync::stats)
+            .fold(BlinkStats::default(), |total, stats| BlinkStats {
+                chunks: total.chunks + stats.chunks,
+                capacity: total.capacity + stats.capacity,
+                used: total.used + stats.used,
+            })
+    }
+
+    /// Returns `true` if nothing is allocated from this allocator
+    /// since creation or last reset.
+    ///
+    /// Chunks of [`LocalBlinkAlloc`] proxies are allocated from this allocator,
+    /// so it is not empty once any proxy allocated a chunk.
+    ///
+    /// Allocator may still keep a chunk for reuse,
+    /// check [`last_chunk_cap`](SyncBlinkAlloc::last_chunk_cap)
+    /// to tell fresh allocator from reset one.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.arenas().all(ArenaSync::is_empty)
+    }
+
+    /// Returns iterator over memory chunks owned by this allocator.
+    /// Chunks are listed from the newest to the oldest.
+    ///
+    /// Iterator holds read lock of the arena it walks.
+    /// Allocations that need new chunk, [`reset_epoch`](SyncBlinkAlloc::reset_epoch)
+    /// and dropping [`EpochGuard`] wait until iterator moves past that arena,
+    /// so they must not be done on the same thread while iterator is alive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::SyncBlinkAlloc;
+    /// let blink = SyncBlinkAlloc::new();
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 1024]>()).unwrap();
+    ///
+    /// for chunk in blink.chunks() {
+    ///     assert!(chunk.used() <= chunk.cap());
+    /// }
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn chunks(&self) -> ChunkIter<'_> {
+        self.arena.chunks_with(&self.shards)
+    }
+
+    /// Calls `f` with base pointer and number of used bytes
+    /// of each chunk that has at least one byte allocated.
+    /// Chunks are visited from the newest to the oldest.
+    ///
+    /// Unlike [`chunks`](SyncBlinkAlloc::chunks) this yields only used prefix
+    /// of each chunk, which covers all live allocations,
+    /// including chunks of [`LocalBlinkAlloc`] proxies.
+    ///
+    /// Chunk chain is walked under the read lock.
+    /// `f` must not allocate from this allocator,
+    /// as allocating new chunk would deadlock.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::SyncBlinkAlloc;
+    /// let blink = SyncBlinkAlloc::new();
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 16]>()).unwrap();
+    ///
+    /// let mut used = 0;
+    /// blink.for_each_used_byte_range(|_, len| used += len);
+    /// assert_eq!(used, 16);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn for_each_used_byte_range(&self, mut f: impl FnMut(*const u8, usize)) {
+        for arena in self.arenas() {
+            arena.for_each_used_byte_range(&mut f)
+        }
+    }
+
+    /// Calls `f` with memory of each chunk and number of used bytes in it.
+    /// Chunks are visited from the newest to the oldest.
+    ///
+    /// `chunk_bytes` covers usable memory of the chunk,
+    /// starting from [`ChunkInfo::base`](crate::ChunkInfo::base).
+    /// `used_bytes <= chunk_bytes.len()` always holds,
+    /// and `chunk_bytes[..used_bytes]` covers all allocations from the chunk.
+    /// Bytes are not guaranteed to be initialized,
+    /// e.g. alignment padding and memory above `used_bytes` may be uninitialized.
+    ///
+    /// Takes mutable borrow so that no allocation is mutably borrowed
+    /// while `f` inspects chunk memory.
+    /// This also guarantees that no [`LocalBlinkAlloc`] proxy is alive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::SyncBlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let mut blink = SyncBlinkAlloc::new();
+    /// let ptr = blink.allocate(Layout::new::<[u8; 16]>()).unwrap();
+    /// unsafe { ptr.cast::<[u8; 16]>().as_ptr().write([1; 16]) };
+    ///
+    /// let mut sum = 0;
+    /// blink.for_each_chunk(|chunk_bytes, used_bytes| {
+    ///     assert!(used_bytes <= chunk_bytes.len());
+    ///     let used = &chunk_bytes[..used_bytes];
+    ///     // Safety: All used bytes are written above.
+    ///     sum += used.iter().map(|b| unsafe { b.assume_init() } as u32).sum::<u32>();
+    /// });
+    /// assert_eq!(sum, 16);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn for_each_chunk<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&[MaybeUninit<u8>], usize),
+    {
+        for chunk in self.chunks() {
+            // Safety: Chunk memory is valid for `cap` bytes
+            // and is not mutably borrowed while `self` is borrowed mutably.
+            let chunk_bytes = unsafe {
+                core::slice::from_raw_parts(
+                    chunk.base().as_ptr().cast::<MaybeUninit<u8>>(),
+                    chunk.cap(),
+                )
+            };
+            f(chunk_bytes, chunk.used());
+        }
+    }
+
+    /// Pushes capacity of each chunk owned by this allocator into `sizes`.
+    /// Chunks are listed from the newest to the oldest.
+    ///
+    /// Useful to diagnose arena growth patterns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::SyncBlinkAlloc;
+    /// let blink = SyncBlinkAlloc::new();
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 1024]>()).unwrap();
+    /// blink.allocate(std::alloc::Layout::new::<[u8; 4096]>()).unwrap();
+    ///
+    /// let mut sizes = Vec::new();
+    /// blink.collect_chunk_sizes(&mut sizes);
+    /// assert_eq!(sizes.len(), 2);
+    /// assert!(sizes[0] > sizes[1]);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn collect_chunk_sizes(&self, sizes: &mut alloc::vec::Vec<usize>) {
+        for arena in self.arenas() {
+            arena.collect_chunk_sizes(sizes)
+        }
+    }
+
     /// Allocates memory with specified layout from this allocator.
     /// If needed it will allocate new chunk using underlying allocator.
     /// If chunk allocation fails, it will return `Err`.
+    ///
+    /// Zero-sized allocations never fail. They return dangling pointer
+    /// aligned to `layout.align()` without allocating chunks
+    /// or consuming chunk memory.
     #[inline(always)]
     pub fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = self.allocate_untracked(layout)?;
+
+        self.shard().count_allocated(layout.size());
+
+        #[cfg(any(test, feature = "allocation-tracking"))]
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+
+        Ok(ptr)
+    }
+
+    /// Allocates memory without updating allocation counters.
+    /// Used directly for chunks of [`LocalBlinkAlloc`] proxies.
+    #[inline(always)]
+    fn allocate_untracked(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let layout = self.min_aligned(layout)?;
+
+        let arena = self.shard();
+
         // Safety:
         // Same instance is used for all allocations and resets.
-        if let Some(ptr) = unsafe { self.arena.alloc_fast(layout) } {
-            return Ok(ptr);
+        match unsafe { arena.alloc_fast(layout) } {
+            Some(ptr) => Ok(ptr),
+            None => {
+                let (ptr, _cap) = unsafe { arena.alloc_slow(layout, &self.allocator)? };
+                #[cfg(feature = "metrics")]
+                self.gauges.add_chunk(_cap);
+                Ok(ptr)
+            }
         }
-        unsafe { self.arena.alloc_slow(layout, &self.allocator) }
+    }
+
+    /// Allocates zero-initialized memory for `count` values with specified layout
+    /// in one contiguous block.
+    ///
+    /// Values are placed at `layout.pad_to_align().size()` stride,
+    /// which is `layout.size()` for layouts of Rust types.
+    /// Block is aligned to `layout.align()`.
+    ///
+    /// Takes arena lock once instead of once per value.
+    /// Returns `Err` if size of the block overflows
+    /// or if chunk allocation fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::SyncBlinkAlloc;
+    /// # use std::alloc::Layout;
+    /// let blink = SyncBlinkAlloc::new();
+    /// let layout = Layout::new::<u64>();
+    /// let block = blink.allocate_zeroed_batch(10, layout).unwrap();
+    /// assert!(block.len() >= 80);
+    ///
+    /// let first = block.cast::<u64>();
+    /// for i in 0..10 {
+    ///     assert_eq!(unsafe { first.as_ptr().add(i).read() }, 0);
+    /// }
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub fn allocate_zeroed_batch(
+        &self,
+        count: usize,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let stride = layout.pad_to_align().size();
+        let size = stride.checked_mul(count).ok_or(AllocError)?;
+        let layout = Layout::from_size_align(size, layout.align()).map_err(|_| AllocError)?;
+        let ptr = self.allocate(layout)?;
+
+        // Safety: `ptr` points to allocation of at least `size` bytes.
+        unsafe {
+            core::ptr::write_bytes(ptr.as_ptr().cast::<u8>(), 0, size);
+        }
+        Ok(ptr)
     }
 
     /// Resizes memory allocation.
@@ -257,17 +871,31 @@ where
         old_layout: Layout,
         new_layout: Layout,
     ) -> Result<NonNull<[u8]>, AllocError> {
-        if let Some(ptr) = unsafe { self.arena.resize_fast(ptr, old_layout, new_layout) } {
-            return Ok(ptr);
-        }
+        let old_layout = self.min_aligned(old_layout)?;
+        let new_layout = self.min_aligned(new_layout)?;
 
-        // Safety:
-        // Same instance is used for all allocations and resets.
-        // `ptr` was allocated by this allocator.
-        unsafe {
-            self.arena
-                .resize_slow(ptr, old_layout, new_layout, &self.allocator)
-        }
+        // `ptr` may be allocated from another shard.
+        // Resizing in place succeeds only for the last allocation
+        // of the current thread's arena.
+        let arena = self.shard();
+
+        let new_ptr = match unsafe { arena.resize_fast(ptr, old_layout, new_layout) } {
+            Some(ptr) => ptr,
+            None => {
+                // Safety:
+                // Same instance is used for all allocations and resets.
+                // `ptr` was allocated by this allocator.
+                let (new_ptr, _cap) =
+                    unsafe { arena.resize_slow(ptr, old_layout, new_layout, &self.allocator)? };
+                #[cfg(feature = "metrics")]
+                self.gauges.add_chunk(_cap);
+                new_ptr
+            }
+        };
+
+        let grown = new_layout.size().saturating_sub(old_layout.size());
+        arena.count_allocated(grown);
+        Ok(new_ptr)
     }
 
     /// Deallocates memory previously allocated from this allocator.
@@ -286,8 +914,75 @@ where
         // Safety:
         // `ptr` was allocated by this allocator.
         unsafe {
-            self.arena.dealloc(ptr, size);
+            self.deallocate_untracked(ptr, size);
         }
+
+        // Never wraps below zero, even if deallocations
+        // are not paired with allocations.
+        #[cfg(any(test, feature = "allocation-tracking"))]
+        let _ = self
+            .allocations
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                count.checked_sub(1)
+            });
+    }
+
+    /// Deallocates memory without updating allocation counters.
+    /// Used directly for chunks of [`LocalBlinkAlloc`] proxies.
+    ///
+    /// # Safety
+    ///
+    /// See [`deallocate`](SyncBlinkAlloc::deallocate).
+    #[inline(always)]
+    unsafe fn deallocate_untracked(&self, ptr: NonNull<u8>, size: usize) {
+        // Safety:
+        // `ptr` was allocated by this allocator.
+        // Memory is reclaimed only if it is the last allocation
+        // of the current thread's arena.
+        unsafe {
+            self.shard().dealloc(ptr, size);
+        }
+    }
+
+    /// Returns number of bytes requested from this allocator since last reset.
+    ///
+    /// Counts sizes of allocation layouts and growth of resized allocations.
+    /// Unlike [`stats`](SyncBlinkAlloc::stats) that reports memory reserved in chunks,
+    /// this reports memory actually asked for.
+    /// Difference between the two is memory lost to fragmentation and padding,
+    /// plus chunks of [`LocalBlinkAlloc`] proxies.
+    ///
+    /// Allocations made through [`LocalBlinkAlloc`] proxies
+    /// and their chunks are not counted.
+    #[inline(always)]
+    pub fn allocated_since_reset(&self) -> usize {
+        self.arenas().map(ArenaSync::allocated).sum()
+    }
+
+    /// Returns number of allocations made since last reset
+    /// minus number of deallocations.
+    ///
+    /// Useful in tests to check that all allocations are accounted for.
+    ///
+    /// Only available with "allocation-tracking" feature.
+    #[cfg(any(test, feature = "allocation-tracking"))]
+    #[inline(always)]
+    pub fn current_allocation_count(&self) -> usize {
+        self.allocations.load(Ordering::Relaxed)
+    }
+
+    /// Resets this allocator if all allocations were deallocated.
+    /// Returns `true` if allocator was reset.
+    ///
+    /// Only available with "allocation-tracking" feature.
+    #[cfg(any(test, feature = "allocation-tracking"))]
+    #[inline(always)]
+    pub fn try_reset_if_count_is_zero(&mut self) -> bool {
+        if *self.allocations.get_mut() != 0 {
+            return false;
+        }
+        self.reset();
+        true
     }
 
     /// Resets this allocator, deallocating all chunks except the last one.
@@ -296,20 +991,48 @@ where
     /// one chunk should be sufficient for all allocations between resets.
     #[inline(always)]
     pub fn reset(&mut self) {
+        // Spare proxy chunk is allocated from chunks being reset.
+        self.local_spare.get_mut().reset_leak(false);
+
         // Safety:
         // Same instance is used for all allocations and resets.
         unsafe {
             self.arena.reset(true, &self.allocator);
+            for shard in &mut self.shards {
+                shard.reset(true, &self.allocator);
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        self.gauges.reset(self.stats());
+
+        #[cfg(any(test, feature = "allocation-tracking"))]
+        {
+            *self.allocations.get_mut() = 0;
         }
     }
 
     /// Resets this allocator, deallocating all chunks.
     #[inline(always)]
     pub fn reset_final(&mut self) {
+        // Spare proxy chunk is allocated from chunks being reset.
+        self.local_spare.get_mut().reset_leak(false);
+
         // Safety:
         // Same instance is used for all allocations and resets.
         unsafe {
             self.arena.reset(false, &self.allocator);
+            for shard in &mut self.shards {
+                shard.reset(false, &self.allocator);
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        self.gauges.reset(self.stats());
+
+        #[cfg(any(test, feature = "allocation-tracking"))]
+        {
+            *self.allocations.get_mut() = 0;
         }
     }
 
@@ -327,10 +1050,111 @@ where
     /// that allocated memory won't be used after reset.
     #[inline(always)]
     pub unsafe fn reset_unchecked(&self) {
+        // Spare proxy chunk is allocated from chunks being reset.
+        self.local_spare.lock().reset_leak(false);
+
         // Safety:
         // Same instance is used for all allocations and resets.
         unsafe {
-            self.arena.reset_unchecked(true, &self.allocator);
+            for arena in self.arenas() {
+                arena.reset_unchecked(true, &self.allocator);
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        self.gauges.reset(self.stats());
+
+        #[cfg(any(test, feature = "allocation-tracking"))]
+        self.allocations.store(0, Ordering::Relaxed);
+    }
+
+    /// Returns current epoch of this allocator.
+    ///
+    /// Epoch starts at zero and is advanced by
+    /// [`reset_epoch`](SyncBlinkAlloc::reset_epoch).
+    #[inline]
+    pub fn epoch(&self) -> u64 {
+        self.epochs.lock().current
+    }
+
+    /// Pins current epoch.
+    ///
+    /// Chunks retired by [`reset_epoch`](SyncBlinkAlloc::reset_epoch)
+    /// are not deallocated while guards of older epochs are alive.
+    /// Dropping the last guard of the oldest pinned epoch
+    /// deallocates chunks that are not needed anymore.
+    #[inline]
+    pub fn pin_epoch(&self) -> EpochGuard<'_, A> {
+        let epoch = self.epochs.lock().pin();
+        EpochGuard { blink: self, epoch }
+    }
+
+    /// Resets this allocator through shared reference
+    /// using epoch-based reclamation.
+    ///
+    /// If `epoch` is newer than current epoch,
+    /// all chunks are retired and current epoch is advanced to `epoch`.
+    /// Allocations made afterwards are served from new chunks.
+    /// Retired chunks are deallocated once no guard
+    /// returned by [`pin_epoch`](SyncBlinkAlloc::pin_epoch)
+    /// is alive for an epoch older than `epoch`.
+    ///
+    /// Otherwise only deallocates previously retired chunks
+    /// that are not needed anymore.
+    ///
+    /// # Safety
+    ///
+    /// If `epoch` is newer than current epoch,
+    /// memory allocated before this call must be used only by threads
+    /// that pinned an epoch before this call,
+    /// and only while their guards are alive.
+    ///
+    /// No [`LocalBlinkAlloc`] proxies may be alive during this call.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] fn main() {
+    /// # use blink_alloc::SyncBlinkAlloc;
+    /// let blink = SyncBlinkAlloc::new();
+    ///
+    /// let guard = blink.pin_epoch();
+    /// let ptr = blink.allocate(std::alloc::Layout::new::<u32>()).unwrap();
+    /// unsafe { ptr.as_ptr().cast::<u32>().write(42) };
+    ///
+    /// unsafe { blink.reset_epoch(blink.epoch() + 1) };
+    ///
+    /// // Memory is still valid while the guard is alive.
+    /// assert_eq!(unsafe { ptr.as_ptr().cast::<u32>().read() }, 42);
+    ///
+    /// // Retired chunks are deallocated here.
+    /// drop(guard);
+    /// # }
+    /// # #[cfg(not(feature = "alloc"))] fn main() {}
+    /// ```
+    #[inline]
+    pub unsafe fn reset_epoch(&self, epoch: u64) {
+        let mut epochs = self.epochs.lock();
+
+        if epoch > epochs.current {
+            // Spare proxy chunk is allocated from chunks being retired.
+            self.local_spare.lock().reset_leak(false);
+
+            for arena in self.arenas() {
+                let _retired = arena.retire(epoch);
+                #[cfg(feature = "metrics")]
+                self.gauges.release(_retired);
+            }
+            epochs.current = epoch;
+        }
+
+        // Safety:
+        // Guards of epochs older than retirement epoch keep chunks alive.
+        // Same instance is used for all allocations and resets.
+        unsafe {
+            for arena in self.arenas() {
+                arena.reclaim(epochs.oldest_pinned(), &self.allocator);
+            }
         }
     }
 
@@ -391,6 +1215,8 @@ where
     }
 }
 
+// Core provides blanket implementation for `&mut A` on nightly.
+#[cfg(not(feature = "nightly"))]
 unsafe impl<A> Allocator for &mut SyncBlinkAlloc<A>
 where
     A: Allocator,
@@ -439,6 +1265,47 @@ where
     fn reset(&mut self) {
         SyncBlinkAlloc::reset(self)
     }
+
+    #[inline(always)]
+    fn blink_stats(&self) -> Option<BlinkStats> {
+        Some(SyncBlinkAlloc::stats(self))
+    }
+
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        SyncBlinkAlloc::is_empty(self)
+    }
+}
+
+/// Guard that pins epoch of [`SyncBlinkAlloc`].
+///
+/// Returned by [`SyncBlinkAlloc::pin_epoch`].
+/// While alive, chunks retired by [`SyncBlinkAlloc::reset_epoch`]
+/// with a newer epoch are not deallocated.
+pub struct EpochGuard<'a, A: Allocator> {
+    blink: &'a SyncBlinkAlloc<A>,
+    epoch: u64,
+}
+
+impl<A> Drop for EpochGuard<'_, A>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn drop(&mut self) {
+        let mut epochs = self.blink.epochs.lock();
+
+        if epochs.unpin(self.epoch) {
+            // Safety:
+            // Guards of epochs older than retirement epoch keep chunks alive.
+            // Same instance is used for all allocations and resets.
+            unsafe {
+                for arena in self.blink.arenas() {
+                    arena.reclaim(epochs.oldest_pinned(), &self.blink.allocator);
+                }
+            }
+        }
+    }
 }
 
 switch_alloc_default! {
@@ -447,6 +1314,15 @@ switch_alloc_default! {
     /// Using proxy can yield better performance when
     /// it is possible to create proxy once to use for many allocations.
     ///
+    /// When dropped, proxy donates its last chunk to the [`SyncBlinkAlloc`]
+    /// to be reused by the next proxy. Memory allocated through the proxy
+    /// must not be used after the proxy is dropped.
+    ///
+    /// Proxy chunks are allocated from the [`SyncBlinkAlloc`] itself,
+    /// not from its underlying allocator.
+    /// They are freed by reset of the [`SyncBlinkAlloc`]
+    /// even if the proxy is leaked or dropped during unwinding.
+    ///
     /// See [`SyncBlinkAlloc::local`] for more details.
     pub struct LocalBlinkAlloc<'a, A: Allocator = +Global> {
         arena: ArenaLocal,
@@ -454,13 +1330,39 @@ switch_alloc_default! {
     }
 }
 
+/// Allocator of [`LocalBlinkAlloc`] chunks.
+///
+/// Proxy chunks are never deallocated one by one,
+/// so they bypass allocation counters of the shared allocator.
+struct ProxyChunks<'a, A: Allocator>(&'a SyncBlinkAlloc<A>);
+
+unsafe impl<A> Allocator for ProxyChunks<'_, A>
+where
+    A: Allocator,
+{
+    #[inline(always)]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.allocate_untracked(layout)
+    }
+
+    #[inline(always)]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // Safety: `ptr` is a proxy chunk allocated from shared allocator.
+        unsafe { self.0.deallocate_untracked(ptr, layout.size()) }
+    }
+}
+
 impl<A> Drop for LocalBlinkAlloc<'_, A>
 where
     A: Allocator,
 {
     fn drop(&mut self) {
+        // Nothing here may panic, this is called during unwinding too.
+        // Chunks not donated are owned by the shared arena
+        // and freed on its reset.
         self.shared
             .update_max_local_alloc(self.arena.last_chunk_size());
+        self.shared.donate_local_chunk(&mut self.arena);
         self.arena.reset_leak(false);
     }
 }
@@ -479,7 +1381,7 @@ where
         if let Some(ptr) = unsafe { self.arena.alloc_fast(layout) } {
             return Ok(ptr);
         }
-        unsafe { self.arena.alloc_slow(layout, self.shared) }
+        unsafe { self.arena.alloc_slow(layout, ProxyChunks(self.shared)) }
     }
 
     /// Resizes memory allocation.
@@ -510,7 +1412,7 @@ where
         // `ptr` was allocated by this allocator.
         unsafe {
             self.arena
-                .resize_slow(ptr, old_layout, new_layout, self.shared)
+                .resize_slow(ptr, old_layout, new_layout, ProxyChunks(self.shared))
         }
     }
 
@@ -545,6 +1447,13 @@ where
         self.arena.reset_leak(true);
     }
 
+    /// Forgets all chunks without accessing them.
+    /// Used when chunks were freed by reset of the shared allocator.
+    #[inline(always)]
+    pub(crate) fn forget_chunks(&mut self) {
+        self.arena.reset_leak(false);
+    }
+
     /// Resets this allocator, deallocating all chunks except the last one.
     /// Last chunk will be reused.
     /// With steady memory usage after few iterations
@@ -562,9 +1471,38 @@ where
         // Safety:
         // Same instance is used for all allocations and resets.
         unsafe {
-            self.arena.reset_unchecked(true, self.shared);
+            self.arena.reset_unchecked(true, ProxyChunks(self.shared));
         }
     }
+
+    /// Returns memory usage statistics of chunks owned by this proxy.
+    #[inline]
+    pub fn stats(&self) -> BlinkStats {
+        self.arena.stats()
+    }
+
+    /// Returns `true` if nothing is allocated from this proxy
+    /// since creation or last reset.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.arena.is_empty()
+    }
+}
+
+/// Zeroes bytes of the memory block starting from `from`.
+///
+/// # Safety
+///
+/// `ptr` must be valid for writes and `from` must not exceed its length.
+#[inline(always)]
+unsafe fn zero_tail(ptr: NonNull<[u8]>, from: usize) {
+    let len = ptr.len();
+    debug_assert!(from <= len);
+
+    // Safety: Covered by this function's contract.
+    unsafe {
+        core::ptr::write_bytes(ptr.as_ptr().cast::<u8>().add(from), 0, len - from);
+    }
 }
 
 unsafe impl<A> Allocator for LocalBlinkAlloc<'_, A>
@@ -596,12 +1534,26 @@ where
         LocalBlinkAlloc::resize(self, ptr, old_layout, new_layout)
     }
 
+    #[inline(always)]
+    unsafe fn grow_zeroed(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let new_ptr = LocalBlinkAlloc::resize(self, ptr, old_layout, new_layout)?;
+        zero_tail(new_ptr, old_layout.size());
+        Ok(new_ptr)
+    }
+
     #[inline(always)]
     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
         LocalBlinkAlloc::deallocate(self, ptr, layout.size())
     }
 }
 
+// Core provides blanket implementation for `&mut A` on nightly.
+#[cfg(not(feature = "nightly"))]
 unsafe impl<A> Allocator for &mut LocalBlinkAlloc<'_, A>
 where
     A: Allocator,
@@ -636,6 +1588,18 @@ where
         LocalBlinkAlloc::resize(self, ptr, old_layout, new_layout)
     }
 
+    #[inline(always)]
+    unsafe fn grow_zeroed(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        let new_ptr = LocalBlinkAlloc::resize(self, ptr, old_layout, new_layout)?;
+        zero_tail(new_ptr, old_layout.size());
+        Ok(new_ptr)
+    }
+
     #[inline(always)]
     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
         LocalBlinkAlloc::deallocate(self, ptr, layout.size())
@@ -650,4 +1614,14 @@ where
     fn reset(&mut self) {
         LocalBlinkAlloc::reset(self)
     }
+
+    #[inline(always)]
+    fn blink_stats(&self) -> Option<BlinkStats> {
+        Some(LocalBlinkAlloc::stats(self))
+    }
+
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        LocalBlinkAlloc::is_empty(self)
+    }
 }
@@ -1,6 +1,12 @@
 #![cfg(feature = "alloc")]
 
-use core::{alloc::Layout, cell::Cell, mem::size_of, ptr::NonNull};
+use core::{
+    alloc::Layout,
+    cell::Cell,
+    mem::{align_of, size_of},
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 use allocator_api2::{
     alloc::{AllocError, Allocator, Global},
@@ -9,6 +15,72 @@ use allocator_api2::{
 
 use crate::{blink::Blink, local::BlinkAlloc};
 
+/// Allocator that never has memory.
+struct NoMemory;
+
+unsafe impl Allocator for NoMemory {
+    fn allocate(&self, _layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        Err(AllocError)
+    }
+
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
+        unreachable!()
+    }
+}
+
+/// Allocator that forwards to `Global` and counts calls.
+/// Used by reference to inspect counters while in use.
+struct CountingGlobal {
+    allocations: AtomicUsize,
+    deallocations: AtomicUsize,
+}
+
+impl CountingGlobal {
+    const fn new() -> Self {
+        CountingGlobal {
+            allocations: AtomicUsize::new(0),
+            deallocations: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns number of allocations made.
+    fn allocations(&self) -> usize {
+        self.allocations.load(Ordering::Relaxed)
+    }
+
+    /// Returns number of deallocations made.
+    fn deallocations(&self) -> usize {
+        self.deallocations.load(Ordering::Relaxed)
+    }
+
+    /// Returns number of allocations not yet deallocated.
+    fn live(&self) -> usize {
+        self.allocations() - self.deallocations()
+    }
+}
+
+unsafe impl Allocator for CountingGlobal {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = Global.allocate(layout)?;
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        Ok(ptr)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.deallocations.fetch_add(1, Ordering::Relaxed);
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+/// Value that increments shared counter when dropped.
+struct DropCounter(alloc::rc::Rc<Cell<usize>>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
 #[test]
 fn test_local_alloc() {
     let mut blink = BlinkAlloc::new();
@@ -104,6 +176,348 @@ fn test_reuse() {
     }
 }
 
+#[test]
+fn test_put_boxed() {
+    use alloc::{boxed::Box, rc::Rc, string::String};
+
+    let dropped = Rc::new(Cell::new(0));
+    let mut blink = Blink::new();
+
+    let boxed = Box::new((String::from("Hello"), DropCounter(dropped.clone())));
+    let heap = &*boxed as *const (String, DropCounter);
+
+    // Value is moved out of the box, box is freed without dropping it.
+    let foo = blink.put_boxed(boxed);
+    assert_ne!(foo as *const (String, DropCounter), heap);
+    assert_eq!(foo.0, "Hello");
+    assert_eq!(dropped.get(), 0);
+
+    foo.0.push_str(", World");
+    assert_eq!(foo.0, "Hello, World");
+
+    blink.reset();
+    assert_eq!(dropped.get(), 1);
+}
+
+#[test]
+fn test_emplace_with_context() {
+    use alloc::{rc::Rc, string::String};
+
+    #[derive(Debug, PartialEq)]
+    enum Error {
+        Invalid,
+        OutOfMemory,
+    }
+
+    impl From<AllocError> for Error {
+        fn from(_: AllocError) -> Self {
+            Error::OutOfMemory
+        }
+    }
+
+    let context = Rc::new(());
+    let mut blink = Blink::new();
+
+    let value = blink
+        .emplace_with_context(Ok::<_, Error>(String::from("foo")), context.clone())
+        .unwrap();
+    value.push_str("bar");
+    assert_eq!(value, "foobar");
+    assert_eq!(Rc::strong_count(&context), 1);
+
+    let (err, ctx) = blink
+        .emplace_with_context(Err::<String, _>(Error::Invalid), context.clone())
+        .unwrap_err();
+    assert_eq!(err, Error::Invalid);
+    assert!(Rc::ptr_eq(&ctx, &context));
+    drop(ctx);
+
+    blink.reset();
+
+    let value = Rc::new(());
+    let blink = Blink::new_in(BlinkAlloc::new_in(NoMemory));
+    let (err, ctx) = blink
+        .emplace_with_context(Ok::<_, Error>(value.clone()), 42)
+        .unwrap_err();
+    assert_eq!(err, Error::OutOfMemory);
+    assert_eq!(ctx, 42);
+    assert_eq!(Rc::strong_count(&value), 1);
+}
+
+#[test]
+fn test_jagged() {
+    use alloc::rc::Rc;
+
+    let value = Rc::new(());
+    let mut blink = Blink::new();
+
+    let rows = blink.jagged((0..4).map(|len| (0..len).map(|idx| (idx, value.clone()))));
+    assert_eq!(rows.len(), 4);
+    for (len, row) in rows.iter().enumerate() {
+        assert_eq!(row.len(), len);
+        assert!(row.iter().map(|(idx, _)| *idx).eq(0..len));
+    }
+    assert_eq!(Rc::strong_count(&value), 7);
+
+    rows[3][0].0 = 42;
+    assert_eq!(rows[3][0].0, 42);
+
+    // Empty rows and no rows.
+    assert!(blink.jagged(core::iter::empty::<[u32; 0]>()).is_empty());
+    assert!(blink
+        .jagged([[0u32; 0]; 3])
+        .iter()
+        .all(|row| row.is_empty()));
+
+    blink.reset();
+    assert_eq!(Rc::strong_count(&value), 1);
+
+    let rows = blink
+        .try_jagged([[1u32, 2], [3, 4]].iter().map(|row| row.iter().copied()))
+        .unwrap();
+    assert_eq!(rows[0], [1, 2]);
+    assert_eq!(rows[1], [3, 4]);
+    blink.reset();
+
+    let blink = Blink::new_in(BlinkAlloc::new_in(NoMemory));
+    assert!(blink.try_jagged([[1u32, 2], [3, 4]]).is_none());
+    assert!(blink.try_jagged(core::iter::empty::<[u32; 0]>()).is_some());
+}
+
+#[test]
+fn test_try_from_iter_recover() {
+    use alloc::string::{String, ToString};
+
+    struct OneChunk {
+        served: Cell<bool>,
+    }
+
+    unsafe impl Allocator for OneChunk {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            if self.served.replace(true) {
+                Err(AllocError)
+            } else {
+                Global.allocate(layout)
+            }
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    let mut blink = Blink::new();
+    let slice = blink.emplace().try_from_iter_recover(0..5u32).unwrap();
+    assert_eq!(slice, [0, 1, 2, 3, 4]);
+    blink.reset();
+
+    // Iterator without size hint forces the allocation to grow until it fails.
+    let blink = Blink::new_in(BlinkAlloc::with_chunk_size_in(
+        256,
+        OneChunk {
+            served: Cell::new(false),
+        },
+    ));
+    let (slice, rest) = blink
+        .emplace()
+        .try_from_iter_recover((0..10000u32).filter(|_| true))
+        .unwrap_err();
+    assert!(!slice.is_empty());
+    assert!(slice.len() < 10000);
+    let rest = rest.collect::<alloc::vec::Vec<_>>();
+    assert_eq!(rest.len() + slice.len(), 10000);
+    assert!(slice.iter().chain(&rest).copied().eq(0..10000));
+
+    let blink = Blink::new_in(BlinkAlloc::with_chunk_size_in(
+        256,
+        OneChunk {
+            served: Cell::new(false),
+        },
+    ));
+    let (slice, rest) = blink
+        .emplace()
+        .try_from_iter_recover((0..10000).map(|i| i.to_string()).filter(|_| true))
+        .unwrap_err();
+    let rest = rest.collect::<alloc::vec::Vec<String>>();
+    assert_eq!(rest.len() + slice.len(), 10000);
+    assert_eq!(slice[0], "0");
+    assert_eq!(rest[0], slice.len().to_string());
+}
+
+#[test]
+fn test_put_rc() {
+    use alloc::rc::Rc;
+
+    let dropped = Rc::new(Cell::new(0));
+    let mut blink = Blink::new();
+    let shared = Rc::new(DropCounter(dropped.clone()));
+
+    let a: &Rc<DropCounter> = blink.put(shared.clone());
+    let b: &Rc<DropCounter> = blink.put(Rc::clone(a));
+    let c = Rc::clone(b);
+    assert_eq!(Rc::strong_count(&shared), 4);
+    drop(c);
+
+    // Emplaced handles are dropped on reset, value is still alive.
+    blink.reset();
+    assert_eq!(Rc::strong_count(&shared), 1);
+    assert_eq!(dropped.get(), 0);
+
+    // Last handle emplaced into blink drops the value on reset.
+    blink.put(shared);
+    blink.reset();
+    assert_eq!(dropped.get(), 1);
+}
+
+#[test]
+fn test_batch_put() {
+    use alloc::{rc::Rc, string::String};
+
+    let mut blink = Blink::new();
+    let slice = blink.batch_put((0..3).map(|i| String::from(["a", "b", "c"][i])));
+    assert_eq!(slice, ["a", "b", "c"]);
+    assert_eq!(blink.try_batch_put(0..4u32).unwrap(), [0, 1, 2, 3]);
+    assert!(blink.batch_put(core::iter::empty::<String>()).is_empty());
+
+    let rc = Rc::new(());
+    blink.batch_put([rc.clone(), rc.clone()]);
+    assert_eq!(Rc::strong_count(&rc), 3);
+    blink.reset();
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    let blink = Blink::new_in(BlinkAlloc::new_in(NoMemory));
+    assert!(blink.try_batch_put([rc.clone(), rc.clone()]).is_err());
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn test_blink_clear() {
+    let mut blink = Blink::new();
+    assert!(blink.is_empty());
+
+    let value = blink.put(Cell::new(1));
+    value.set(2);
+    assert!(!blink.is_empty());
+
+    blink.clear();
+    assert!(blink.is_empty());
+    assert_ne!(blink.allocator().last_chunk_cap(), 0);
+
+    // Values without drop glue are accounted by allocator.
+    blink.put_no_drop(1u32);
+    assert!(!blink.is_empty());
+    blink.clear();
+    assert!(blink.is_empty());
+
+    // Allocations spanning new chunks are accounted as well.
+    blink.emplace_no_drop().from_iter(0..100_000u32);
+    assert!(!blink.is_empty());
+    blink.reset();
+    assert!(blink.is_empty());
+
+    let shared = BlinkAlloc::new();
+    let mut blink = Blink::new_in(&shared);
+    assert!(blink.is_empty());
+    blink.put(1u32);
+    assert!(!blink.is_empty());
+    blink.clear();
+
+    // Shared allocator is not reset by `Blink`.
+    assert!(!blink.is_empty());
+}
+
+#[test]
+fn test_emplace_map_iter() {
+    use alloc::string::{String, ToString};
+
+    let mut blink = Blink::new();
+
+    let pairs = blink.emplace_map_iter((0..4u32).rev().map(|i| (i, i.to_string())));
+    assert_eq!(pairs[0], (3, String::from("3")));
+    assert_eq!(pairs.len(), 4);
+
+    let sorted = blink.emplace_sorted_map_iter((0..100u32).map(|i| ((i * 37) % 100, i)));
+    assert!(sorted.windows(2).all(|w| w[0].0 < w[1].0));
+    for (key, value) in sorted.iter() {
+        assert_eq!((value * 37) % 100, *key);
+    }
+
+    let sorted = blink
+        .try_emplace_sorted_map_iter([("b", 2), ("a", 1)])
+        .unwrap();
+    assert_eq!(sorted, [("a", 1), ("b", 2)]);
+    assert_eq!(blink.try_emplace_map_iter([(1, 2)]).unwrap(), [(1, 2)]);
+    blink.reset();
+}
+
+#[test]
+fn test_try_put() {
+    use alloc::{boxed::Box, string::String};
+
+    let mut blink = Blink::new();
+    assert_eq!(*blink.try_put(1u32).unwrap(), 1);
+    assert_eq!(*blink.try_put_no_drop(2u32).unwrap(), 2);
+    assert_eq!(blink.try_put_boxed(Box::new(3u32)).unwrap(), &3);
+    assert_eq!(
+        blink.try_emplace_from_flatten([0..2, 2..4]).unwrap(),
+        [0, 1, 2, 3]
+    );
+    blink.reset();
+
+    let blink = Blink::new_in(BlinkAlloc::new_in(NoMemory));
+    assert_eq!(blink.try_put(String::from("a")).unwrap_err(), "a");
+    assert_eq!(blink.try_put_no_drop(String::from("b")).unwrap_err(), "b");
+    assert_eq!(
+        *blink
+            .try_put_boxed(Box::new(String::from("c")))
+            .unwrap_err(),
+        "c"
+    );
+    // Space for estimated number of items is allocated before taking any.
+    let (slice, next) = blink.try_emplace_from_flatten([0..2, 2..4]).unwrap_err();
+    assert!(slice.is_empty());
+    assert_eq!(next, None);
+}
+
+#[test]
+fn test_dealloc_reclaims_last() {
+    let blink = BlinkAlloc::new();
+    let layout = Layout::new::<[u64; 4]>();
+
+    let a = blink.allocate(layout).unwrap();
+    let b = blink.allocate(layout).unwrap();
+    unsafe { blink.deallocate(b.cast(), layout.size()) };
+
+    // Memory of the last allocation is reused.
+    let c = blink.allocate(layout).unwrap();
+    assert_eq!(c.cast::<u8>(), b.cast::<u8>());
+
+    // Memory of any other allocation is not.
+    unsafe { blink.deallocate(a.cast(), layout.size()) };
+    let d = blink.allocate(layout).unwrap();
+    assert_ne!(d.cast::<u8>(), a.cast::<u8>());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_dealloc_reclaims_last() {
+    use crate::sync::SyncBlinkAlloc;
+
+    let blink = SyncBlinkAlloc::new();
+    let layout = Layout::new::<[u64; 4]>();
+
+    let a = blink.allocate(layout).unwrap();
+    let b = blink.allocate(layout).unwrap();
+    unsafe { blink.deallocate(b.cast(), layout.size()) };
+
+    let c = blink.allocate(layout).unwrap();
+    assert_eq!(c.cast::<u8>(), b.cast::<u8>());
+
+    unsafe { blink.deallocate(a.cast(), layout.size()) };
+    let d = blink.allocate(layout).unwrap();
+    assert_ne!(d.cast::<u8>(), a.cast::<u8>());
+}
+
 #[test]
 fn test_emplace_no_drop() {
     use alloc::{borrow::ToOwned, string::String};
@@ -141,3 +555,3294 @@ fn test_vec() {
     drop(vec);
     blink_alloc.reset();
 }
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_tiered_cache() {
+    use crate::cache::TieredBlinkAllocCache;
+
+    // Instances are told apart by size of their chunk.
+    fn blink(size: usize) -> BlinkAlloc {
+        let mut blink = BlinkAlloc::new();
+        blink
+            .allocate(Layout::from_size_align(size, 1).unwrap())
+            .unwrap();
+        blink.reset();
+        blink
+    }
+
+    let cache = TieredBlinkAllocCache::with_hot_capacity(2);
+    assert!(cache.pop().is_none());
+
+    // Oldest instance migrates to the cold tier.
+    cache.push(blink(1 << 10));
+    cache.push(blink(1 << 14));
+    cache.push(blink(1 << 18));
+    assert_eq!(cache.hot_len(), 2);
+    assert_eq!(cache.cold_len(), 1);
+
+    // Hot tier is popped first, newest instance first.
+    assert!(cache.pop().unwrap().last_chunk_cap() >= 1 << 18);
+    assert_eq!(cache.hot_len(), 1);
+    assert_eq!(cache.cold_len(), 1);
+
+    cache.cool();
+    assert_eq!(cache.hot_len(), 0);
+    assert_eq!(cache.cold_len(), 2);
+
+    // Cold tier keeps migration order.
+    let cap = cache.pop().unwrap().last_chunk_cap();
+    assert!(((1 << 14)..(1 << 18)).contains(&cap));
+    assert!(cache.pop().unwrap().last_chunk_cap() < 1 << 14);
+    assert!(cache.pop().is_none());
+}
+
+#[test]
+fn test_box() {
+    use alloc::rc::Rc;
+    use allocator_api2::boxed::Box;
+
+    let dropped = Rc::new(Cell::new(0));
+    let mut blink = BlinkAlloc::with_chunk_size(1024);
+
+    // Last allocation is reclaimed on drop.
+    let first = Box::new_in(DropCounter(dropped.clone()), &blink);
+    let addr = &*first as *const DropCounter;
+    drop(first);
+    assert_eq!(dropped.get(), 1);
+    let second = Box::new_in(DropCounter(dropped.clone()), &blink);
+    assert_eq!(&*second as *const DropCounter, addr);
+
+    // Memory of not the last allocation is kept until reset.
+    let third = Box::new_in(DropCounter(dropped.clone()), &blink);
+    drop(second);
+    assert_eq!(dropped.get(), 2);
+    let fourth = Box::new_in(DropCounter(dropped.clone()), &blink);
+    assert_ne!(&*fourth as *const DropCounter, addr);
+
+    drop(third);
+    drop(fourth);
+    assert_eq!(dropped.get(), 4);
+
+    blink.reset();
+    let fifth = Box::new_in(DropCounter(dropped.clone()), &blink);
+    assert_eq!(&*fifth as *const DropCounter, addr);
+    drop(fifth);
+    assert_eq!(dropped.get(), 5);
+}
+
+#[test]
+fn test_emplace_from_flatten() {
+    use alloc::{
+        string::{String, ToString},
+        vec,
+    };
+
+    let mut blink = Blink::new();
+
+    let nested = vec![
+        vec![String::from("a"), String::from("b")],
+        vec![],
+        (0..100).map(|i| i.to_string()).collect(),
+        vec![String::from("c")],
+    ];
+
+    let slice = blink.emplace_from_flatten(nested);
+    assert_eq!(slice.len(), 103);
+    assert_eq!(slice[..2], ["a", "b"]);
+    assert_eq!(slice[2], "0");
+    assert_eq!(slice[101], "99");
+    assert_eq!(slice[102], "c");
+
+    blink.reset();
+}
+
+#[test]
+fn test_emplace_write() {
+    use core::fmt::Write as _;
+
+    let mut blink = Blink::new();
+
+    let bytes = blink.emplace_write(4, |writer| {
+        for i in 0..100 {
+            write!(writer, "{i},").unwrap();
+        }
+        assert_eq!(&writer.written()[..4], b"0,1,");
+    });
+    let expected: alloc::string::String = (0..100).map(|i| alloc::format!("{i},")).collect();
+    assert_eq!(bytes, expected.as_bytes());
+
+    // Allocations in between prevent growing in place.
+    let bytes = blink.emplace_write(0, |writer| {
+        for i in 0..100 {
+            blink.put(i);
+            write!(writer, "{i},").unwrap();
+        }
+    });
+    assert_eq!(bytes, expected.as_bytes());
+
+    let empty = blink.emplace_write(100, |_| {});
+    assert!(empty.is_empty());
+
+    #[cfg(feature = "std")]
+    {
+        use std::io::Write as _;
+
+        let bytes = blink.emplace_write(0, |writer| {
+            writer.write_all(&[1, 2, 3]).unwrap();
+            writer.write_all(&[4; 1000]).unwrap();
+        });
+        assert_eq!(bytes.len(), 1003);
+        assert_eq!(bytes[..4], [1, 2, 3, 4]);
+    }
+
+    blink.reset();
+}
+
+#[test]
+fn test_emplace_from_chunks() {
+    use alloc::vec::Vec as StdVec;
+
+    let mut blink = Blink::new();
+
+    let chunks: StdVec<StdVec<u32>> = (0..20u32).map(|i| (i * 10..i * 10 + i).collect()).collect();
+    let expected: StdVec<u32> = chunks.iter().flatten().copied().collect();
+
+    let slice = blink.emplace_from_chunks(chunks.iter().map(|c| &c[..]));
+    assert_eq!(slice, &expected[..]);
+
+    // Allocations in between prevent growing in place.
+    let slice = blink.emplace_from_chunks(chunks.iter().map(|c| {
+        blink.put(0u64);
+        &c[..]
+    }));
+    assert_eq!(slice, &expected[..]);
+
+    let empty: &mut [u32] = blink.emplace_from_chunks([]);
+    assert!(empty.is_empty());
+
+    let units = blink.emplace_from_chunks([&[(); 3][..], &[(); 5]]);
+    assert_eq!(units.len(), 8);
+
+    blink.reset();
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_cacheline_align() {
+    use crate::sync::SyncBlinkAlloc;
+
+    let blink = SyncBlinkAlloc::new().with_cacheline_align();
+
+    let a = blink.allocate(Layout::new::<u8>()).unwrap();
+    let b = blink.allocate(Layout::new::<u8>()).unwrap();
+
+    let a = a.as_ptr().cast::<u8>() as usize;
+    let b = b.as_ptr().cast::<u8>() as usize;
+
+    assert_eq!(a % 64, 0);
+    assert_eq!(b % 64, 0);
+    assert_ne!(a / 64, b / 64);
+}
+
+#[test]
+fn test_emplace_with_uninit_error() {
+    use alloc::string::String;
+    use core::mem::MaybeUninit;
+
+    let mut blink = Blink::new();
+    blink.put(0u32);
+
+    let mut failed = core::ptr::null_mut();
+    let result = unsafe {
+        blink
+            .emplace()
+            .with_uninit(|slot: &mut MaybeUninit<String>| {
+                failed = slot.as_mut_ptr();
+                Err("failed")
+            })
+    };
+    assert_eq!(result.unwrap_err(), "failed");
+
+    // Memory is given back and reused by the next allocation.
+    let value = unsafe {
+        blink
+            .emplace()
+            .with_uninit(|slot: &mut MaybeUninit<String>| {
+                assert_eq!(slot.as_mut_ptr(), failed);
+                slot.write(String::from("ok"));
+                Ok::<(), ()>(())
+            })
+    };
+    assert_eq!(value.unwrap(), "ok");
+
+    blink.reset();
+}
+
+#[test]
+fn test_zero_size_alloc() {
+    use alloc::vec::Vec;
+
+    let blink = BlinkAlloc::new();
+
+    // No chunk is allocated for zero-sized allocations.
+    for align in [1, 2, 8, 64, 4096] {
+        let layout = Layout::from_size_align(0, align).unwrap();
+        let ptr = blink.allocate(layout).unwrap();
+        assert_eq!(ptr.len(), 0);
+        assert_eq!(ptr.as_ptr().cast::<u8>() as usize % align, 0);
+    }
+    let mut sizes = Vec::new();
+    blink.collect_chunk_sizes(&mut sizes);
+    assert!(sizes.is_empty());
+
+    // Cursor is not advanced by zero-sized allocations.
+    let a = blink.allocate(Layout::new::<u8>()).unwrap();
+    for align in [1, 2, 8, 64, 4096] {
+        let layout = Layout::from_size_align(0, align).unwrap();
+        let ptr = blink.allocate(layout).unwrap();
+        assert_eq!(ptr.as_ptr().cast::<u8>() as usize % align, 0);
+    }
+    let b = blink.allocate(Layout::new::<u8>()).unwrap();
+    assert_eq!(
+        a.as_ptr().cast::<u8>().wrapping_add(1),
+        b.as_ptr().cast::<u8>()
+    );
+
+    let mut blink = Blink::new_in(blink);
+    assert!(blink.copy_slice::<u64>(&[]).is_empty());
+    assert!(blink
+        .emplace()
+        .from_iter(core::iter::empty::<u64>())
+        .is_empty());
+    blink.reset();
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_global_fallback() {
+    use core::alloc::GlobalAlloc;
+
+    use crate::global::sync::GlobalBlinkAlloc;
+
+    let global = GlobalBlinkAlloc::new_in(NoMemory).with_fallback(&std::alloc::System);
+
+    unsafe {
+        global.blink_mode();
+
+        let layout = Layout::new::<[u32; 4]>();
+        let ptr = global.alloc(layout);
+        assert!(!ptr.is_null());
+        assert_eq!(global.fallback_alloc_count(), 1);
+
+        core::ptr::write(ptr.cast::<[u32; 4]>(), [1, 2, 3, 4]);
+        let ptr = global.realloc(ptr, layout, 64);
+        assert!(!ptr.is_null());
+        assert_eq!(core::ptr::read(ptr.cast::<[u32; 4]>()), [1, 2, 3, 4]);
+        assert_eq!(global.fallback_live_count(), 1);
+
+        global.dealloc(ptr, Layout::from_size_align(64, layout.align()).unwrap());
+        assert_eq!(global.fallback_alloc_count(), 1);
+        assert_eq!(global.fallback_live_count(), 0);
+        global.direct_mode();
+    }
+}
+
+#[test]
+fn test_emplace_string_from_chars() {
+    let mut blink = Blink::new();
+
+    let text = "blink ⚡ аллокатор 🦀";
+    let string = blink.emplace_string_from_chars(text.chars());
+    assert_eq!(string, text);
+
+    let string = blink
+        .try_emplace_string_from_chars(text.chars().filter(|c| !c.is_ascii()))
+        .unwrap();
+    assert_eq!(string, "⚡аллокатор🦀");
+
+    assert_eq!(blink.emplace_string_from_chars(core::iter::empty()), "");
+    blink.reset();
+}
+
+#[test]
+fn test_from_iter_rev() {
+    use alloc::{
+        string::{String, ToString},
+        vec::Vec as StdVec,
+    };
+
+    let mut blink = Blink::new();
+
+    let iter = (0..100).map(|i| i.to_string());
+    let expected = iter.clone().rev().collect::<StdVec<String>>();
+    let slice = blink.emplace().from_iter_rev(iter.filter(|_| true));
+    assert_eq!(*slice, *expected);
+
+    let slice = blink.emplace_no_drop().try_from_iter_rev(0..10u32).unwrap();
+    assert_eq!(slice, [9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+
+    blink.reset();
+}
+
+#[test]
+fn test_chunks() {
+    use alloc::vec::Vec;
+
+    let mut blink = BlinkAlloc::new();
+    assert_eq!(blink.chunks().count(), 0);
+
+    for size in [16, 300, 1000, 5000, 20000] {
+        blink
+            .allocate(Layout::from_size_align(size, 8).unwrap())
+            .unwrap();
+    }
+
+    let mut sizes = Vec::new();
+    blink.collect_chunk_sizes(&mut sizes);
+
+    assert_eq!(blink.chunks().count(), sizes.len());
+    assert_eq!(
+        blink.chunks().map(|chunk| chunk.cap()).sum::<usize>(),
+        sizes.iter().sum::<usize>()
+    );
+    for chunk in blink.chunks() {
+        assert!(chunk.used() > 0);
+        assert!(chunk.used() <= chunk.cap());
+    }
+
+    blink.reset();
+    let chunk = blink.chunks().next().unwrap();
+    assert_eq!(blink.chunks().count(), 1);
+    assert_eq!(chunk.used(), 0);
+}
+
+#[test]
+fn test_minimum_viable_chunk_size() {
+    let workload = |blink: &BlinkAlloc| {
+        for i in 0..200 {
+            let layout = Layout::from_size_align(1 + i * 7 % 100, 1 << (i % 5)).unwrap();
+            blink.allocate(layout).unwrap();
+        }
+    };
+
+    let blink = BlinkAlloc::new();
+    assert_eq!(blink.minimum_viable_chunk_size(), 0);
+
+    workload(&blink);
+    assert!(blink.chunks().count() > 1);
+
+    let chunk_size = blink.minimum_viable_chunk_size();
+    let used = blink.chunks().map(|chunk| chunk.used()).sum::<usize>();
+    assert!(chunk_size >= used);
+
+    let blink = BlinkAlloc::with_chunk_size(chunk_size);
+    workload(&blink);
+    assert_eq!(blink.chunks().count(), 1);
+}
+
+#[test]
+fn test_last_chunk_cap() {
+    let mut blink = BlinkAlloc::new();
+    assert_eq!(blink.last_chunk_cap(), 0);
+
+    blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    let first = blink.last_chunk_cap();
+    assert!(first >= 100);
+
+    blink.allocate(Layout::new::<[u8; 5000]>()).unwrap();
+    let last = blink.last_chunk_cap();
+    assert!(last >= 5000);
+    assert_eq!(blink.chunks().next().unwrap().cap(), last);
+
+    blink.reset();
+    assert_eq!(blink.last_chunk_cap(), last);
+
+    blink.reset_final();
+    assert_eq!(blink.last_chunk_cap(), 0);
+}
+
+#[test]
+fn test_refcell_reset() {
+    use crate::refcell::RefCellBlinkAlloc;
+
+    let blink = RefCellBlinkAlloc::new();
+
+    let mut vec = Vec::new_in(&blink);
+    vec.extend(0..100u32);
+    assert_eq!(blink.live_allocations(), 1);
+    drop(vec);
+
+    assert_eq!(blink.live_allocations(), 0);
+    blink.reset();
+}
+
+#[test]
+#[should_panic]
+fn test_refcell_reset_live() {
+    use crate::refcell::RefCellBlinkAlloc;
+
+    let blink = RefCellBlinkAlloc::new();
+
+    let mut vec = Vec::new_in(&blink);
+    vec.push(1u32);
+    blink.reset();
+    drop(vec);
+}
+
+#[test]
+fn test_blink_scope() {
+    use alloc::rc::Rc;
+
+    let dropped = Rc::new(Cell::new(0));
+    let mut blink = Blink::new();
+    blink.put(DropCounter(dropped.clone()));
+
+    {
+        let mut outer = blink.open_scope();
+        let first = outer.put(1u64) as *mut u64;
+        outer.put(DropCounter(dropped.clone()));
+
+        {
+            let inner = outer.open_scope();
+            inner.put(DropCounter(dropped.clone()));
+            inner
+                .emplace()
+                .from_iter((0..1000).map(|_| DropCounter(dropped.clone())));
+        }
+        assert_eq!(dropped.get(), 1001);
+
+        // Memory of the inner scope is reused.
+        drop(outer);
+        let outer = blink.open_scope();
+        assert_eq!(outer.put(2u64) as *mut u64, first);
+    }
+    assert_eq!(dropped.get(), 1002);
+
+    blink.reset();
+    assert_eq!(dropped.get(), 1003);
+}
+
+#[test]
+fn test_try_scope_with_result() {
+    use alloc::rc::Rc;
+
+    let dropped = Rc::new(Cell::new(0));
+    let mut blink = Blink::new();
+
+    let result = blink.try_scope_with_result(|scope| {
+        scope.put(DropCounter(dropped.clone()));
+        Err::<(), _>(scope.put(1u64) as *mut u64)
+    });
+    assert_eq!(dropped.get(), 1);
+
+    assert!(result.is_err());
+    let result = blink.try_scope_with_result(|scope| {
+        scope.put(DropCounter(dropped.clone()));
+        Ok::<_, ()>(scope.put(2u64) as *mut u64)
+    });
+    assert_eq!(dropped.get(), 1);
+
+    // Values of the successful scope are kept until reset.
+    let kept = result.unwrap();
+    assert_eq!(unsafe { *kept }, 2);
+    blink.reset();
+    assert_eq!(dropped.get(), 2);
+}
+
+#[test]
+fn test_from_exact_iter() {
+    use alloc::rc::Rc;
+
+    let mut blink = Blink::new();
+
+    let slice = blink.emplace().from_exact_iter(0..100usize);
+    assert_eq!(slice.len(), 100);
+    assert!(slice.iter().copied().eq(0..100));
+
+    let source = [1u32, 2, 3];
+    let slice = blink.emplace_no_drop().from_exact_iter(source.iter());
+    assert_eq!(slice, [&1, &2, &3]);
+
+    let slice = blink.emplace().from_exact_iter(0..0usize);
+    assert!(slice.is_empty());
+
+    let rc = Rc::new(());
+    let slice = blink
+        .emplace()
+        .from_exact_iter([rc.clone(), rc.clone(), rc.clone()]);
+    assert_eq!(slice.len(), 3);
+    assert_eq!(Rc::strong_count(&rc), 4);
+
+    blink.reset();
+    assert_eq!(Rc::strong_count(&rc), 1);
+
+    let slice = blink.emplace().from_exact_iter([(), (), ()]);
+    assert_eq!(slice.len(), 3);
+}
+
+#[test]
+fn test_allocate_many_same_layout() {
+    let blink = BlinkAlloc::new();
+
+    let layout = Layout::from_size_align(12, 8).unwrap();
+    let block = blink.allocate_many_same_layout(100, layout).unwrap();
+    assert!(block.len() >= 1600);
+    assert_eq!(block.cast::<u8>().as_ptr() as usize % 8, 0);
+    assert_eq!(blink.chunks().count(), 1);
+
+    let block = blink.allocate_many_same_layout(0, layout).unwrap();
+    assert_eq!(block.len(), 0);
+
+    assert!(blink
+        .allocate_many_same_layout(usize::MAX, Layout::new::<u64>())
+        .is_err());
+}
+
+#[test]
+fn test_allocate_cache_aligned() {
+    use crate::CACHE_LINE_SIZE;
+
+    let blink = BlinkAlloc::new();
+
+    let mut lines = alloc::vec::Vec::new();
+    for size in [1, 24, CACHE_LINE_SIZE, CACHE_LINE_SIZE + 1] {
+        blink.allocate(Layout::new::<u8>()).unwrap();
+        let ptr = blink.allocate_cache_aligned(size).unwrap();
+        assert!(ptr.len() >= size);
+
+        let addr = ptr.as_ptr().cast::<u8>() as usize;
+        assert_eq!(addr % CACHE_LINE_SIZE, 0);
+        lines.push(addr / CACHE_LINE_SIZE);
+    }
+
+    // Each allocation starts on its own cache line.
+    lines.sort_unstable();
+    lines.dedup();
+    assert_eq!(lines.len(), 4);
+
+    assert!(blink.allocate_cache_aligned(usize::MAX).is_err());
+}
+
+#[test]
+fn test_deterministic_layout() {
+    /// Rounds allocation sizes up to `granularity`
+    /// and returns whole memory to the caller.
+    struct SlackGlobal {
+        granularity: usize,
+    }
+
+    impl SlackGlobal {
+        fn actual(&self, layout: Layout) -> Layout {
+            let size = layout.size().div_ceil(self.granularity) * self.granularity;
+            Layout::from_size_align(size, layout.align()).unwrap()
+        }
+    }
+
+    unsafe impl Allocator for SlackGlobal {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Global.allocate(self.actual(layout))
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            Global.deallocate(ptr, self.actual(layout))
+        }
+    }
+
+    fn run(granularity: usize, deterministic: bool) -> Vec<(usize, usize)> {
+        let mut blink = BlinkAlloc::with_chunk_size_in(100, SlackGlobal { granularity });
+        if deterministic {
+            blink = blink.with_deterministic_layout();
+        }
+
+        let mut offsets = Vec::new();
+        for i in 0..300usize {
+            let layout = Layout::from_size_align(i % 13 * 8 + 1, 1 << (i % 3)).unwrap();
+            let ptr = blink.allocate(layout).unwrap();
+            let chunk = blink.chunks().next().unwrap();
+            let offset = ptr.as_ptr() as *mut u8 as usize - chunk.base().as_ptr() as usize;
+            offsets.push((blink.chunks().count(), offset));
+            offsets.push((chunk.cap(), chunk.used()));
+        }
+        offsets
+    }
+
+    assert_eq!(run(1, true), run(1, true));
+    assert_eq!(run(1, true), run(1000, true));
+    assert_eq!(run(1, true), run(4096, true));
+
+    // Spare memory changes layout otherwise.
+    assert_ne!(run(1, false), run(4096, false));
+}
+
+#[test]
+fn test_min_align() {
+    let mut blink = BlinkAlloc::new().with_min_align(32);
+
+    for size in [0, 1, 3, 8, 17, 100] {
+        let ptr = blink
+            .allocate(Layout::from_size_align(size, 1).unwrap())
+            .unwrap();
+        assert_eq!(ptr.as_ptr() as *mut u8 as usize % 32, 0);
+    }
+
+    // Larger alignment is kept.
+    let ptr = blink
+        .allocate(Layout::from_size_align(8, 64).unwrap())
+        .unwrap();
+    assert_eq!(ptr.as_ptr() as *mut u8 as usize % 64, 0);
+
+    let layout = Layout::new::<[u8; 3]>();
+    let ptr = blink.allocate(layout).unwrap();
+    blink.allocate(layout).unwrap();
+    let grown = unsafe {
+        blink
+            .resize(ptr.cast(), layout, Layout::new::<[u8; 4096]>())
+            .unwrap()
+    };
+    assert_eq!(grown.as_ptr() as *mut u8 as usize % 32, 0);
+
+    let ptr = blink.try_allocate_in_current_chunk(layout).unwrap();
+    assert_eq!(ptr.as_ptr() as *mut u8 as usize % 32, 0);
+
+    blink.reset();
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_min_align() {
+    use crate::sync::SyncBlinkAlloc;
+
+    let blink = SyncBlinkAlloc::new().with_min_align(16);
+    for _ in 0..8 {
+        let ptr = blink.allocate(Layout::new::<u8>()).unwrap();
+        assert_eq!(ptr.as_ptr() as *mut u8 as usize % 16, 0);
+    }
+}
+
+#[test]
+#[should_panic = "Minimum alignment must be a power of two"]
+fn test_min_align_not_power_of_two() {
+    let _ = BlinkAlloc::new().with_min_align(24);
+}
+
+#[test]
+fn test_for_each_chunk() {
+    let mut blink = BlinkAlloc::with_chunk_size(64);
+
+    let mut count = 0;
+    blink.for_each_chunk(|_, _| count += 1);
+    assert_eq!(count, 0);
+
+    let bytes = blink.allocate_slice::<u8>(16).unwrap();
+    bytes.iter_mut().for_each(|b| {
+        b.write(7);
+    });
+    let _ = blink.allocate_slice::<u8>(1024).unwrap();
+
+    let mut chunks = Vec::new();
+    blink.for_each_chunk(|chunk_bytes, used_bytes| {
+        assert!(used_bytes <= chunk_bytes.len());
+        chunks.push((
+            chunk_bytes.as_ptr() as *const u8,
+            chunk_bytes.len(),
+            used_bytes,
+        ));
+    });
+
+    let expected = blink
+        .chunks()
+        .map(|chunk| {
+            (
+                chunk.base().as_ptr() as *const u8,
+                chunk.cap(),
+                chunk.used(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(chunks, expected);
+
+    // Oldest chunk holds the first allocation.
+    // Newer chunk is not initialized and must not be read.
+    let mut first = Vec::new();
+    let mut index = 0;
+    blink.for_each_chunk(|chunk_bytes, used_bytes| {
+        if index + 1 == chunks.len() {
+            first = chunk_bytes[..used_bytes]
+                .iter()
+                .map(|b| unsafe { b.assume_init() })
+                .collect();
+        }
+        index += 1;
+    });
+    assert_eq!(first, [7; 16]);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_for_each_chunk() {
+    use crate::sync::SyncBlinkAlloc;
+
+    let mut blink = SyncBlinkAlloc::new();
+    blink.allocate(Layout::new::<[u8; 16]>()).unwrap();
+    blink.local().allocate(Layout::new::<[u8; 32]>()).unwrap();
+
+    let mut used = 0;
+    blink.for_each_chunk(|chunk_bytes, used_bytes| {
+        assert!(used_bytes <= chunk_bytes.len());
+        used += used_bytes;
+    });
+    assert_eq!(used, blink.stats().used);
+}
+
+#[test]
+fn test_brand() {
+    let mut blink = BlinkAlloc::new();
+
+    let sum = blink.brand(|arena| {
+        let mut a = arena.put(1u32);
+        *a += 10;
+        let b = arena.try_put(2u32).unwrap();
+
+        let mut vec = Vec::new_in(arena.allocator());
+        vec.extend([*a, *b]);
+
+        let raw = arena.allocate(Layout::new::<u64>()).unwrap();
+        assert!(raw.get().len() >= size_of::<u64>());
+
+        assert!(!arena.allocator().is_empty());
+        vec.iter().sum::<u32>()
+    });
+    assert_eq!(sum, 13);
+    assert!(blink.is_empty());
+}
+
+#[test]
+fn test_allocate_slice() {
+    let mut blink = BlinkAlloc::new();
+
+    let slice = blink.allocate_slice::<u64>(16).unwrap();
+    assert_eq!(slice.len(), 16);
+    assert_eq!(slice.as_ptr() as usize % align_of::<u64>(), 0);
+    for (i, item) in slice.iter_mut().enumerate() {
+        item.write(i as u64);
+    }
+
+    let defaults = blink.allocate_slice_default::<(u8, u32)>(8).unwrap();
+    assert_eq!(defaults, [(0, 0); 8]);
+    defaults[7].1 = 7;
+
+    // Previously allocated slice is not overwritten.
+    assert!(slice
+        .iter()
+        .map(|item| unsafe { item.assume_init() })
+        .eq(0..16));
+
+    assert!(blink.allocate_slice::<()>(usize::MAX).unwrap().len() == usize::MAX);
+    assert!(blink.allocate_slice::<u32>(usize::MAX).is_err());
+    assert!(blink.allocate_slice_default::<u32>(0).unwrap().is_empty());
+
+    blink.reset();
+}
+
+#[test]
+fn test_is_empty() {
+    let mut blink = BlinkAlloc::new().with_huge_alloc_threshold(4096);
+    assert!(blink.is_empty());
+    assert_eq!(blink.last_chunk_cap(), 0);
+
+    // Zero-sized allocations consume nothing.
+    blink.allocate(Layout::new::<()>()).unwrap();
+    assert!(blink.is_empty());
+
+    blink.allocate(Layout::new::<u32>()).unwrap();
+    assert!(!blink.is_empty());
+
+    blink.allocate(Layout::new::<[u8; 65536]>()).unwrap();
+    blink.reset();
+    assert!(blink.is_empty());
+    assert_ne!(blink.last_chunk_cap(), 0);
+
+    // Huge allocation is not in chunks.
+    blink.allocate(Layout::new::<[u8; 65536]>()).unwrap();
+    assert!(!blink.is_empty());
+
+    blink.reset_final();
+    assert!(blink.is_empty());
+    assert_eq!(blink.last_chunk_cap(), 0);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_is_empty() {
+    use crate::sync::SyncBlinkAlloc;
+
+    let mut blink = SyncBlinkAlloc::new();
+    assert!(blink.is_empty());
+
+    blink.allocate(Layout::new::<u32>()).unwrap();
+    assert!(!blink.is_empty());
+
+    blink.reset();
+    assert!(blink.is_empty());
+    assert_ne!(blink.last_chunk_cap(), 0);
+
+    let local = blink.local();
+    local.allocate(Layout::new::<u32>()).unwrap();
+    drop(local);
+    assert!(!blink.is_empty());
+
+    blink.reset();
+    assert!(blink.is_empty());
+}
+
+#[test]
+fn test_allocate_at_least() {
+    let blink = BlinkAlloc::with_chunk_size(1024);
+    let layout = Layout::new::<[u32; 4]>();
+
+    let ptr = blink.allocate_at_least(layout, 100).unwrap();
+    assert!(ptr.len() >= layout.size() + 100);
+    assert_eq!(blink.allocated_since_reset(), ptr.len());
+
+    // Next allocation is placed past the extended capacity.
+    let next = blink.allocate(layout).unwrap();
+    let end = ptr.as_ptr() as *mut u8 as usize + ptr.len();
+    assert!(next.as_ptr() as *mut u8 as usize >= end);
+
+    // Extension is bounded by chunk end.
+    let free = blink.stats().capacity - blink.stats().used;
+    let ptr = blink.allocate_at_least(layout, usize::MAX).unwrap();
+    assert!(ptr.len() > layout.size() && ptr.len() <= free);
+    assert_eq!(blink.stats().capacity, blink.stats().used);
+
+    // Zero extra is regular allocation.
+    let ptr = blink.allocate_at_least(layout, 0).unwrap();
+    assert_eq!(ptr.len(), layout.size());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_double_buffered_global() {
+    use core::alloc::GlobalAlloc;
+
+    use crate::global::double::DoubleBufferedGlobalAlloc;
+
+    let global = DoubleBufferedGlobalAlloc::new();
+    let layout = Layout::new::<[u32; 4]>();
+
+    unsafe {
+        // Frame 0.
+        let frame0 = global.alloc(layout);
+        assert!(!frame0.is_null());
+        core::ptr::write(frame0.cast::<[u32; 4]>(), [0; 4]);
+        global.flip();
+
+        // Frame 1. Last frame's allocations remain valid.
+        let frame1 = global.alloc(layout);
+        assert!(!frame1.is_null());
+        assert_ne!(frame1, frame0);
+        core::ptr::write(frame1.cast::<[u32; 4]>(), [1; 4]);
+        assert_eq!(core::ptr::read(frame0.cast::<[u32; 4]>()), [0; 4]);
+        global.flip();
+
+        // Frame 2. Memory of frame 0 is reused.
+        let frame2 = global.alloc(layout);
+        assert_eq!(frame2, frame0);
+        core::ptr::write(frame2.cast::<[u32; 4]>(), [2; 4]);
+        assert_eq!(core::ptr::read(frame1.cast::<[u32; 4]>()), [1; 4]);
+
+        // Frame 1 allocation is moved to the active buffer on realloc.
+        let moved = global.realloc(frame1, layout, 32);
+        assert!(!moved.is_null());
+        assert_eq!(core::ptr::read(moved.cast::<[u32; 4]>()), [1; 4]);
+        global.flip();
+
+        // Frame 3. Memory of frame 1 is reused.
+        let frame3 = global.alloc(layout);
+        assert_eq!(frame3, frame1);
+        assert_eq!(core::ptr::read(frame2.cast::<[u32; 4]>()), [2; 4]);
+
+        // Last allocation grows in place and is reclaimed on dealloc.
+        let grown = global.realloc(frame3, layout, 32);
+        assert_eq!(grown, frame3);
+        let grown_layout = Layout::from_size_align(32, layout.align()).unwrap();
+        global.dealloc(grown, grown_layout);
+        assert_eq!(global.alloc(layout), frame3);
+    }
+}
+
+#[test]
+fn test_unsafe_global_mode_switch() {
+    use core::alloc::GlobalAlloc;
+
+    use allocator_api2::alloc::Global;
+
+    use crate::global::local::UnsafeGlobalBlinkAlloc;
+
+    let layout = Layout::new::<[u32; 4]>();
+
+    unsafe {
+        let global = UnsafeGlobalBlinkAlloc::new_in(Global);
+
+        let direct = global.alloc(layout);
+        assert!(!direct.is_null());
+        core::ptr::write(direct.cast::<[u32; 4]>(), [0; 4]);
+
+        global.blink_mode();
+        let blink = global.alloc(layout);
+        assert!(!blink.is_null());
+        core::ptr::write(blink.cast::<[u32; 4]>(), [1; 4]);
+        let moved = global.alloc(layout);
+        assert!(!moved.is_null());
+        core::ptr::write(moved.cast::<[u32; 4]>(), [2; 4]);
+
+        global.direct_mode();
+
+        // Blink memory is still valid after switching to direct mode.
+        assert_eq!(core::ptr::read(blink.cast::<[u32; 4]>()), [1; 4]);
+
+        // Reallocating blink memory moves it to the underlying allocator.
+        let moved = global.realloc(moved, layout, 64);
+        assert!(!moved.is_null());
+        assert_eq!(core::ptr::read(moved.cast::<[u32; 4]>()), [2; 4]);
+
+        // Deallocating blink memory in direct mode is a no-op.
+        global.dealloc(blink, layout);
+        global.dealloc(moved, Layout::from_size_align(64, layout.align()).unwrap());
+        global.dealloc(direct, layout);
+
+        global.reset();
+    }
+}
+
+#[test]
+fn test_unsafe_global_direct_realloc() {
+    use core::alloc::GlobalAlloc;
+
+    use crate::global::local::UnsafeGlobalBlinkAlloc;
+
+    let layout = Layout::new::<[u32; 4]>();
+
+    unsafe {
+        let global = UnsafeGlobalBlinkAlloc::new_in(Global);
+
+        let ptr = global.alloc(layout);
+        assert!(!ptr.is_null());
+        core::ptr::write(ptr.cast::<[u32; 4]>(), [1; 4]);
+
+        let ptr = global.realloc(ptr, layout, 1024);
+        assert!(!ptr.is_null());
+        assert_eq!(core::ptr::read(ptr.cast::<[u32; 4]>()), [1; 4]);
+
+        let big = Layout::from_size_align(1024, layout.align()).unwrap();
+        let ptr = global.realloc(ptr, big, 8);
+        assert!(!ptr.is_null());
+        assert_eq!(core::ptr::read(ptr.cast::<[u32; 2]>()), [1; 2]);
+
+        global.dealloc(ptr, Layout::from_size_align(8, layout.align()).unwrap());
+    }
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_global_direct_realloc() {
+    use core::alloc::GlobalAlloc;
+
+    use crate::global::sync::GlobalBlinkAlloc;
+
+    let layout = Layout::new::<[u32; 4]>();
+    let global = GlobalBlinkAlloc::new_in(Global);
+
+    unsafe {
+        let ptr = global.alloc(layout);
+        assert!(!ptr.is_null());
+        core::ptr::write(ptr.cast::<[u32; 4]>(), [1; 4]);
+
+        let ptr = global.realloc(ptr, layout, 1024);
+        assert!(!ptr.is_null());
+        assert_eq!(core::ptr::read(ptr.cast::<[u32; 4]>()), [1; 4]);
+
+        let big = Layout::from_size_align(1024, layout.align()).unwrap();
+        let ptr = global.realloc(ptr, big, 8);
+        assert!(!ptr.is_null());
+        assert_eq!(core::ptr::read(ptr.cast::<[u32; 2]>()), [1; 2]);
+
+        global.dealloc(ptr, Layout::from_size_align(8, layout.align()).unwrap());
+    }
+}
+
+#[test]
+fn test_emplace_zst_from_iter_drops_once() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    struct Zst;
+
+    impl Drop for Zst {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut blink = Blink::new();
+    let slice = blink.emplace().from_iter((0..5).map(|_| Zst));
+    assert_eq!(slice.len(), 5);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+
+    let empty = blink.emplace().from_iter(core::iter::empty::<Zst>());
+    assert!(empty.is_empty());
+
+    blink.reset();
+    assert_eq!(DROPS.load(Ordering::Relaxed), 5);
+}
+
+#[test]
+fn test_shrink_reclaims_tail() {
+    let blink = BlinkAlloc::with_chunk_size(4096);
+
+    for align in [1, 2, 8, 16, 64, 256] {
+        let layout = Layout::from_size_align(512, align).unwrap();
+        let small = Layout::from_size_align(64, align).unwrap();
+
+        let ptr = blink.allocate(layout).unwrap();
+        let addr = ptr.as_ptr().cast::<u8>() as usize;
+        assert_eq!(addr % align, 0);
+
+        // Shrinking the last allocation gives its tail back.
+        let shrunk = unsafe { blink.resize(ptr.cast(), layout, small).unwrap() };
+        assert_eq!(shrunk.as_ptr().cast::<u8>() as usize, addr);
+        assert_eq!(shrunk.len(), 64);
+
+        // Next allocation starts right after the shrunk one, aligned.
+        let next = blink.allocate(small).unwrap();
+        let next_addr = (addr + 64 + align - 1) & !(align - 1);
+        assert_eq!(next.as_ptr().cast::<u8>() as usize, next_addr);
+
+        // Shrinking not the last allocation keeps the tail.
+        let shrunk = unsafe {
+            blink
+                .resize(
+                    shrunk.cast(),
+                    small,
+                    Layout::from_size_align(32, align).unwrap(),
+                )
+                .unwrap()
+        };
+        assert_eq!(shrunk.as_ptr().cast::<u8>() as usize, addr);
+        assert_eq!(shrunk.len(), 64);
+
+        unsafe { blink.deallocate(next.cast(), next.len()) };
+    }
+}
+
+#[test]
+fn test_shrink_to_fit() {
+    let mut blink = BlinkAlloc::new();
+
+    blink.allocate(Layout::new::<[u8; 1 << 20]>()).unwrap();
+    blink.reset();
+    let large = blink.last_chunk_cap();
+    assert!(large >= 1 << 20);
+
+    blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+    blink.reset();
+    assert_eq!(blink.last_chunk_cap(), large);
+
+    blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+    blink.shrink_to_fit();
+    let small = blink.last_chunk_cap();
+    assert!(small >= 1000);
+    assert!(small < large);
+    assert_eq!(blink.chunks().count(), 1);
+
+    // Retained chunk fits the workload.
+    blink.allocate(Layout::new::<[u8; 1000]>()).unwrap();
+    assert_eq!(blink.chunks().count(), 1);
+    blink.shrink_to_fit();
+    assert_eq!(blink.last_chunk_cap(), small);
+
+    let mut blink = Blink::new();
+    blink.put([0u8; 1 << 16]);
+    blink.shrink_to_fit();
+    blink.put(1u32);
+    blink.shrink_to_fit();
+    assert!(blink.allocator().last_chunk_cap() < 1 << 16);
+}
+
+#[test]
+fn test_ordered_reset() {
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
+
+    struct Log(Rc<RefCell<Vec<u32>>>, u32);
+
+    impl Drop for Log {
+        fn drop(&mut self) {
+            self.0.borrow_mut().push(self.1);
+        }
+    }
+
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut blink = Blink::new();
+
+    blink.put(Log(log.clone(), 0));
+    blink
+        .emplace()
+        .from_iter((1..4).map(|idx| Log(log.clone(), idx)));
+    blink.put(Log(log.clone(), 4));
+
+    blink.ordered_reset();
+    assert_eq!(*log.borrow(), [0, 1, 2, 3, 4]);
+
+    log.borrow_mut().clear();
+    blink.put(Log(log.clone(), 0));
+    blink.put(Log(log.clone(), 1));
+    blink.reset();
+    assert_eq!(*log.borrow(), [1, 0]);
+}
+
+#[test]
+fn test_shrink_in_place() {
+    let blink = BlinkAlloc::with_chunk_size(4096);
+
+    let layout = Layout::from_size_align(100, 4).unwrap();
+    let ptr = blink.allocate(layout).unwrap();
+    assert_eq!(ptr.len(), layout.size());
+
+    unsafe {
+        // Shrinking the last allocation returns memory to the chunk.
+        let small = Layout::from_size_align(10, 4).unwrap();
+        let shrunk = blink.resize(ptr.cast(), layout, small).unwrap();
+        assert_eq!(shrunk.cast::<u8>(), ptr.cast::<u8>());
+        assert_eq!(shrunk.len(), small.size());
+
+        // So growing it back happens in place.
+        let grown = blink.resize(shrunk.cast(), small, layout).unwrap();
+        assert_eq!(grown.cast::<u8>(), ptr.cast::<u8>());
+        assert_eq!(grown.len(), layout.size());
+
+        // Shrinking not the last allocation keeps the memory.
+        let other = blink.allocate(layout).unwrap();
+        let shrunk = blink.resize(grown.cast(), layout, small).unwrap();
+        assert_eq!(shrunk.cast::<u8>(), ptr.cast::<u8>());
+        assert_eq!(shrunk.len(), layout.size());
+
+        // Returned length may be used for deallocation.
+        blink.deallocate(other.cast(), other.len());
+    }
+
+    // `Vec` grows in place while it is the last allocation.
+    let mut vec = Vec::with_capacity_in(16, &blink);
+    let first = vec.as_ptr();
+    vec.extend(0..1000u32);
+    assert_eq!(vec.as_ptr(), first);
+}
+
+#[test]
+fn test_high_water_mark() {
+    let mut blink = BlinkAlloc::new();
+    assert_eq!(blink.high_water_mark(), 0);
+
+    blink.allocate(Layout::new::<[u8; 4096]>()).unwrap();
+    let peak = blink.high_water_mark();
+    assert!(peak >= 4096);
+    assert_eq!(peak, blink.used_bytes());
+
+    blink.reset();
+    assert_eq!(blink.used_bytes(), 0);
+    assert_eq!(blink.high_water_mark(), peak);
+
+    blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    assert_eq!(blink.high_water_mark(), peak);
+
+    blink.reset_watermark();
+    let small = blink.high_water_mark();
+    assert!(small >= 100 && small < peak);
+
+    // Peak is kept when deallocation gives memory back.
+    let layout = Layout::new::<[u8; 8192]>();
+    let ptr = blink.allocate(layout).unwrap();
+    let used = blink.used_bytes();
+    unsafe { blink.deallocate(ptr.cast(), layout.size()) };
+    assert!(blink.used_bytes() < used);
+    assert_eq!(blink.high_water_mark(), used);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_local_drop_on_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    use crate::sync::SyncBlinkAlloc;
+
+    let allocator = CountingGlobal::new();
+    let mut shared = SyncBlinkAlloc::with_chunk_size_in(256, &allocator);
+    shared.allocate(Layout::new::<u64>()).unwrap();
+    assert_eq!(allocator.live(), 1);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let local = shared.local();
+        local.allocate(Layout::new::<[u8; 65536]>()).unwrap();
+        local.allocate(Layout::new::<[u8; 65536]>()).unwrap();
+        panic!("proxy is dropped during unwinding");
+    }));
+    assert!(result.is_err());
+
+    // Proxy chunks are allocated from the shared allocator.
+    assert!(allocator.live() > 1);
+
+    shared.reset();
+    assert_eq!(allocator.live(), 1);
+
+    shared.reset_final();
+    assert_eq!(allocator.live(), 0);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_cache_total_cached() {
+    use crate::cache::BlinkAllocCache;
+
+    let cache = BlinkAllocCache::new();
+    assert_eq!(cache.total_cached_capacity(), 0);
+    assert_eq!(cache.total_cached_used(), 0);
+
+    let mut capacity = 0;
+    let mut used = 0;
+    for size in [1024, 4096, 16384] {
+        let blink = BlinkAlloc::new();
+        blink
+            .allocate(Layout::from_size_align(size, 1).unwrap())
+            .unwrap();
+        capacity += blink.stats().capacity;
+        used += blink.used_bytes();
+        cache.push(blink);
+    }
+    cache.push_reset(BlinkAlloc::new());
+
+    assert_eq!(cache.total_cached_capacity(), capacity);
+    assert_eq!(cache.total_cached_used(), used);
+
+    let mut blink = cache.pop().unwrap();
+    capacity -= blink.stats().capacity;
+    used -= blink.used_bytes();
+    assert_eq!(cache.total_cached_capacity(), capacity);
+    assert_eq!(cache.total_cached_used(), used);
+
+    blink.reset();
+    let kept = blink.stats().capacity;
+    cache.push(blink);
+    assert_eq!(cache.total_cached_capacity(), capacity + kept);
+    assert_eq!(cache.total_cached_used(), used);
+
+    for mut blink in cache {
+        blink.reset_final();
+    }
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_cache_factory() {
+    use alloc::sync::Arc;
+
+    use crate::cache::BlinkAllocCache;
+
+    // Allocator without `Default`.
+    static ALLOCATOR: CountingGlobal = CountingGlobal::new();
+
+    let created = Arc::new(AtomicUsize::new(0));
+
+    let cache = {
+        let created = created.clone();
+        BlinkAllocCache::new_with_factory(move || {
+            created.fetch_add(1, Ordering::Relaxed);
+            BlinkAlloc::new_in(&ALLOCATOR)
+        })
+    };
+
+    let blink = cache.pop_or_new().unwrap();
+    assert_eq!(created.load(Ordering::Relaxed), 1);
+    blink.allocate(Layout::new::<u32>()).unwrap();
+    assert_eq!(ALLOCATOR.allocations(), 1);
+
+    // Cached instance is reused.
+    cache.push_reset(blink);
+    let blink = cache.pop_or_new().unwrap();
+    assert_eq!(created.load(Ordering::Relaxed), 1);
+
+    let other = cache.pop_or_new().unwrap();
+    assert_eq!(created.load(Ordering::Relaxed), 2);
+
+    cache.push(blink);
+    cache.push(other);
+    for mut blink in cache {
+        blink.reset_final();
+    }
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_cache_pop_or_new_without_factory() {
+    use crate::cache::BlinkAllocCache;
+
+    let cache = BlinkAllocCache::<Global>::new();
+    assert!(cache.pop_or_new().is_none());
+
+    cache.push(BlinkAlloc::new());
+    let blink = cache.pop_or_new().unwrap();
+    assert!(cache.pop_or_new().is_none());
+    drop(blink);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_cache_into_iter() {
+    use crate::cache::BlinkAllocCache;
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    let cache = BlinkAllocCache::new();
+    for _ in 0..5 {
+        cache.push(BlinkAlloc::new());
+    }
+    let popped = cache.pop().unwrap();
+    cache.push(popped);
+    cache.pop().unwrap();
+
+    let iter = cache.into_iter();
+    assert_send(&iter);
+    assert_eq!(iter.len(), 4);
+    assert_eq!(iter.count(), 4);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_thread_local_proxy() {
+    use crate::global::sync::GlobalBlinkAlloc;
+
+    static GLOBAL: GlobalBlinkAlloc = GlobalBlinkAlloc::new();
+
+    crate::thread_local_proxy!(fn with_proxy(GLOBAL));
+
+    let threads = (0..4usize)
+        .map(|i| {
+            std::thread::spawn(move || {
+                for _ in 0..10 {
+                    let proxy = with_proxy(|proxy| proxy as *const _);
+
+                    with_proxy(|p| {
+                        // Same proxy is used within the thread.
+                        assert_eq!(p as *const _, proxy);
+
+                        let mut vec = Vec::new_in(p);
+                        vec.extend(i * 100..(i + 1) * 100);
+                        assert!(vec.iter().copied().eq(i * 100..(i + 1) * 100));
+                    });
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_stats() {
+    use crate::sync::SyncBlinkAlloc;
+
+    let blink = SyncBlinkAlloc::new();
+
+    std::thread::scope(|scope| {
+        let writer = scope.spawn(|| {
+            // Miri is too slow for many iterations.
+            let iterations = if cfg!(miri) { 100 } else { 10000 };
+            for i in 0..iterations {
+                blink
+                    .allocate(Layout::from_size_align(1 + i % 100, 8).unwrap())
+                    .unwrap();
+            }
+        });
+
+        let mut last = blink.stats();
+        while !writer.is_finished() {
+            let stats = blink.stats();
+            assert!(stats.used <= stats.capacity);
+            assert!(stats.chunks >= last.chunks);
+            assert!(stats.capacity >= last.capacity);
+            assert!(stats.used >= last.used);
+            last = stats;
+        }
+    });
+
+    let stats = blink.stats();
+    assert_eq!(stats.chunks, blink.chunks().count());
+    assert_eq!(
+        stats.capacity,
+        blink.chunks().map(|chunk| chunk.cap()).sum::<usize>()
+    );
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_allocation_count() {
+    use crate::sync::SyncBlinkAlloc;
+
+    let mut blink = SyncBlinkAlloc::new();
+    assert_eq!(blink.current_allocation_count(), 0);
+
+    let mut vec = Vec::new_in(&blink);
+    vec.push(1u32);
+    let b = allocator_api2::boxed::Box::new_in(2u32, &blink);
+    assert_eq!(blink.current_allocation_count(), 2);
+
+    drop(vec);
+    assert_eq!(blink.current_allocation_count(), 1);
+
+    let _ = allocator_api2::boxed::Box::leak(b);
+    assert!(!blink.try_reset_if_count_is_zero());
+    assert_eq!(blink.current_allocation_count(), 1);
+
+    blink.reset();
+    assert_eq!(blink.current_allocation_count(), 0);
+
+    let b = allocator_api2::boxed::Box::new_in(3u32, &blink);
+    drop(b);
+    assert!(blink.try_reset_if_count_is_zero());
+
+    // Chunks of proxies are not counted as allocations.
+    let local = blink.local();
+    local.allocate(Layout::new::<u32>()).unwrap();
+    drop(local);
+    assert_eq!(blink.current_allocation_count(), 0);
+    assert!(blink.try_reset_if_count_is_zero());
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_emplace_from_async() {
+    use core::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    let blink = Blink::new();
+
+    let mut future = pin!(blink.emplace_from_async(async {
+        YieldOnce(false).await;
+        String::from("async")
+    }));
+
+    let mut cx = Context::from_waker(Waker::noop());
+    assert!(future.as_mut().poll(&mut cx).is_pending());
+    match future.poll(&mut cx) {
+        Poll::Ready(value) => assert_eq!(value, "async"),
+        Poll::Pending => panic!("Future must be ready"),
+    }
+}
+
+#[test]
+fn test_emplace_value_with_len() {
+    use alloc::string::String;
+
+    let mut blink = Blink::new();
+
+    let (value, len) = blink.emplace().value_with_len(7u16);
+    assert_eq!(*value, 7);
+    assert!(len >= size_of::<u16>());
+
+    let (value, len) = blink.emplace().value_with_len(String::from("drop"));
+    assert_eq!(value, "drop");
+    assert!(len >= size_of::<String>());
+
+    let (value, len) = blink.emplace_no_drop().value_with_len([3u64; 5]);
+    assert_eq!(*value, [3; 5]);
+    assert!(len >= size_of::<[u64; 5]>());
+
+    blink.reset();
+}
+
+#[test]
+fn test_grow_larger_align_in_place() {
+    let mut blink = BlinkAlloc::new();
+
+    // Leaves cursor aligned to 8.
+    blink.allocate(Layout::new::<u64>()).unwrap();
+
+    let ptr = blink.allocate(Layout::new::<u32>()).unwrap();
+    assert_eq!(ptr.as_ptr().cast::<u8>() as usize % 8, 0);
+
+    let grown = unsafe {
+        blink
+            .resize(ptr.cast(), Layout::new::<u32>(), Layout::new::<u64>())
+            .unwrap()
+    };
+    assert_eq!(grown.cast::<u8>(), ptr.cast::<u8>());
+    assert!(grown.len() >= size_of::<u64>());
+
+    // Misaligned pointer is moved.
+    blink.allocate(Layout::new::<u32>()).unwrap();
+    let ptr = blink.allocate(Layout::new::<u32>()).unwrap();
+    assert_ne!(ptr.as_ptr().cast::<u8>() as usize % 8, 0);
+
+    let grown = unsafe {
+        blink
+            .resize(ptr.cast(), Layout::new::<u32>(), Layout::new::<u64>())
+            .unwrap()
+    };
+    assert_ne!(grown.cast::<u8>(), ptr.cast::<u8>());
+    assert_eq!(grown.as_ptr().cast::<u8>() as usize % 8, 0);
+
+    blink.reset();
+}
+
+#[test]
+fn test_reset_leak() {
+    let mut pool = BlinkAlloc::new();
+
+    {
+        let mut blink = BlinkAlloc::with_chunk_size_in(64, &pool);
+        for _ in 0..10 {
+            blink.allocate(Layout::new::<[u64; 8]>()).unwrap();
+        }
+        let chunks = blink.chunks().count();
+        assert!(chunks > 1);
+
+        blink.reset_leak(true);
+        assert_eq!(blink.chunks().count(), 1);
+        assert_eq!(blink.used_bytes(), 0);
+
+        blink.allocate(Layout::new::<u64>()).unwrap();
+
+        blink.reset_leak(false);
+        assert_eq!(blink.chunks().count(), 0);
+    }
+
+    pool.reset();
+}
+
+#[test]
+fn test_retain_slice() {
+    use alloc::string::{String, ToString};
+
+    let mut blink = Blink::new();
+
+    let slice = blink.emplace().from_iter((0..10).map(|i| i.to_string()));
+    let retained = blink.retain_slice(slice, |s| s.parse::<u32>().unwrap() % 3 == 0);
+    assert_eq!(retained, ["0", "3", "6", "9"]);
+
+    let retained = blink.retain_slice(retained, |s| {
+        s.push('!');
+        s != "3!"
+    });
+    assert_eq!(retained, ["0!", "6!", "9!"]);
+
+    let empty = blink.retain_slice(retained, |_: &mut String| false);
+    assert!(empty.is_empty());
+
+    blink.reset();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_retain_slice_drops() {
+    use alloc::rc::Rc;
+
+    let drops = Rc::new(Cell::new(0));
+    let mut blink = Blink::new();
+
+    let slice = blink
+        .emplace()
+        .from_iter((0..10).map(|i| (DropCounter(drops.clone()), i)));
+    let retained = blink.retain_slice(slice, |foo| foo.1 % 3 == 0);
+    assert_eq!(drops.get(), 6);
+    assert_eq!(
+        retained.iter().map(|foo| foo.1).collect::<Vec<_>>(),
+        [0, 3, 6, 9]
+    );
+
+    // Retained slice can be retained again.
+    let retained = blink.retain_slice(retained, |foo| foo.1 != 3);
+    assert_eq!(drops.get(), 7);
+    assert_eq!(retained.len(), 3);
+
+    // Panic in predicate keeps unprocessed elements.
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        blink.retain_slice(retained, |foo| match foo.1 {
+            0 => false,
+            _ => panic!(),
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 8);
+
+    // Only retained elements are dropped on reset.
+    blink.reset();
+    assert_eq!(drops.get(), 10);
+
+    // Elements of slice not emplaced into the `Blink` are not dropped.
+    let mut owned = (0..4)
+        .map(|i| (DropCounter(drops.clone()), i))
+        .collect::<alloc::vec::Vec<_>>();
+    let retained = blink.retain_slice(&mut owned, |foo| foo.1 % 2 == 0);
+    assert_eq!(retained.len(), 2);
+    assert_eq!(drops.get(), 10);
+    drop(owned);
+    assert_eq!(drops.get(), 14);
+}
+
+#[test]
+fn test_allocate_array() {
+    let mut blink = BlinkAlloc::new();
+
+    let array = blink.allocate_array::<u32>(10).unwrap();
+    assert_eq!(array.len(), 10);
+    assert_eq!(array.cast::<u32>().as_ptr() as usize % align_of::<u32>(), 0);
+
+    assert_eq!(blink.allocate_array::<u32>(usize::MAX / 2), Err(AllocError));
+    assert_eq!(
+        blink.allocate_array_zeroed::<u64>(usize::MAX),
+        Err(AllocError)
+    );
+
+    // Dirty the memory before reset so zeroing is observable.
+    unsafe {
+        core::ptr::write_bytes(array.cast::<u32>().as_ptr(), 0xAB, 10);
+    }
+    blink.reset();
+
+    let zeroed = blink.allocate_array_zeroed::<u32>(10).unwrap();
+    assert_eq!(zeroed.len(), 10);
+    assert!(unsafe { zeroed.as_ref() }.iter().all(|&x| x == 0));
+
+    let empty = blink.allocate_array::<u64>(0).unwrap();
+    assert_eq!(empty.len(), 0);
+
+    blink.reset();
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn test_metrics() {
+    use std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    };
+
+    use metrics::{
+        Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+
+    #[derive(Default)]
+    struct TestRecorder {
+        chunks: Arc<AtomicU64>,
+        reserved: Arc<AtomicU64>,
+        resets: Arc<AtomicU64>,
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            match key.name() {
+                "blink_alloc.resets" => Counter::from_arc(self.resets.clone()),
+                _ => Counter::noop(),
+            }
+        }
+
+        fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+            match key.name() {
+                "blink_alloc.chunks" => Gauge::from_arc(self.chunks.clone()),
+                "blink_alloc.reserved_bytes" => Gauge::from_arc(self.reserved.clone()),
+                _ => Gauge::noop(),
+            }
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    let recorder = TestRecorder::default();
+    let gauge = |gauge: &AtomicU64| f64::from_bits(gauge.load(Ordering::Relaxed)) as usize;
+
+    metrics::with_local_recorder(&recorder, || {
+        let mut blink = BlinkAlloc::with_chunk_size(64);
+        for _ in 0..10 {
+            blink.allocate(Layout::new::<[u64; 8]>()).unwrap();
+        }
+
+        let stats = blink.stats();
+        assert!(stats.chunks > 1);
+        assert_eq!(gauge(&recorder.chunks), stats.chunks);
+        assert_eq!(gauge(&recorder.reserved), stats.capacity);
+        assert_eq!(recorder.resets.load(Ordering::Relaxed), 0);
+
+        blink.reset();
+        assert_eq!(recorder.resets.load(Ordering::Relaxed), 1);
+        assert_eq!(gauge(&recorder.chunks), 1);
+        assert_eq!(gauge(&recorder.reserved), blink.last_chunk_cap());
+
+        // Reused kept chunks are not counted again.
+        for _ in 0..10 {
+            blink.allocate(Layout::new::<[u64; 8]>()).unwrap();
+        }
+        blink.reset_partial(3);
+        for _ in 0..20 {
+            blink.allocate(Layout::new::<[u64; 8]>()).unwrap();
+        }
+        let stats = blink.stats();
+        assert_eq!(gauge(&recorder.chunks), stats.chunks);
+        assert_eq!(gauge(&recorder.reserved), stats.capacity);
+
+        blink.reset_final();
+        assert_eq!(recorder.resets.load(Ordering::Relaxed), 3);
+        assert_eq!(gauge(&recorder.chunks), 0);
+        assert_eq!(gauge(&recorder.reserved), 0);
+
+        #[cfg(feature = "sync")]
+        {
+            let blink = crate::sync::SyncBlinkAlloc::new_sharded(2);
+            for _ in 0..10 {
+                blink.allocate(Layout::new::<[u64; 64]>()).unwrap();
+            }
+
+            let stats = blink.stats();
+            assert_eq!(gauge(&recorder.chunks), stats.chunks);
+            assert_eq!(gauge(&recorder.reserved), stats.capacity);
+
+            // Retired chunks are released.
+            unsafe { blink.reset_epoch(1) };
+            assert_eq!(gauge(&recorder.chunks), 0);
+            assert_eq!(gauge(&recorder.reserved), 0);
+        }
+    });
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn test_tracing() {
+    use alloc::string::{String, ToString};
+    use std::sync::{Arc, Mutex};
+
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Event, Level, Metadata, Subscriber,
+    };
+
+    type Fields = Vec<(&'static str, String)>;
+
+    struct TestSubscriber {
+        level: Level,
+        events: Arc<Mutex<alloc::vec::Vec<Fields>>>,
+    }
+
+    struct FieldVisitor(Fields);
+
+    impl Visit for FieldVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn core::fmt::Debug) {
+            self.0.push((field.name(), alloc::format!("{:?}", value)));
+        }
+    }
+
+    impl Subscriber for TestSubscriber {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            *metadata.level() <= self.level
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut visitor = FieldVisitor(Fields::new());
+            event.record(&mut visitor);
+            self.events.lock().unwrap().push(visitor.0);
+        }
+
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    let reset = |level| {
+        let events = Arc::new(Mutex::new(alloc::vec::Vec::new()));
+        let subscriber = TestSubscriber {
+            level,
+            events: events.clone(),
+        };
+        tracing::subscriber::with_default(subscriber, || {
+            let mut blink = Blink::new();
+            blink.put(1u32);
+            blink.emplace().from_iter((0..3).map(|i| i.to_string()));
+            blink.reset();
+        });
+        let events = events.lock().unwrap().clone();
+        events
+    };
+
+    let events = reset(Level::DEBUG);
+    assert_eq!(events.len(), 1);
+    let field = |name| {
+        events[0]
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.clone())
+    };
+    assert_eq!(field("message").as_deref(), Some("Blink reset"));
+    assert_eq!(field("drop_count").as_deref(), Some("1"));
+    assert_eq!(field("chunk_count").as_deref(), Some("1"));
+    assert!(field("used_bytes").unwrap().parse::<usize>().unwrap() > 0);
+
+    // Event is filtered out.
+    assert!(reset(Level::INFO).is_empty());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_local_chunk_donation() {
+    use crate::sync::SyncBlinkAlloc;
+
+    let mut blink = SyncBlinkAlloc::new();
+
+    {
+        let local = blink.local();
+        local.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    }
+    // Proxy chunks are allocated from the shared arena.
+    let used = blink.stats().used;
+
+    {
+        let local = blink.local();
+        local.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    }
+
+    // Second proxy reuses chunk of the first one.
+    assert_eq!(blink.stats().used, used);
+
+    // Proxy that asks for larger chunk does not take the spare one.
+    let local = blink.local_with(1 << 20);
+    local.allocate(Layout::new::<u8>()).unwrap();
+    assert!(blink.stats().used >= used + (1 << 20));
+    drop(local);
+
+    blink.reset();
+    let local = blink.local();
+    local.allocate(Layout::new::<[u8; 100]>()).unwrap();
+}
+
+#[test]
+fn test_emplace_cell() {
+    let mut blink = Blink::new();
+
+    let value = blink.emplace_cell().value(1u32);
+    let alias = value;
+    alias.set(alias.get() * 10);
+    value.set(value.get() + 1);
+    assert_eq!(alias.get(), 11);
+
+    let slice = blink.emplace_cell().from_iter(0..4u32);
+    for cell in slice {
+        cell.set(cell.get() * 2);
+    }
+    assert_eq!(slice[3].get(), 6);
+
+    let s = alloc::string::String::from("borrowed");
+    let cell = blink.emplace_cell().value(None);
+    cell.set(Some(&s));
+    assert_eq!(cell.get(), Some(&s));
+
+    blink.reset();
+}
+
+#[test]
+fn test_emplace_option() {
+    let dropped = alloc::rc::Rc::new(Cell::new(0));
+
+    let mut blink = Blink::new();
+    let value = blink.emplace_option(Some(dropped.clone())).unwrap();
+    assert_eq!(alloc::rc::Rc::strong_count(value), 2);
+    assert!(blink.emplace_option(None::<u32>).is_none());
+
+    let value = blink.try_emplace_option(Some(42u32)).unwrap().unwrap();
+    assert_eq!(*value, 42);
+    assert!(blink.try_emplace_option(None::<u32>).unwrap().is_none());
+
+    blink.reset();
+    assert_eq!(alloc::rc::Rc::strong_count(&dropped), 1);
+
+    let blink = Blink::new_in(BlinkAlloc::new_in(NoMemory));
+    match blink.try_emplace_option(Some(dropped.clone())) {
+        Err(inner) => assert!(alloc::rc::Rc::ptr_eq(&inner, &dropped)),
+        Ok(_) => panic!("allocation must fail"),
+    }
+    assert!(blink.try_emplace_option(None::<u32>).unwrap().is_none());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_frame_blink_aliases() {
+    use crate::{sync::SyncBlinkAlloc, BlinkAllocator, LocalFrameBlink, SyncFrameBlink};
+
+    let mut blink: SyncFrameBlink = Blink::new_in(SyncBlinkAlloc::new());
+    assert_eq!(*blink.put(1), 1);
+    blink.reset();
+
+    let shared = SyncBlinkAlloc::new();
+    let mut local: LocalFrameBlink<'_> = Blink::new_in(shared.local());
+    assert!(local.is_empty());
+    assert_eq!(*local.put(2), 2);
+    assert!(!local.is_empty());
+    assert_eq!(local.allocator().blink_stats().unwrap().chunks, 1);
+    local.reset();
+    assert!(local.is_empty());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_local_mut_grow() {
+    use crate::sync::SyncBlinkAlloc;
+
+    let shared = SyncBlinkAlloc::new();
+    let mut local = shared.local();
+
+    {
+        let mut vec = Vec::new_in(&mut local);
+        for i in 0..1000u32 {
+            vec.push(i);
+        }
+        assert!(vec.iter().copied().eq(0..1000));
+    }
+
+    let alloc = &mut local;
+    unsafe {
+        let old_layout = Layout::new::<[u8; 16]>();
+        let new_layout = Layout::new::<[u8; 64]>();
+
+        let ptr = alloc.allocate(old_layout).unwrap().cast::<u8>();
+        core::ptr::write_bytes(ptr.as_ptr(), 0xFF, 16);
+
+        let grown = alloc.grow_zeroed(ptr, old_layout, new_layout).unwrap();
+        let bytes = grown.as_ref();
+        assert!(bytes.len() >= 64);
+        assert!(bytes[..16].iter().all(|&b| b == 0xFF));
+        assert!(bytes[16..].iter().all(|&b| b == 0));
+
+        alloc.deallocate(grown.cast(), new_layout);
+    }
+
+    local.reset();
+}
+
+#[test]
+fn test_raw_handle() {
+    let handle = BlinkAlloc::into_raw(BlinkAlloc::new());
+
+    #[cfg(feature = "ffi")]
+    unsafe {
+        use crate::blink_alloc_alloc_raw;
+
+        let ptr = blink_alloc_alloc_raw(handle, 24, 8);
+        assert!(!ptr.is_null());
+        assert_eq!(ptr as usize % 8, 0);
+        core::ptr::write_bytes(ptr, 1, 24);
+
+        assert!(blink_alloc_alloc_raw(handle, 8, 3).is_null());
+    }
+
+    let mut blink = unsafe { BlinkAlloc::<Global>::from_raw(handle) };
+    blink.allocate(Layout::new::<u64>()).unwrap();
+    blink.reset();
+}
+
+#[test]
+fn test_reset_zeroed() {
+    let mut blink = BlinkAlloc::with_chunk_size(1024);
+
+    let first = blink.allocate(Layout::new::<[u8; 64]>()).unwrap();
+    unsafe {
+        core::ptr::write_bytes(first.as_ptr().cast::<u8>(), 0xAA, 64);
+    }
+
+    // Memory given back by deallocation is zeroed too.
+    let second = blink.allocate(Layout::new::<[u8; 64]>()).unwrap();
+    unsafe {
+        core::ptr::write_bytes(second.as_ptr().cast::<u8>(), 0xBB, 64);
+        blink.deallocate(second.cast(), 64);
+    }
+
+    // Spill into another chunk.
+    blink.allocate(Layout::new::<[u8; 4096]>()).unwrap();
+
+    blink.reset_zeroed();
+    assert_eq!(blink.chunks().count(), 1);
+
+    for chunk in blink.chunks() {
+        let bytes = unsafe { core::slice::from_raw_parts(chunk.base().as_ptr(), chunk.cap()) };
+        assert!(bytes.iter().all(|&b| b == 0));
+    }
+}
+
+#[test]
+fn test_on_reset() {
+    use alloc::sync::Arc;
+
+    let mut blink = BlinkAlloc::new();
+    let order = Arc::new(AtomicUsize::new(0));
+
+    for i in 1..=3 {
+        let order = order.clone();
+        blink.on_reset(move || {
+            // Runs in reverse order of registration.
+            assert_eq!(order.fetch_add(1, Ordering::Relaxed), 3 - i);
+        });
+    }
+    assert_eq!(order.load(Ordering::Relaxed), 0);
+
+    blink.reset();
+    assert_eq!(order.load(Ordering::Relaxed), 3);
+
+    // Finalizers run only once.
+    blink.reset();
+    assert_eq!(order.load(Ordering::Relaxed), 3);
+
+    // Finalizers run on drop.
+    let flag = Arc::new(AtomicUsize::new(0));
+    let set_flag = flag.clone();
+    blink.on_reset(move || set_flag.store(1, Ordering::Relaxed));
+    drop(blink);
+    assert_eq!(flag.load(Ordering::Relaxed), 1);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_cache_push_reset() {
+    use crate::cache::BlinkAllocCache;
+
+    let cache = BlinkAllocCache::new();
+
+    let blink = BlinkAlloc::new();
+    blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    cache.push_reset(blink);
+
+    let blink = cache.pop().unwrap();
+    assert_eq!(blink.used_bytes(), 0);
+    assert_eq!(blink.chunks().count(), 1);
+
+    blink.allocate(Layout::new::<[u8; 100]>()).unwrap();
+    cache.push_reset_all(blink);
+
+    let blink = cache.pop().unwrap();
+    assert_eq!(blink.chunks().count(), 0);
+}
+
+#[test]
+fn test_emplace_reserve() {
+    let allocator = CountingGlobal::new();
+    let mut blink = Blink::new_in(BlinkAlloc::new_in(&allocator));
+
+    // Iterator without size hint.
+    let mut next = 0u32;
+    let iter = core::iter::from_fn(|| {
+        next += 1;
+        (next <= 1000).then_some(next)
+    });
+
+    let emplace = blink.emplace();
+    emplace.reserve(2000);
+    let slice = emplace.from_iter(iter);
+    assert_eq!(slice.len(), 1000);
+    assert!(slice.iter().copied().eq(1..=1000));
+    assert_eq!(allocator.allocations(), 1);
+
+    blink.reset();
+}
+
+#[test]
+fn test_huge_alloc_threshold() {
+    let allocator = CountingGlobal::new();
+    let mut blink =
+        BlinkAlloc::with_chunk_size_in(1024, &allocator).with_huge_alloc_threshold(4096);
+
+    for _ in 0..3 {
+        let small = blink.allocate(Layout::new::<[u64; 8]>()).unwrap();
+        let huge = blink.allocate(Layout::new::<[u64; 1024]>()).unwrap();
+        assert!(huge.len() >= 8192);
+        assert_eq!(huge.as_ptr().cast::<u8>() as usize % align_of::<u64>(), 0);
+        unsafe {
+            core::ptr::write_bytes(small.as_ptr().cast::<u8>(), 0xAA, 64);
+            core::ptr::write_bytes(huge.as_ptr().cast::<u8>(), 0xBB, 8192);
+        }
+
+        // Huge allocation is not linked into chunk list.
+        assert_eq!(blink.chunks().count(), 1);
+        assert!(blink.last_chunk_cap() < 8192);
+        assert_eq!(allocator.live(), 2);
+
+        // Growing huge allocation keeps it out of chunks.
+        let grown = unsafe {
+            blink
+                .resize(
+                    huge.cast(),
+                    Layout::new::<[u64; 1024]>(),
+                    Layout::new::<[u64; 2048]>(),
+                )
+                .unwrap()
+        };
+        let bytes = unsafe { core::slice::from_raw_parts(grown.as_ptr().cast::<u8>(), 8192) };
+        assert!(bytes.iter().all(|&b| b == 0xBB));
+        assert_eq!(blink.chunks().count(), 1);
+        assert_eq!(allocator.live(), 3);
+
+        // Huge allocations are freed while small ones reuse the chunk.
+        blink.reset();
+        assert_eq!(allocator.live(), 1);
+    }
+
+    drop(blink);
+    assert_eq!(allocator.live(), 0);
+}
+
+#[test]
+fn test_fill_blink_slice() {
+    use crate::blink::IteratorExt;
+
+    let blink = Blink::new();
+
+    // Iterator longer than the slice.
+    let slice = blink.uninit_slice::<u32>(4);
+    let (filled, mut rest) = (0..10u32).fill_blink_slice(slice);
+    assert_eq!(filled, [0, 1, 2, 3]);
+    assert_eq!(rest.next(), Some(4));
+
+    // Iterator shorter than the slice.
+    let slice = blink.uninit_slice::<u32>(8);
+    let (filled, mut rest) = (0..3u32).fill_blink_slice(slice);
+    assert_eq!(filled, [0, 1, 2]);
+    assert_eq!(rest.next(), None);
+
+    let slice = blink.try_uninit_slice::<u32>(0).unwrap();
+    let (filled, mut rest) = (0..3u32).fill_blink_slice(slice);
+    assert!(filled.is_empty());
+    assert_eq!(rest.next(), Some(0));
+}
+
+#[test]
+fn test_fill_blink_slice_write() {
+    use crate::blink::IteratorExt;
+
+    let blink = Blink::new();
+
+    let slice = blink.uninit_slice::<u32>(4);
+    let (filled, _) = (0..4u32).fill_blink_slice(slice);
+    for value in filled.iter_mut() {
+        *value *= 2;
+    }
+    filled[3] = 7;
+    assert_eq!(filled, [0, 2, 4, 7]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_fill_blink_slice_panic() {
+    use crate::blink::IteratorExt;
+
+    let blink = Blink::new();
+    let value = alloc::rc::Rc::new(());
+
+    let slice = blink.uninit_slice::<alloc::rc::Rc<()>>(8);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _ = (0..8)
+            .map(|i| {
+                assert!(i < 5);
+                value.clone()
+            })
+            .fill_blink_slice(slice);
+    }));
+    assert!(result.is_err());
+
+    // Written items are dropped on panic.
+    assert_eq!(alloc::rc::Rc::strong_count(&value), 1);
+}
+
+#[test]
+fn test_allocated_since_reset() {
+    let mut blink = BlinkAlloc::with_chunk_size(1024);
+
+    // `u8` followed by `u64` wastes 7 bytes on padding.
+    blink.allocate(Layout::new::<u8>()).unwrap();
+    blink.allocate(Layout::new::<u64>()).unwrap();
+    blink.allocate(Layout::new::<u8>()).unwrap();
+    blink.allocate(Layout::new::<u64>()).unwrap();
+    assert_eq!(blink.allocated_since_reset(), 18);
+    assert_eq!(blink.stats().used, 32);
+
+    // Growth of resized allocation is counted.
+    let ptr = blink.allocate(Layout::new::<[u8; 16]>()).unwrap();
+    unsafe {
+        blink
+            .resize(
+                ptr.cast(),
+                Layout::new::<[u8; 16]>(),
+                Layout::new::<[u8; 64]>(),
+            )
+            .unwrap();
+    }
+    assert_eq!(blink.allocated_since_reset(), 82);
+
+    blink.reset();
+    assert_eq!(blink.allocated_since_reset(), 0);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_allocated_since_reset() {
+    let mut blink = crate::sync::SyncBlinkAlloc::with_chunk_size_in(1024, Global);
+
+    blink.allocate(Layout::new::<u8>()).unwrap();
+    blink.allocate(Layout::new::<u64>()).unwrap();
+    assert_eq!(blink.allocated_since_reset(), 9);
+    assert_eq!(blink.stats().used, 16);
+
+    // Proxy chunk and allocations are not counted.
+    let local = blink.local();
+    local.allocate(Layout::new::<u32>()).unwrap();
+    drop(local);
+    assert_eq!(blink.allocated_since_reset(), 9);
+
+    blink.reset();
+    assert_eq!(blink.allocated_since_reset(), 0);
+
+    // Counters of all shards are summed.
+    let mut blink = crate::sync::SyncBlinkAlloc::new_sharded(4);
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            let blink = &blink;
+            scope.spawn(move || {
+                blink.allocate(Layout::new::<u64>()).unwrap();
+            });
+        }
+    });
+    assert_eq!(blink.allocated_since_reset(), 64);
+
+    blink.reset();
+    assert_eq!(blink.allocated_since_reset(), 0);
+}
+
+#[test]
+fn test_for_each_used_byte_range() {
+    let mut blink = BlinkAlloc::with_chunk_size(1024).with_huge_alloc_threshold(4096);
+
+    let mut ranges = Vec::new();
+    blink.for_each_used_byte_range(|base, len| ranges.push((base, len)));
+    assert!(ranges.is_empty());
+
+    let small = blink.allocate(Layout::new::<[u8; 16]>()).unwrap();
+    // Spill into another chunk.
+    let large = blink.allocate(Layout::new::<[u8; 2048]>()).unwrap();
+    let huge = blink.allocate(Layout::new::<[u8; 8192]>()).unwrap();
+
+    blink.for_each_used_byte_range(|base, len| ranges.push((base, len)));
+    assert_eq!(ranges.len(), 3);
+
+    let chunks = blink.chunks().collect::<Vec<_>>();
+    for (chunk, &(base, len)) in chunks.iter().zip(&ranges) {
+        assert_eq!(chunk.base().as_ptr().cast_const(), base);
+        assert_eq!(chunk.used(), len);
+    }
+
+    let covers = |ptr: NonNull<[u8]>, size: usize| {
+        let ptr = ptr.as_ptr().cast::<u8>().cast_const();
+        ranges
+            .iter()
+            .any(|&(base, len)| base <= ptr && ptr as usize + size <= base as usize + len)
+    };
+    assert!(covers(small, 16));
+    assert!(covers(large, 2048));
+    assert!(covers(huge, 8192));
+
+    // Retained chunk is empty after reset.
+    blink.reset();
+    ranges.clear();
+    blink.for_each_used_byte_range(|base, len| ranges.push((base, len)));
+    assert!(ranges.is_empty());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_for_each_used_byte_range() {
+    let blink = crate::sync::SyncBlinkAlloc::with_chunk_size_in(1024, Global);
+
+    blink.allocate(Layout::new::<[u8; 16]>()).unwrap();
+    blink.allocate(Layout::new::<[u8; 2048]>()).unwrap();
+
+    let mut ranges = Vec::new();
+    blink.for_each_used_byte_range(|base, len| ranges.push((base, len)));
+    assert_eq!(ranges.len(), 2);
+    assert_eq!(ranges.iter().map(|&(_, len)| len).sum::<usize>(), 2064);
+}
+
+#[test]
+fn test_emplace_value_raw() {
+    struct Value {
+        data: u64,
+        dropped: alloc::rc::Rc<Cell<bool>>,
+    }
+
+    impl Drop for Value {
+        fn drop(&mut self) {
+            self.dropped.set(true);
+        }
+    }
+
+    extern "C" fn read_back(ptr: *mut core::ffi::c_void) -> u64 {
+        unsafe { (*ptr.cast::<Value>()).data }
+    }
+
+    let dropped = alloc::rc::Rc::new(Cell::new(false));
+
+    let mut blink = Blink::new();
+    let ptr = blink.emplace().value_raw(Value {
+        data: 0xDEAD_BEEF,
+        dropped: dropped.clone(),
+    });
+    assert_eq!(ptr as usize % align_of::<Value>(), 0);
+    assert_eq!(read_back(ptr.cast()), 0xDEAD_BEEF);
+
+    let ptr = blink.emplace_no_drop().try_value_raw(7u32).unwrap();
+    assert_eq!(unsafe { *ptr }, 7);
+
+    assert!(!dropped.get());
+    blink.reset();
+    assert!(dropped.get());
+}
+
+#[test]
+fn test_small_chunk_size_bumped() {
+    for chunk_size in [0, 1, 16, 63] {
+        let blink = BlinkAlloc::with_chunk_size(chunk_size);
+        for _ in 0..16 {
+            blink.allocate(Layout::new::<u32>()).unwrap();
+        }
+
+        // Single chunk fits all allocations.
+        assert_eq!(blink.chunks().count(), 1);
+        assert!(blink.last_chunk_cap() >= 64);
+    }
+}
+
+#[test]
+fn test_blink_allocator_stats() {
+    use crate::api::BlinkAllocator;
+
+    fn stats<A: BlinkAllocator>(alloc: &A) -> Option<crate::BlinkStats> {
+        alloc.blink_stats()
+    }
+
+    let mut blink = BlinkAlloc::new();
+    blink.allocate(Layout::new::<[u8; 128]>()).unwrap();
+
+    let expected = blink.stats();
+    assert!(expected.used >= 128);
+    assert_eq!(stats(&blink), Some(expected));
+    assert_eq!(stats(&&blink), Some(expected));
+    assert_eq!(stats(&&mut blink), Some(expected));
+}
+
+struct SetOnDrop<'a>(&'a Cell<bool>);
+
+impl Drop for SetOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.set(true);
+    }
+}
+
+#[test]
+fn test_drop_list_add_while_dropping() {
+    use crate::drop_list::{DropItem, DropList};
+    use core::{cell::UnsafeCell, mem::MaybeUninit};
+
+    struct AddOnDrop<'a> {
+        list: &'a DropList,
+        slot: &'a UnsafeCell<MaybeUninit<DropItem<SetOnDrop<'a>>>>,
+        dropped: &'a Cell<bool>,
+    }
+
+    impl Drop for AddOnDrop<'_> {
+        fn drop(&mut self) {
+            let ptr = NonNull::new(self.slot.get()).unwrap().cast();
+            unsafe {
+                let item = DropItem::init_value(ptr, SetOnDrop(self.dropped), |slot, value| {
+                    slot.write(value);
+                });
+                self.list.add(item);
+            }
+        }
+    }
+
+    let dropped = Cell::new(false);
+    let inner_slot = UnsafeCell::new(MaybeUninit::uninit());
+    let mut outer_slot = MaybeUninit::<DropItem<AddOnDrop>>::uninit();
+    let list = DropList::new();
+
+    unsafe {
+        let item = DropItem::init_value(
+            NonNull::from(&mut outer_slot).cast(),
+            AddOnDrop {
+                list: &list,
+                slot: &inner_slot,
+                dropped: &dropped,
+            },
+            |slot, value| {
+                slot.write(value);
+            },
+        );
+        list.add(item);
+    }
+
+    // Item added by dropped value is dropped by the same reset.
+    assert_eq!(unsafe { list.reset_unchecked() }, 2);
+    assert!(dropped.get());
+    assert_eq!(unsafe { list.reset_unchecked() }, 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_drop_list_panic_safety() {
+    use alloc::rc::Rc;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    struct PanicOnDrop(bool);
+
+    impl Drop for PanicOnDrop {
+        fn drop(&mut self) {
+            if self.0 {
+                panic!("PanicOnDrop panicked");
+            }
+        }
+    }
+
+    let dropped = Rc::new(Cell::new(0));
+    let mut blink = Blink::new();
+
+    for ordered in [false, true] {
+        dropped.set(0);
+        blink.put((DropCounter(dropped.clone()), PanicOnDrop(false)));
+        blink.put((DropCounter(dropped.clone()), PanicOnDrop(true)));
+        blink.put((DropCounter(dropped.clone()), PanicOnDrop(false)));
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            if ordered {
+                blink.ordered_reset();
+            } else {
+                blink.reset();
+            }
+        }));
+        assert!(result.is_err());
+
+        // Values after the panicking one are dropped too.
+        assert_eq!(dropped.get(), 3);
+    }
+
+    // Nothing is dropped twice.
+    blink.put((DropCounter(dropped.clone()), PanicOnDrop(false)));
+    blink.reset();
+    assert_eq!(dropped.get(), 4);
+
+    let scope = blink.open_scope();
+    scope.put((DropCounter(dropped.clone()), PanicOnDrop(false)));
+    scope.put((DropCounter(dropped.clone()), PanicOnDrop(true)));
+    scope.put((DropCounter(dropped.clone()), PanicOnDrop(false)));
+    let result = catch_unwind(AssertUnwindSafe(|| drop(scope)));
+    assert!(result.is_err());
+    assert_eq!(dropped.get(), 7);
+
+    blink.reset();
+    assert_eq!(dropped.get(), 7);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "cannot be reset while its values are being dropped")]
+fn test_drop_list_reset_while_dropping() {
+    use crate::drop_list::{DropItem, DropList};
+    use core::mem::MaybeUninit;
+
+    struct ResetOnDrop<'a>(&'a DropList);
+
+    impl Drop for ResetOnDrop<'_> {
+        fn drop(&mut self) {
+            unsafe {
+                self.0.reset_unchecked();
+            }
+        }
+    }
+
+    let mut slot = MaybeUninit::<DropItem<ResetOnDrop>>::uninit();
+    let list = DropList::new();
+
+    unsafe {
+        let item = DropItem::init_value(
+            NonNull::from(&mut slot).cast(),
+            ResetOnDrop(&list),
+            |slot, value| {
+                slot.write(value);
+            },
+        );
+        list.add(item);
+        list.reset_unchecked();
+    }
+}
+
+#[test]
+fn test_emplace_then_init() {
+    struct Node {
+        this: *const Node,
+        value: u32,
+    }
+
+    let mut blink = Blink::new();
+    let node = unsafe {
+        blink.emplace_then_init(|slot: &mut core::mem::MaybeUninit<Node>| {
+            let this = slot.as_ptr();
+            slot.write(Node { this, value: 42 });
+        })
+    };
+    assert_eq!(node.this, &*node as *const Node);
+    assert_eq!(node.value, 42);
+
+    struct Flag(alloc::rc::Rc<Cell<bool>>);
+
+    impl Drop for Flag {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    let dropped = alloc::rc::Rc::new(Cell::new(false));
+    let flag = unsafe {
+        blink
+            .try_emplace_then_init(|slot| {
+                slot.write(Flag(dropped.clone()));
+            })
+            .unwrap()
+    };
+    assert!(!flag.0.get());
+    blink.reset();
+    assert!(dropped.get());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_allocate_zeroed_batch() {
+    use crate::sync::SyncBlinkAlloc;
+
+    let mut blink = SyncBlinkAlloc::new();
+    let layout = Layout::from_size_align(12, 8).unwrap();
+
+    for _ in 0..3 {
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    let block = blink.allocate_zeroed_batch(100, layout).unwrap();
+                    assert!(block.len() >= 1600);
+                    assert_eq!(block.as_ptr().cast::<u8>() as usize % 8, 0);
+
+                    let bytes = unsafe {
+                        core::slice::from_raw_parts_mut(block.as_ptr().cast::<u8>(), 1600)
+                    };
+                    assert!(bytes.iter().all(|&b| b == 0));
+
+                    // Dirty memory to be reused after reset.
+                    bytes.fill(0xAA);
+                });
+            }
+        });
+        blink.reset();
+    }
+
+    assert!(blink.allocate_zeroed_batch(usize::MAX, layout).is_err());
+    assert_eq!(blink.allocate_zeroed_batch(0, layout).unwrap().len(), 0);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_reset_epoch() {
+    use crate::sync::SyncBlinkAlloc;
+    use std::sync::Barrier;
+
+    const READERS: usize = 4;
+
+    let allocator = CountingGlobal::new();
+    let blink = SyncBlinkAlloc::new_in(&allocator);
+
+    // Readers allocated, then reclaimer retired chunks.
+    let allocated = Barrier::new(READERS + 1);
+    let retired = Barrier::new(READERS + 1);
+
+    std::thread::scope(|scope| {
+        for idx in 0..READERS {
+            let blink = &blink;
+            let (allocated, retired) = (&allocated, &retired);
+
+            scope.spawn(move || {
+                let guard = blink.pin_epoch();
+                assert_eq!(blink.epoch(), 0);
+
+                let ptr = blink.allocate(Layout::new::<usize>()).unwrap();
+                let ptr = ptr.as_ptr().cast::<usize>();
+                unsafe { ptr.write(idx) };
+
+                allocated.wait();
+                retired.wait();
+
+                // Memory of old epoch is valid while guard is alive.
+                assert_eq!(unsafe { ptr.read() }, idx);
+
+                drop(guard);
+
+                let _guard = blink.pin_epoch();
+                assert_eq!(blink.epoch(), 1);
+            });
+        }
+
+        allocated.wait();
+        assert!(allocator.live() > 0);
+
+        unsafe { blink.reset_epoch(1) };
+        assert_eq!(blink.epoch(), 1);
+        assert!(blink.chunks().next().is_none());
+
+        // Readers still pin epoch 0.
+        assert!(allocator.live() > 0);
+        retired.wait();
+    });
+
+    // All readers advanced past retirement epoch.
+    assert_eq!(allocator.live(), 0);
+
+    // Allocations of new epoch are served from new chunks.
+    blink.allocate(Layout::new::<usize>()).unwrap();
+    assert_eq!(allocator.live(), 1);
+
+    drop(blink);
+    assert_eq!(allocator.live(), 0);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_chunks_during_reclaim() {
+    use crate::sync::SyncBlinkAlloc;
+
+    let rounds = if cfg!(miri) { 10 } else { 1000 };
+
+    let allocator = CountingGlobal::new();
+    let blink = SyncBlinkAlloc::new_sharded_in(2, &allocator);
+
+    std::thread::scope(|scope| {
+        let blink = &blink;
+        scope.spawn(move || {
+            for _ in 0..rounds {
+                for chunk in blink.chunks() {
+                    assert!(chunk.used() <= chunk.cap());
+                }
+            }
+        });
+
+        for _ in 0..rounds {
+            blink.allocate(Layout::new::<[u8; 1024]>()).unwrap();
+            // Retired chunks are reclaimed right away, as no epoch is pinned.
+            unsafe { blink.reset_epoch(blink.epoch() + 1) };
+        }
+    });
+
+    drop(blink);
+    assert_eq!(allocator.live(), 0);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_reset_epoch_sharded() {
+    use crate::sync::SyncBlinkAlloc;
+
+    const SHARDS: usize = 4;
+
+    let allocator = CountingGlobal::new();
+    let blink = SyncBlinkAlloc::new_sharded_in(SHARDS, &allocator);
+
+    std::thread::scope(|scope| {
+        for _ in 0..SHARDS * 2 {
+            let blink = &blink;
+            scope.spawn(move || {
+                blink.allocate(Layout::new::<usize>()).unwrap();
+            });
+        }
+    });
+    assert!(allocator.live() > 0);
+
+    let guard = blink.pin_epoch();
+    unsafe { blink.reset_epoch(1) };
+
+    // Guard keeps chunks of all shards alive.
+    assert!(allocator.live() > 0);
+
+    drop(guard);
+    assert_eq!(allocator.live(), 0);
+}
+
+#[test]
+fn test_emplace_slice_default() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Default)]
+    struct Counted;
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut blink = Blink::new();
+    let strings = blink.emplace::<alloc::string::String>().slice_default(5);
+    assert_eq!(strings.len(), 5);
+    assert!(strings.iter().all(|s| s.is_empty()));
+    strings[2].push_str("blink");
+    assert_eq!(strings[2], "blink");
+
+    assert!(blink.emplace::<Counted>().slice_default(0).is_empty());
+    let counted = blink.emplace::<Counted>().try_slice_default(4).unwrap();
+    assert_eq!(counted.len(), 4);
+
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 0);
+    blink.reset();
+    assert_eq!(DROPPED.load(Ordering::Relaxed), 4);
+}
+
+#[cfg(feature = "oom-handler")]
+#[test]
+fn test_oom_handler() {
+    use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    static AVAILABLE: AtomicBool = AtomicBool::new(false);
+    static HANDLED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Scarce;
+
+    unsafe impl Allocator for Scarce {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            if AVAILABLE.load(Ordering::Relaxed) {
+                Global.allocate(layout)
+            } else {
+                Err(AllocError)
+            }
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    let mut blink = BlinkAlloc::new_in(Scarce);
+
+    // Default handler does nothing.
+    assert!(blink.allocate(Layout::new::<u32>()).is_err());
+
+    // Handler that frees memory makes retry succeed.
+    blink.set_oom_handler(|_| {
+        HANDLED.fetch_add(1, Ordering::Relaxed);
+        AVAILABLE.store(true, Ordering::Relaxed);
+    });
+    blink.allocate(Layout::new::<u32>()).unwrap();
+    assert_eq!(HANDLED.load(Ordering::Relaxed), 1);
+
+    // Allocations served from existing chunk don't call handler.
+    AVAILABLE.store(false, Ordering::Relaxed);
+    blink.allocate(Layout::new::<u32>()).unwrap();
+    assert_eq!(HANDLED.load(Ordering::Relaxed), 1);
+
+    // Retry happens only once.
+    blink.set_oom_handler(|layout| {
+        assert_eq!(layout, Layout::new::<[u8; 4096]>());
+        HANDLED.fetch_add(1, Ordering::Relaxed);
+    });
+    assert!(blink.allocate(Layout::new::<[u8; 4096]>()).is_err());
+    assert_eq!(HANDLED.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_try_allocate_in_current_chunk() {
+    let blink = BlinkAlloc::with_chunk_size(1024);
+
+    // Zero-sized allocations need no chunk.
+    assert!(blink
+        .try_allocate_in_current_chunk(Layout::new::<()>())
+        .is_some());
+    assert!(blink
+        .try_allocate_in_current_chunk(Layout::new::<u64>())
+        .is_none());
+    assert_eq!(blink.chunks().count(), 0);
+
+    blink.allocate(Layout::new::<u64>()).unwrap();
+    let cap = blink.last_chunk_cap();
+
+    let mut served = 0;
+    while let Some(ptr) = blink.try_allocate_in_current_chunk(Layout::new::<u64>()) {
+        assert_eq!(ptr.as_ptr().cast::<u8>() as usize % align_of::<u64>(), 0);
+        served += 1;
+    }
+
+    // Current chunk is exhausted, no new chunk allocated.
+    assert_eq!(served, cap / size_of::<u64>() - 1);
+    assert_eq!(blink.chunks().count(), 1);
+    assert_eq!(blink.allocated_since_reset(), cap);
+}
+
+#[test]
+fn test_display() {
+    use alloc::string::ToString;
+
+    use crate::human::HumanBytes;
+
+    assert_eq!(HumanBytes(0).to_string(), "0 B");
+    assert_eq!(HumanBytes(1023).to_string(), "1023 B");
+    assert_eq!(HumanBytes(1024).to_string(), "1.0 KB");
+    assert_eq!(HumanBytes(1536).to_string(), "1.5 KB");
+    assert_eq!(HumanBytes(12 * 1024 + 1000).to_string(), "13.0 KB");
+    assert_eq!(HumanBytes(1024 * 1024 - 1).to_string(), "1024.0 KB");
+    assert_eq!(HumanBytes(3 << 20).to_string(), "3.0 MB");
+    assert_eq!(HumanBytes(5 << 30).to_string(), "5.0 GB");
+
+    let mut blink = BlinkAlloc::with_chunk_size(256);
+    assert_eq!(
+        blink.to_string(),
+        "BlinkAlloc(0 chunks, 0 B used of 0 B capacity, min_chunk=256 B)"
+    );
+
+    blink.allocate(Layout::new::<[u8; 128]>()).unwrap();
+    let stats = blink.stats();
+    assert_eq!(
+        blink.to_string(),
+        alloc::format!(
+            "BlinkAlloc(1 chunk, {} used of {} capacity, min_chunk=256 B)",
+            HumanBytes(stats.used),
+            HumanBytes(stats.capacity),
+        )
+    );
+
+    blink.reset();
+}
+
+#[test]
+fn test_with_warmup() {
+    let allocator = CountingGlobal::new();
+    let mut blink = BlinkAlloc::with_warmup_in(4096, &allocator).unwrap();
+    assert_eq!(allocator.allocations(), 1);
+    assert!(blink.last_chunk_cap() >= 4096);
+    assert_eq!(blink.used_bytes(), 0);
+    assert_eq!(blink.allocated_since_reset(), 0);
+
+    for _ in 0..4 {
+        blink.allocate(Layout::new::<[u8; 1024]>()).unwrap();
+    }
+    assert_eq!(allocator.allocations(), 1);
+
+    blink.reset();
+    assert_eq!(blink.chunks().count(), 1);
+
+    // Warmup chunk does not raise minimum chunk size.
+    blink.reset_final();
+    blink.allocate(Layout::new::<u8>()).unwrap();
+    assert!(blink.last_chunk_cap() < 4096);
+    blink.reset_final();
+
+    let blink = BlinkAlloc::with_warmup_in_panic(64, &allocator);
+    assert_eq!(allocator.allocations(), 3);
+    assert_eq!(blink.chunks().count(), 1);
+
+    assert!(BlinkAlloc::with_warmup_in(64, NoMemory).is_err());
+    assert!(BlinkAlloc::with_warmup_in(usize::MAX, Global).is_err());
+}
+
+#[test]
+fn test_auto_reset_period() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static FINALIZED: AtomicUsize = AtomicUsize::new(0);
+
+    let mut blink = BlinkAlloc::with_chunk_size(1024);
+
+    // Safety: Allocated memory is not used.
+    unsafe { blink.set_auto_reset_period(3) };
+
+    // Finalizer takes one allocation.
+    blink.on_reset(|| {
+        FINALIZED.fetch_add(1, Ordering::Relaxed);
+    });
+    blink.allocate(Layout::new::<[u8; 512]>()).unwrap();
+    blink.allocate(Layout::new::<[u8; 512]>()).unwrap();
+    assert_eq!(blink.alloc_count_since_reset(), 3);
+    assert_eq!(FINALIZED.load(Ordering::Relaxed), 0);
+
+    for _ in 0..10 {
+        for count in 1..=3 {
+            blink.allocate(Layout::new::<[u8; 512]>()).unwrap();
+            assert_eq!(blink.alloc_count_since_reset(), count);
+        }
+        assert_eq!(FINALIZED.load(Ordering::Relaxed), 1);
+    }
+
+    // Memory usage is bounded by the period.
+    assert_eq!(blink.allocated_since_reset(), 3 * 512);
+    assert!(blink.chunks().count() <= 2);
+
+    blink.clear_auto_reset_period();
+    for count in 4..=10 {
+        blink.allocate(Layout::new::<[u8; 512]>()).unwrap();
+        assert_eq!(blink.alloc_count_since_reset(), count);
+    }
+
+    blink.reset();
+    assert_eq!(blink.alloc_count_since_reset(), 0);
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_counting_blink_alloc() {
+    use crate::{api::BlinkAllocator, counting::CountingBlinkAlloc};
+
+    let mut blink = CountingBlinkAlloc::new(BlinkAlloc::new());
+
+    {
+        let mut vec = Vec::new_in(&blink);
+        for i in 0..16u32 {
+            vec.push(i);
+        }
+        assert_eq!(vec.capacity(), 16);
+    }
+
+    // Capacity grows 4 -> 8 -> 16.
+    assert_eq!(blink.allocation_count(), 3);
+    assert_eq!(blink.byte_count(), 16 * size_of::<u32>());
+
+    {
+        let mut vec = Vec::with_capacity_in(16, &blink);
+        vec.extend(0..16u32);
+        vec.shrink_to_fit();
+    }
+    assert_eq!(blink.allocation_count(), 4);
+    assert_eq!(blink.byte_count(), 32 * size_of::<u32>());
+
+    assert_eq!(blink.reset_count(), 0);
+    blink.reset();
+    blink.reset();
+    assert_eq!(blink.reset_count(), 2);
+    assert_eq!(blink.blink_stats(), Some(blink.inner().stats()));
+}
+
+#[cfg(all(debug_assertions, feature = "debug-alloc-tracking"))]
+#[test]
+fn test_debug_alloc_tracking() {
+    fn live(blink: &BlinkAlloc) -> alloc::vec::Vec<(usize, u32)> {
+        let mut sites = alloc::vec::Vec::new();
+        blink.for_each_live_allocation(|addr, location| {
+            if location.file() == file!() {
+                sites.push((addr, location.line()));
+            }
+        });
+        sites
+    }
+
+    let mut blink = BlinkAlloc::new();
+    let layout = Layout::new::<[u8; 16]>();
+
+    let a = blink.allocate(layout).unwrap().cast::<u8>();
+    let a_line = line!() - 1;
+    let b = blink.allocate(layout).unwrap().cast::<u8>();
+    let b_line = line!() - 1;
+    blink.allocate(Layout::new::<()>()).unwrap();
+    assert_eq!(
+        live(&blink),
+        [(a.as_ptr() as usize, a_line), (b.as_ptr() as usize, b_line)]
+    );
+
+    unsafe { blink.deallocate(a, layout.size()) };
+    assert_eq!(live(&blink), [(b.as_ptr() as usize, b_line)]);
+
+    let grown = Layout::new::<[u8; 64]>();
+    let c = unsafe { blink.resize(b, layout, grown) }
+        .unwrap()
+        .cast::<u8>();
+    assert_eq!(live(&blink), [(c.as_ptr() as usize, b_line)]);
+
+    // Allocations made by collections are recorded too,
+    // with location in the collection code.
+    let mut vec = Vec::<u32, _>::new_in(&blink);
+    vec.push(1);
+    let mut count = 0;
+    blink.for_each_live_allocation(|_, _| count += 1);
+    assert_eq!(count, 2);
+    drop(vec);
+    assert_eq!(live(&blink), [(c.as_ptr() as usize, b_line)]);
+
+    blink.reset();
+    assert!(live(&blink).is_empty());
+}
+
+#[cfg(feature = "global-alloc")]
+#[test]
+fn test_global_alloc() {
+    use core::alloc::GlobalAlloc;
+
+    let mut blink = BlinkAlloc::new();
+    let layout = Layout::new::<[u32; 4]>();
+
+    unsafe {
+        let ptr = blink.alloc(layout).cast::<u32>();
+        assert!(!ptr.is_null());
+        ptr.write(42);
+
+        let ptr = blink.realloc(ptr.cast(), layout, 1024).cast::<u32>();
+        assert!(!ptr.is_null());
+        assert_eq!(ptr.read(), 42);
+        blink.dealloc(ptr.cast(), Layout::from_size_align(1024, 4).unwrap());
+
+        let zeroed = blink.alloc_zeroed(layout).cast::<[u32; 4]>();
+        assert_eq!(zeroed.read(), [0; 4]);
+        blink.dealloc(zeroed.cast(), layout);
+    }
+    blink.reset();
+
+    let blink = BlinkAlloc::new_in(NoMemory);
+    assert!(unsafe { blink.alloc(layout) }.is_null());
+}
+
+#[cfg(all(feature = "hugepage", target_os = "linux"))]
+#[test]
+fn test_hugepage_chunk() {
+    // Region of two huge pages, starting at huge page boundary.
+    let layout = Layout::from_size_align(4 << 20, 1 << 21).unwrap();
+    let region = Global.allocate(layout).unwrap();
+    let ptr = region.as_ptr().cast::<u8>();
+
+    // Too small to contain whole huge page.
+    assert!(!crate::arena::advise_huge_pages(ptr, 1 << 20));
+    assert!(!crate::arena::advise_huge_pages(
+        unsafe { ptr.add(1) },
+        (2 << 20) - 1
+    ));
+
+    // Kernel may be built without transparent huge pages support,
+    // so only check that advice is not rejected for other reasons.
+    if !crate::arena::advise_huge_pages(ptr, 4 << 20) {
+        let errno = unsafe { *libc::__errno_location() };
+        assert_eq!(errno, libc::EINVAL);
+    }
+    unsafe { Global.deallocate(region.cast(), layout) };
+
+    let mut blink = BlinkAlloc::new();
+    let big = Layout::from_size_align(8 << 20, 8).unwrap();
+    let ptr = blink.allocate(big).unwrap();
+    unsafe {
+        core::ptr::write_bytes(ptr.as_ptr().cast::<u8>(), 0xAB, big.size());
+        assert_eq!(*ptr.as_ptr().cast::<u8>().add(big.size() - 1), 0xAB);
+    }
+    blink.reset();
+}
+
+#[test]
+fn test_initial_chunk() {
+    use alloc::boxed::Box;
+    use core::mem::MaybeUninit;
+
+    let allocator = CountingGlobal::new();
+
+    // Buffer is reclaimed at the end so Miri doesn't report a leak.
+    let raw = Box::into_raw(Box::new([MaybeUninit::<u8>::uninit(); 1024]));
+    let bytes: &'static mut [MaybeUninit<u8>] = unsafe { &mut *raw };
+    let range = bytes.as_ptr_range();
+
+    let mut blink = BlinkAlloc::with_initial_chunk_in(bytes, &allocator);
+    assert!(blink.is_empty());
+    assert!(blink.last_chunk_cap() > 0);
+
+    let ptr = blink.allocate(Layout::new::<[u64; 16]>()).unwrap();
+    assert!(range.contains(&ptr.as_ptr().cast::<MaybeUninit<u8>>().cast_const()));
+    assert_eq!(allocator.allocations(), 0);
+
+    blink.reset();
+    let ptr = blink.allocate(Layout::new::<[u64; 16]>()).unwrap();
+    assert!(range.contains(&ptr.as_ptr().cast::<MaybeUninit<u8>>().cast_const()));
+
+    // Overflow goes to the allocator.
+    let ptr = blink.allocate(Layout::new::<[u8; 2048]>()).unwrap();
+    assert!(!range.contains(&ptr.as_ptr().cast::<MaybeUninit<u8>>().cast_const()));
+    assert_eq!(allocator.allocations(), 1);
+
+    // Buffer is skipped on deallocation.
+    blink.reset_final();
+    assert_eq!(allocator.deallocations(), 1);
+    assert_eq!(blink.last_chunk_cap(), 0);
+    drop(blink);
+    drop(unsafe { Box::from_raw(raw) });
+
+    // Too small buffer is ignored.
+    let raw = Box::into_raw(Box::new([MaybeUninit::<u8>::uninit(); 8]));
+    let blink = BlinkAlloc::with_initial_chunk_in(unsafe { &mut *raw }, &allocator);
+    assert_eq!(blink.last_chunk_cap(), 0);
+    drop(blink);
+    drop(unsafe { Box::from_raw(raw) });
+}
+
+#[test]
+fn test_reset_partial() {
+    let allocator = CountingGlobal::new();
+    let mut blink = BlinkAlloc::with_chunk_size_in(1024, &allocator);
+    let layout = Layout::new::<[u8; 1024]>();
+
+    let fill = |blink: &BlinkAlloc<&CountingGlobal>| {
+        for _ in 0..16 {
+            let ptr = blink.allocate(layout).unwrap();
+            unsafe { core::ptr::write_bytes(ptr.as_ptr().cast::<u8>(), 0xFF, layout.size()) };
+        }
+    };
+
+    fill(&blink);
+    assert!(allocator.live() > 3);
+    let newest = blink.chunks().map(|chunk| chunk.base()).collect::<Vec<_>>();
+
+    // Newest chunks are kept.
+    blink.reset_partial(3);
+    assert_eq!(allocator.live(), 3);
+    assert!(blink
+        .chunks()
+        .map(|chunk| chunk.base())
+        .eq(newest[..3].iter().copied()));
+    assert!(blink.is_empty());
+
+    // Kept chunks are reused before allocating new ones.
+    let capacity = blink.stats().capacity;
+    let mut used = 0;
+    while used + layout.size() <= capacity / 2 {
+        blink.allocate(layout).unwrap();
+        used += layout.size();
+    }
+    assert_eq!(allocator.live(), 3);
+
+    // Rollback deallocates reused chunks along with new ones.
+    blink.reset_partial(3);
+    blink.allocate(layout).unwrap();
+    let used = blink.used_bytes();
+    let checkpoint = blink.checkpoint();
+    fill(&blink);
+    assert!(blink.used_bytes() >= used + 16 * layout.size());
+    unsafe { blink.rollback(checkpoint) };
+    assert_eq!(blink.used_bytes(), used);
+    assert!(allocator.live() < 3);
+
+    // Remaining kept chunks are still reused after rollback,
+    // and used bytes follow chain order.
+    let live = allocator.live();
+    let current = blink.last_chunk_cap();
+    let newest_base = |blink: &BlinkAlloc<&CountingGlobal>| blink.chunks().next().unwrap().base();
+    let base = newest_base(&blink);
+    while newest_base(&blink) == base {
+        blink.allocate(layout).unwrap();
+    }
+    assert_eq!(allocator.live(), live);
+    assert!(blink.used_bytes() >= current + layout.size());
+    fill(&blink);
+
+    blink.reset_partial(1);
+    assert_eq!(allocator.live(), 1);
+
+    blink.reset_partial(0);
+    assert_eq!(allocator.live(), 0);
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn test_sync_sharded() {
+    use core::sync::atomic::{AtomicPtr, Ordering};
+
+    use crate::sync::SyncBlinkAlloc;
+
+    const THREADS: usize = 4;
+
+    let mut blink = SyncBlinkAlloc::new_sharded(THREADS);
+    let layout = Layout::new::<[u8; 64]>();
+
+    std::thread::scope(|scope| {
+        for i in 0..THREADS * 2 {
+            let blink = &blink;
+            scope.spawn(move || {
+                for _ in 0..100 {
+                    let ptr = blink.allocate(layout).unwrap();
+                    unsafe { core::ptr::write_bytes(ptr.as_ptr().cast::<u8>(), i as u8, 64) };
+                }
+            });
+        }
+    });
+
+    let stats = blink.stats();
+    assert!(stats.used >= THREADS * 2 * 100 * 64);
+    assert_eq!(stats.chunks, blink.chunks().count());
+
+    let mut used = 0;
+    blink.for_each_used_byte_range(|_, len| used += len);
+    assert_eq!(used, stats.used);
+
+    let mut sizes = alloc::vec::Vec::new();
+    blink.collect_chunk_sizes(&mut sizes);
+    assert_eq!(sizes.len(), stats.chunks);
+
+    // Each thread allocated from some shard, and all shards are reset.
+    blink.reset();
+    assert!(blink.is_empty());
+    assert!(blink.chunks().count() <= THREADS);
+
+    // Allocation may be resized from thread that uses another shard.
+    let ptr = blink.allocate(Layout::new::<u32>()).unwrap();
+    unsafe { ptr.as_ptr().cast::<u32>().write(42) };
+    let shared = AtomicPtr::new(ptr.as_ptr().cast::<u8>());
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            let ptr = NonNull::new(shared.load(Ordering::Acquire)).unwrap();
+            let new_ptr =
+                unsafe { blink.resize(ptr, Layout::new::<u32>(), Layout::new::<[u32; 4]>()) };
+            shared.store(new_ptr.unwrap().as_ptr().cast::<u8>(), Ordering::Release);
+        });
+    });
+    assert_eq!(unsafe { *shared.load(Ordering::Acquire).cast::<u32>() }, 42);
+
+    blink.reset_final();
+    assert_eq!(blink.chunks().count(), 0);
+}